@@ -1,4 +1,139 @@
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::path::PathBuf;
 
-pub fn main() -> Result<(), anyhow::Error> {
+use anyhow::anyhow;
+
+use osm_io::osm::filter::reference_complete::ReferenceCompleteFilter;
+use osm_io::osm::filter::tag_expression::TagExpression;
+use osm_io::osm::filter::tag_filter::{filter, TagFilter};
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::compression_type::CompressionType;
+
+const USAGE: &str = "\
+Usage: osmio <command> [options]
+
+Commands:
+  tags-filter <input.osm.pbf> -o <output.osm.pbf> [-e <expressions-file>] [-r [-t]] [<expression>...]
+      Write the objects that match any of the tag expressions, e.g. w/highway=primary,secondary
+      -r, --add-referenced   add all the objects referenced by the matching objects
+      -t, --remove-tags      write the referenced objects without tags, requires -r
+
+  help
+      Print this message";
+
+/// Command line arguments split into positional arguments, options with values and switches
+struct Arguments {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+    switches: HashSet<String>,
+}
+
+impl Arguments {
+    /// Parse arguments. Each option in with_values is followed by its value, switches have no
+    /// value. Short and long forms are given as "-o|--output" and stored under the long form
+    fn parse(args: &[String], with_values: &[&str], switches: &[&str]) -> Result<Arguments, anyhow::Error> {
+        let find = |names: &[&str], arg: &str| -> Option<String> {
+            names.iter()
+                .find(|option| option.split('|').any(|name| name == arg))
+                .map(|option| option.split('|').next_back().unwrap().to_string())
+        };
+        let mut arguments = Arguments {
+            positional: Vec::new(),
+            options: HashMap::new(),
+            switches: HashSet::new(),
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg.starts_with('-') && arg.len() > 1 {
+                if let Some(name) = find(with_values, arg) {
+                    let value = args.next()
+                        .ok_or(anyhow!("Missing value of option: {}", arg))?;
+                    arguments.options.insert(name, value.clone());
+                } else if let Some(name) = find(switches, arg) {
+                    arguments.switches.insert(name);
+                } else {
+                    return Err(anyhow!("Unknown option: {}\n{}", arg, USAGE));
+                }
+            } else {
+                arguments.positional.push(arg.clone());
+            }
+        }
+        Ok(arguments)
+    }
+
+    fn switch(&self, name: &str) -> bool {
+        self.switches.contains(name)
+    }
+
+    fn option(&self, name: &str) -> Option<&String> {
+        self.options.get(name)
+    }
+
+    fn required(&self, name: &str) -> Result<&String, anyhow::Error> {
+        self.option(name)
+            .ok_or(anyhow!("Missing option: {}\n{}", name, USAGE))
+    }
+}
+
+fn tags_filter(args: &[String]) -> Result<(), anyhow::Error> {
+    let arguments = Arguments::parse(
+        args,
+        &["-o|--output", "-e|--expressions"],
+        &["-r|--add-referenced", "-t|--remove-tags"],
+    )?;
+    let (input, expressions) = arguments.positional.split_first()
+        .ok_or(anyhow!("Missing input\n{}", USAGE))?;
+    let output = arguments.required("--output")?;
+
+    let mut tag_filter = match arguments.option("--expressions") {
+        None => {
+            TagFilter::default()
+        }
+        Some(path) => {
+            TagFilter::from_file(&PathBuf::from(path))?
+        }
+    };
+    for expression in expressions {
+        tag_filter.add_expression(expression.parse::<TagExpression>()?);
+    }
+    if tag_filter.expressions().is_empty() {
+        return Err(anyhow!("Missing tag expressions\n{}", USAGE));
+    }
+    if arguments.switch("--remove-tags") && !arguments.switch("--add-referenced") {
+        return Err(anyhow!("Option --remove-tags requires --add-referenced\n{}", USAGE));
+    }
+
+    let reader = pbf::reader::Reader::new(&PathBuf::from(input))?;
+    if !arguments.switch("--add-referenced") {
+        let count = filter(&reader, &tag_filter, PathBuf::from(output), CompressionType::Zlib)?;
+        println!("Wrote {} objects to {}", count, output);
+    } else {
+        let mut reference_complete = ReferenceCompleteFilter::new(tag_filter);
+        reference_complete.with_omit_referenced_tags(arguments.switch("--remove-tags"));
+        let counts = reference_complete.run(&reader, PathBuf::from(output), CompressionType::Zlib)?;
+        println!(
+            "Wrote {} matching and {} referenced objects to {}",
+            counts.matched,
+            counts.referenced,
+            output,
+        );
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn main() -> Result<(), anyhow::Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(|command| command.as_str()) {
+        Some("tags-filter") => {
+            tags_filter(&args[1..])
+        }
+        None | Some("help") | Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => {
+            Err(anyhow!("Unknown command: {}\n{}", command, USAGE))
+        }
+    }
+}
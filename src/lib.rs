@@ -26,6 +26,30 @@
 //! * implement apidb reader and writer - Done
 //! * provide basic filtering (see example below) - Done
 //! * convert between *.osm.pbf and apidb and vice versa - Done see examples.
+//! * implement OSM XML (*.osm, *.osm.gz, *.osm.bz2) reader and writer - Done
+//! * implement o5m and o5c reader and writer - Done
+//! * implement OPL reader and writer - Done
+//! * implement OSM JSON reader and writer - Done
+//! * implement node location index with in memory, memory mapped and disk backed storage - Done
+//! * read and write node locations on ways (LocationsOnWays) - Done
+//! * assemble areas from closed ways and multipolygon relations - Done
+//! * export GeoJSON and GeoJSONSeq features - Done
+//! * extract bounding boxes with simple, complete ways and smart strategies - Done
+//! * extract polygons from Osmosis *.poly files - Done
+//! * extract many regions at once from a JSON configuration - Done
+//! * filter by tag expressions, from the library and the osmio command line - Done
+//! * reference complete filtering that keeps the dependencies of matching objects - Done
+//! * fetch objects by id with their dependencies, using a block index when available (getid) - Done
+//! * referential integrity check of *.osm.pbf files and apidb dumps (check-refs) - Done
+//! * history time filter, point in time snapshots and reduced history for a time range - Done
+//! * per object version history with tag diffs, node moves and contributors - Done
+//! * revert a changeset to an osmChange from history data, with conflict reporting - Done
+//! * renumber object ids into dense sequences with a reusable disk backed id mapping - Done
+//! * extended file statistics in the format of osmium fileinfo JSON - Done
+//! * tag key, key=value and key combination statistics per object type as CSV or JSON - Done
+//! * contributor statistics - edits per user, per changeset and per day or week from history data - Done
+//! * changeset model, read and written in *.osm.pbf changeset groups - Done
+//! * changeset dump XML (changesets-latest.osm.bz2) reader and writer, from apidb changesets, tags and comments - Done
 //! * [S2](http://s2geometry.io/) indexing - index the entire OSM dataset by S2 cells for farther
 //! processing
 //! * context indexing - index the entire OSM dataset by relations between its objects. So, for
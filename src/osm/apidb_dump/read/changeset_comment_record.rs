@@ -0,0 +1,61 @@
+use chrono::NaiveDateTime;
+
+#[derive(Debug)]
+pub(crate) struct ChangesetCommentRecord {
+    id: i64,
+    changeset_id: i64,
+    author_id: i64,
+    body: String,
+    created_at: NaiveDateTime,
+    visible: bool,
+}
+
+impl ChangesetCommentRecord {
+    pub(crate) fn new(
+        id: i64,
+        changeset_id: i64,
+        author_id: i64,
+        body: String,
+        created_at: NaiveDateTime,
+        visible: bool,
+    ) -> ChangesetCommentRecord {
+        ChangesetCommentRecord {
+            id,
+            changeset_id,
+            author_id,
+            body,
+            created_at,
+            visible,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub(crate) fn changeset_id(&self) -> i64 {
+        self.changeset_id
+    }
+
+    pub(crate) fn author_id(&self) -> i64 {
+        self.author_id
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn body(&self) -> &String {
+        &self.body
+    }
+
+    pub(crate) fn take_body(&mut self) -> String {
+        std::mem::take(&mut self.body)
+    }
+
+    pub(crate) fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub(crate) fn visible(&self) -> bool {
+        self.visible
+    }
+}
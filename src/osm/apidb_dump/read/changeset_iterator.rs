@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use chrono::NaiveDateTime;
+use transient_btree_index::BtreeIndex;
+
+use crate::osm::apidb_dump::read::changeset_comment_record::ChangesetCommentRecord;
+use crate::osm::apidb_dump::read::changeset_tag_record::ChangesetTagRecord;
+use crate::osm::apidb_dump::read::element_iterator::ElementIterator;
+use crate::osm::apidb_dump::read::table_def::TableDef;
+use crate::osm::apidb_dump::read::table_reader::{TableIterator, TableReader};
+use crate::osm::apidb_dump::read::table_record::TableRecord;
+use crate::osm::model::changeset::{Changeset, ChangesetComment};
+use crate::osm::model::tag::Tag;
+
+/// Iterate over the changesets of an apidb dump with their tags and discussions
+///
+/// The changesets, changeset_tags and changeset_comments tables are sorted by changeset id and
+/// merged while iterating. Dumps without the changeset_tags or changeset_comments tables produce
+/// changesets without tags or comments. Only visible comments are included.
+///
+/// Changesets that close after the dump time are open.
+pub struct ChangesetIterator {
+    dump_time: NaiveDateTime,
+    user_index: BtreeIndex<i64, String>,
+    changesets_iterator: TableIterator,
+    changeset_tags_iterator: Option<TableIterator>,
+    changeset_comments_iterator: Option<TableIterator>,
+    next_changeset_tag_record: Option<ChangesetTagRecord>,
+    next_changeset_comment_record: Option<ChangesetCommentRecord>,
+}
+
+impl ChangesetIterator {
+    pub(crate) fn new(tables: HashMap<String, TableDef>, dump_time: NaiveDateTime) -> Result<ChangesetIterator, anyhow::Error> {
+        let user_index = ElementIterator::index_users(&tables)?;
+        let changesets_table_def = tables.get("public.changesets")
+            .ok_or(anyhow!("missing table definition for public.changesets"))?;
+        let changesets_iterator = TableReader::new(changesets_table_def)?.into_iter();
+        let changeset_tags_iterator = match tables.get("public.changeset_tags") {
+            None => {
+                None
+            }
+            Some(table_def) => {
+                Some(TableReader::new(table_def)?.into_iter())
+            }
+        };
+        let changeset_comments_iterator = match tables.get("public.changeset_comments") {
+            None => {
+                None
+            }
+            Some(table_def) => {
+                Some(TableReader::new(table_def)?.into_iter())
+            }
+        };
+        Ok(
+            ChangesetIterator {
+                dump_time,
+                user_index,
+                changesets_iterator,
+                changeset_tags_iterator,
+                changeset_comments_iterator,
+                next_changeset_tag_record: None,
+                next_changeset_comment_record: None,
+            }
+        )
+    }
+
+    fn user(&self, uid: i64) -> String {
+        self.user_index.get(&uid).ok().flatten().unwrap_or_default()
+    }
+
+    fn next_changeset_tag(&mut self) -> Option<ChangesetTagRecord> {
+        if let Some(changeset_tag_record) = self.next_changeset_tag_record.take() {
+            return Some(changeset_tag_record);
+        }
+        match self.changeset_tags_iterator.as_mut()?.next()? {
+            TableRecord::ChangesetTag { changeset_tag_record } => {
+                Some(changeset_tag_record)
+            }
+            _ => {
+                panic!("Found incorrect record type, not a TableRecord:ChangesetTag");
+            }
+        }
+    }
+
+    fn next_changeset_comment(&mut self) -> Option<ChangesetCommentRecord> {
+        if let Some(changeset_comment_record) = self.next_changeset_comment_record.take() {
+            return Some(changeset_comment_record);
+        }
+        match self.changeset_comments_iterator.as_mut()?.next()? {
+            TableRecord::ChangesetComment { changeset_comment_record } => {
+                Some(changeset_comment_record)
+            }
+            _ => {
+                panic!("Found incorrect record type, not a TableRecord:ChangesetComment");
+            }
+        }
+    }
+
+    /// Tags of the changeset. Tags of changesets that precede it, which are not in the changesets
+    /// table, are skipped
+    fn tags(&mut self, changeset_id: i64) -> Vec<Tag> {
+        let mut tags = Vec::new();
+        while let Some(mut changeset_tag_record) = self.next_changeset_tag() {
+            if changeset_tag_record.changeset_id() > changeset_id {
+                self.next_changeset_tag_record = Some(changeset_tag_record);
+                break;
+            }
+            if changeset_tag_record.changeset_id() == changeset_id {
+                tags.push(Tag::new(changeset_tag_record.take_k(), changeset_tag_record.take_v()));
+            }
+        }
+        tags
+    }
+
+    fn comments(&mut self, changeset_id: i64) -> Vec<ChangesetComment> {
+        let mut comments = Vec::new();
+        while let Some(mut changeset_comment_record) = self.next_changeset_comment() {
+            if changeset_comment_record.changeset_id() > changeset_id {
+                self.next_changeset_comment_record = Some(changeset_comment_record);
+                break;
+            }
+            if changeset_comment_record.changeset_id() == changeset_id && changeset_comment_record.visible() {
+                comments.push(
+                    ChangesetComment::new(
+                        changeset_comment_record.created_at().and_utc().timestamp_millis(),
+                        changeset_comment_record.author_id() as i32,
+                        self.user(changeset_comment_record.author_id()),
+                        changeset_comment_record.take_body(),
+                    )
+                );
+            }
+        }
+        comments
+    }
+}
+
+impl Iterator for ChangesetIterator {
+    type Item = Changeset;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.changesets_iterator.next()? {
+            TableRecord::Changeset { changeset_record } => {
+                let tags = self.tags(changeset_record.id());
+                let comments = self.comments(changeset_record.id());
+                let user = self.user(changeset_record.user_id());
+                Some(changeset_record.into_changeset(self.dump_time, user, tags, comments))
+            }
+            _ => {
+                panic!("Found incorrect record type, not a TableRecord:Changeset");
+            }
+        }
+    }
+}
@@ -1,14 +1,18 @@
 use chrono::NaiveDateTime;
 
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::changeset::{Changeset, ChangesetComment};
+use crate::osm::model::tag::Tag;
+
 #[derive(Debug)]
 pub(crate) struct ChangesetRecord {
     id: i64,
     user_id: i64,
     created_at: NaiveDateTime,
-    min_lat: i32,
-    max_lat: i32,
-    min_lon: i32,
-    max_lon: i32,
+    min_lat: Option<i32>,
+    max_lat: Option<i32>,
+    min_lon: Option<i32>,
+    max_lon: Option<i32>,
     closed_at: NaiveDateTime,
     num_changes: i32,
 }
@@ -19,10 +23,10 @@ impl ChangesetRecord {
         id: i64,
         user_id: i64,
         created_at: NaiveDateTime,
-        min_lat: i32,
-        max_lat: i32,
-        min_lon: i32,
-        max_lon: i32,
+        min_lat: Option<i32>,
+        max_lat: Option<i32>,
+        min_lon: Option<i32>,
+        max_lon: Option<i32>,
         closed_at: NaiveDateTime,
         num_changes: i32,
     ) -> ChangesetRecord {
@@ -53,22 +57,22 @@ impl ChangesetRecord {
     }
 
     #[allow(dead_code)]
-    pub(crate) fn min_lat(&self) -> i32 {
+    pub(crate) fn min_lat(&self) -> Option<i32> {
         self.min_lat
     }
 
     #[allow(dead_code)]
-    pub(crate) fn max_lat(&self) -> i32 {
+    pub(crate) fn max_lat(&self) -> Option<i32> {
         self.max_lat
     }
 
     #[allow(dead_code)]
-    pub(crate) fn min_lon(&self) -> i32 {
+    pub(crate) fn min_lon(&self) -> Option<i32> {
         self.min_lon
     }
 
     #[allow(dead_code)]
-    pub(crate) fn max_lon(&self) -> i32 {
+    pub(crate) fn max_lon(&self) -> Option<i32> {
         self.max_lon
     }
 
@@ -81,4 +85,71 @@ impl ChangesetRecord {
     pub(crate) fn num_changes(&self) -> i32 {
         self.num_changes
     }
+
+    /// Convert to [Changeset] with the user name, the tags and the discussion that are kept in the
+    /// users, changeset_tags and changeset_comments tables. A changeset is open if its closed_at
+    /// time is after the dump time, in which case closed_at is left out as in the planet changeset
+    /// dumps. The changeset has no bounding box if it has no changes
+    pub(crate) fn into_changeset(self, dump_time: NaiveDateTime, user: String, tags: Vec<Tag>, comments: Vec<ChangesetComment>) -> Changeset {
+        let bounding_box = match (self.min_lon, self.min_lat, self.max_lon, self.max_lat) {
+            (Some(min_lon), Some(min_lat), Some(max_lon), Some(max_lat)) => {
+                Some(
+                    BoundingBox::new(
+                        min_lon as f64 / 10000000.0f64,
+                        min_lat as f64 / 10000000.0f64,
+                        max_lon as f64 / 10000000.0f64,
+                        max_lat as f64 / 10000000.0f64,
+                    )
+                )
+            }
+            _ => {
+                None
+            }
+        };
+        let open = self.closed_at > dump_time;
+        let closed_at = (!open).then(|| self.closed_at.and_utc().timestamp_millis());
+        let mut changeset = Changeset::new(
+            self.id,
+            self.created_at.and_utc().timestamp_millis(),
+            closed_at,
+            open,
+            self.user_id as i32,
+            user,
+            bounding_box,
+            self.num_changes,
+            comments.len() as i32,
+            tags,
+        );
+        changeset.with_comments(comments);
+        changeset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use crate::osm::apidb_dump::read::changeset_record::ChangesetRecord;
+
+    #[test]
+    fn test_into_changeset() {
+        let created_at = NaiveDateTime::parse_from_str("2009-03-25 00:53:03", "%Y-%m-%d %H:%M:%S").unwrap();
+        let closed_at = NaiveDateTime::parse_from_str("2009-03-25 01:53:03", "%Y-%m-%d %H:%M:%S").unwrap();
+        let changeset = ChangesetRecord::new(907211, 18203, created_at, Some(-190554340), Some(-190500000), Some(-1699300000), Some(-1699218762), closed_at, 5)
+            .into_changeset(closed_at, String::new(), Vec::new(), Vec::new());
+        assert_eq!(changeset.id(), 907211);
+        assert_eq!(changeset.uid(), 18203);
+        assert_eq!(changeset.closed_at(), Some(changeset.created_at() + 3600000));
+        assert!(!changeset.open());
+        assert_eq!(changeset.num_changes(), 5);
+        let bounding_box = changeset.bounding_box().as_ref().unwrap();
+        assert_eq!(bounding_box.left(), -169.93);
+        assert_eq!(bounding_box.top(), -19.05);
+
+        let changeset = ChangesetRecord::new(907212, 18203, created_at, None, None, None, None, closed_at, 0)
+            .into_changeset(created_at, String::new(), Vec::new(), Vec::new());
+        assert!(changeset.bounding_box().is_none());
+        assert!(changeset.open());
+        assert!(changeset.closed_at().is_none());
+    }
 }
@@ -0,0 +1,42 @@
+#[derive(Debug)]
+pub(crate) struct ChangesetTagRecord {
+    changeset_id: i64,
+    k: String,
+    v: String,
+}
+
+impl ChangesetTagRecord {
+    pub(crate) fn new(
+        changeset_id: i64,
+        k: String,
+        v: String,
+    ) -> ChangesetTagRecord {
+        ChangesetTagRecord {
+            changeset_id,
+            k,
+            v,
+        }
+    }
+
+    pub(crate) fn changeset_id(&self) -> i64 {
+        self.changeset_id
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn k(&self) -> &String {
+        &self.k
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn v(&self) -> &String {
+        &self.v
+    }
+
+    pub(crate) fn take_k(&mut self) -> String {
+        std::mem::take(&mut self.k)
+    }
+
+    pub(crate) fn take_v(&mut self) -> String {
+        std::mem::take(&mut self.v)
+    }
+}
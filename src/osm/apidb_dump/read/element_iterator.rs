@@ -84,7 +84,7 @@ impl ElementIterator {
         Ok(changeset_user_index)
     }
 
-    fn index_users(tables: &HashMap<String, TableDef>) -> Result<BtreeIndex<i64, String>, anyhow::Error> {
+    pub(crate) fn index_users(tables: &HashMap<String, TableDef>) -> Result<BtreeIndex<i64, String>, anyhow::Error> {
         let mut user_index = BtreeIndex::<i64, String>::with_capacity(BtreeConfig::default(), 0)?;
         let reader = TableReader::new(tables.get("public.users").unwrap())?;
         for record in reader {
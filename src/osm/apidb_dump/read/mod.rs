@@ -8,6 +8,9 @@ mod node_tag_record;
 mod node_relation;
 mod node_relations_reader;
 mod changeset_record;
+mod changeset_tag_record;
+mod changeset_comment_record;
+mod changeset_iterator;
 mod way_record;
 mod way_node_record;
 mod way_tag_record;
@@ -4,9 +4,11 @@ use std::ops::{AddAssign, SubAssign};
 use std::path::PathBuf;
 use anyhow::{anyhow, Context};
 
+use chrono::{NaiveDate, NaiveDateTime};
 use regex::Regex;
 use text_file_sort::sort::Sort;
 
+use crate::osm::apidb_dump::read::changeset_iterator::ChangesetIterator;
 use crate::osm::apidb_dump::read::element_iterator::ElementIterator;
 use crate::osm::apidb_dump::read::table_def::TableDef;
 use crate::osm::apidb_dump::read::table_fields::TableFields;
@@ -14,6 +16,7 @@ use crate::osm::apidb_dump::read::table_fields::TableFields;
 /// Reader of apidb schema dump produced by pg_dump
 pub struct Reader {
     tables: HashMap<String, TableDef>,
+    dump_time: NaiveDateTime,
 }
 
 impl Reader {
@@ -22,7 +25,7 @@ impl Reader {
     /// * input_path - a path to directory that contains apidb schema dump produced by pg_dump with
     /// directory format. For example:
     /// ```bash
-    ///  pg_dump --host localhost --port 5432 --username openstreetmap --no-password --file /result --format d -d openstreetmap --compress 0 --table public.nodes --table public.node_tags --table public.ways --table public.way_nodes --table public.way_tags --table public.relations --table public.relation_members --table public.relation_tags --table public.changesets --table public.users
+    ///  pg_dump --host localhost --port 5432 --username openstreetmap --no-password --file /result --format d -d openstreetmap --compress 0 --table public.nodes --table public.node_tags --table public.ways --table public.way_nodes --table public.way_tags --table public.relations --table public.relation_members --table public.relation_tags --table public.changesets --table public.changeset_tags --table public.changeset_comments --table public.users
     /// ```
     /// The input is sorted using primary keys for each table found in input_path/toc.dat which may
     /// take significant time depending on the size of the input
@@ -34,6 +37,8 @@ impl Reader {
         let toc_path = input_path.join("toc.dat");
         let toc = fs::read(&toc_path)
             .with_context(|| anyhow!("path: {}", toc_path.display()))?;
+        let dump_time = Self::get_creation_time(&toc)
+            .with_context(|| anyhow!("path: {}", toc_path.display()))?;
         let raw_table_defs = Self::get_table_def_strings(&toc);
         // COPY public.node_tags (node_id, version, k, v) FROM stdin
         let re = Regex::new("^([^ ]+) \\((.+)\\)$").unwrap();
@@ -64,6 +69,7 @@ impl Reader {
         Ok(
             Reader {
                 tables,
+                dump_time,
             }
         )
     }
@@ -84,6 +90,36 @@ impl Reader {
         Ok(())
     }
 
+    /// The creation time in the header of toc.dat, as read by ReadHead of pg_backup_archiver.c.
+    /// pg_dump records the local time of the machine that made the dump, which is taken to be UTC
+    fn get_creation_time(toc: &[u8]) -> Result<NaiveDateTime, anyhow::Error> {
+        // PGDMP, major, minor and revision version, int size, offset size, format
+        if toc.len() < 11 || !toc.starts_with(b"PGDMP") {
+            return Err(anyhow!("Not a pg_dump archive header"));
+        }
+        let version = (toc[5], toc[6]);
+        if version < (1, 4) {
+            return Err(anyhow!("No creation time in pg_dump archive version {}.{}", version.0, version.1));
+        }
+        let int_size = toc[8] as usize;
+        // the compression is a single byte since 1.15 and an int before
+        let mut i = if version >= (1, 15) { 12 } else { 12 + int_size };
+        // struct tm fields: seconds, minutes, hours, day of month, months since January and years
+        // since 1900
+        let mut fields = [0_i64; 6];
+        for field in fields.iter_mut() {
+            let int = toc.get(i..i + 1 + int_size)
+                .ok_or(anyhow!("Truncated pg_dump archive header"))?;
+            let value = int[1..].iter().rev().fold(0_i64, |value, byte| value << 8 | *byte as i64);
+            *field = if int[0] == 0 { value } else { -value };
+            i += 1 + int_size;
+        }
+        let [second, minute, hour, day, month0, year] = fields;
+        NaiveDate::from_ymd_opt(year as i32 + 1900, month0 as u32 + 1, day as u32)
+            .and_then(|date| date.and_hms_opt(hour as u32, minute as u32, second as u32))
+            .ok_or(anyhow!("Invalid creation time in pg_dump archive header"))
+    }
+
     fn get_table_def_strings(toc: &[u8]) -> Vec<(String, String)> {
         // COPY public.node_tags (node_id, version, k, v) FROM stdin;......3838.dat
         let mut result: Vec<(String, String)> = Vec::new();
@@ -140,7 +176,31 @@ impl Reader {
     pub fn elements(&self) -> Result<ElementIterator, anyhow::Error> {
         ElementIterator::new(self.tables.clone())
     }
-}
 
+    /// Create iterator over the changesets with their tags and discussions
+    ///
+    /// The changesets are produced in the order of their ids. User names are taken from the users
+    /// table. Changesets that close after the [Reader::dump_time] are open.
+    pub fn changesets(&self) -> Result<ChangesetIterator, anyhow::Error> {
+        ChangesetIterator::new(self.tables.clone(), self.dump_time)
+    }
 
+    /// The creation time of the dump in milliseconds, from the header of toc.dat
+    pub fn dump_time(&self) -> i64 {
+        self.dump_time.and_utc().timestamp_millis()
+    }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_creation_time() {
+        let toc = fs::read("./tests/fixtures/history-niue-230109/toc.dat").unwrap();
+        let creation_time = Reader::get_creation_time(&toc).unwrap();
+        assert_eq!(creation_time, NaiveDate::from_ymd_opt(2023, 7, 5).unwrap().and_hms_opt(8, 39, 49).unwrap());
+        assert!(Reader::get_creation_time(&toc[0..20]).is_err());
+        assert!(Reader::get_creation_time(b"COPY public.nodes").is_err());
+    }
+}
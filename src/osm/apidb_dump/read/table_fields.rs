@@ -72,6 +72,19 @@ pub(crate) enum TableFields {
         closed_at: usize,
         num_changes: usize,
     },
+    ChangesetTags {
+        changeset_id: usize,
+        k: usize,
+        v: usize,
+    },
+    ChangesetComments {
+        id: usize,
+        changeset_id: usize,
+        author_id: usize,
+        body: usize,
+        created_at: usize,
+        visible: usize,
+    },
     Users {
         email: usize,
         id: usize,
@@ -127,6 +140,8 @@ impl TableFields {
             "public.relation_members",
             "public.relation_tags",
             "public.changesets",
+            "public.changeset_tags",
+            "public.changeset_comments",
             "public.users"];
 
         tables_of_interest.contains(&name)
@@ -240,6 +255,27 @@ impl TableFields {
                     }
                 )
             }
+            "public.changeset_tags" => {
+                Ok(
+                    TableFields::ChangesetTags {
+                        changeset_id: Self::index("changeset_id", &fields)?,
+                        k: Self::index("k", &fields)?,
+                        v: Self::index("v", &fields)?,
+                    }
+                )
+            }
+            "public.changeset_comments" => {
+                Ok(
+                    TableFields::ChangesetComments {
+                        id: Self::index("id", &fields)?,
+                        changeset_id: Self::index("changeset_id", &fields)?,
+                        author_id: Self::index("author_id", &fields)?,
+                        body: Self::index("body", &fields)?,
+                        created_at: Self::index("created_at", &fields)?,
+                        visible: Self::index("visible", &fields)?,
+                    }
+                )
+            }
             "public.users" => {
                 Ok(
                     TableFields::Users {
@@ -65,6 +65,17 @@ impl TablePkey {
                 // ADD CONSTRAINT changesets_pkey PRIMARY KEY (id);
                 key.push(Field::new(Self::index("id", &fields)?, FieldType::Integer).with_str_name("id"));
             }
+            "public.changeset_tags" => {
+                // ADD CONSTRAINT changeset_tags_pkey PRIMARY KEY (changeset_id, k);
+                key.push(Field::new(Self::index("changeset_id", &fields)?, FieldType::Integer).with_str_name("changeset_id"));
+                key.push(Field::new(Self::index("k", &fields)?, FieldType::String).with_str_name("k"));
+            }
+            "public.changeset_comments" => {
+                // ADD CONSTRAINT changeset_comments_pkey PRIMARY KEY (id);
+                // sorted by changeset_id first to be merged with the changesets
+                key.push(Field::new(Self::index("changeset_id", &fields)?, FieldType::Integer).with_str_name("changeset_id"));
+                key.push(Field::new(Self::index("id", &fields)?, FieldType::Integer).with_str_name("id"));
+            }
             "public.users" => {
                 // ADD CONSTRAINT users_pkey PRIMARY KEY (id);
                 key.push(Field::new(Self::index("id", &fields)?, FieldType::Integer).with_str_name("id"));
@@ -6,7 +6,9 @@ use std::str::FromStr;
 use anyhow::anyhow;
 use unescape::unescape;
 
+use crate::osm::apidb_dump::read::changeset_comment_record::ChangesetCommentRecord;
 use crate::osm::apidb_dump::read::changeset_record::ChangesetRecord;
+use crate::osm::apidb_dump::read::changeset_tag_record::ChangesetTagRecord;
 use crate::osm::apidb_dump::read::node_record::NodeRecord;
 use crate::osm::apidb_dump::read::node_tag_record::NodeTagRecord;
 use crate::osm::apidb_dump::read::relation_member_record::{RelationMemberRecord, RelationMemberType};
@@ -120,6 +122,22 @@ impl TableReader {
                     }
                 )
             }
+            "public.changeset_tags" => {
+                Ok(
+                    RecordBuilder {
+                        f: Self::create_changeset_tag,
+                        table_def: self.table_def.clone(),
+                    }
+                )
+            }
+            "public.changeset_comments" => {
+                Ok(
+                    RecordBuilder {
+                        f: Self::create_changeset_comment,
+                        table_def: self.table_def.clone(),
+                    }
+                )
+            }
             "public.users" => {
                 Ok(
                     RecordBuilder {
@@ -375,10 +393,10 @@ impl TableReader {
                             i64::from_str(columns[*id]).unwrap(),
                             i64::from_str(columns[*user_id]).unwrap(),
                             parse_sql_time(columns[*created_at]).unwrap(),
-                            i32::from_str(columns[*min_lat]).unwrap(),
-                            i32::from_str(columns[*max_lat]).unwrap(),
-                            i32::from_str(columns[*min_lon]).unwrap(),
-                            i32::from_str(columns[*max_lon]).unwrap(),
+                            parse_sql_null_string(columns[*min_lat]).map(|s| i32::from_str(&s).unwrap()),
+                            parse_sql_null_string(columns[*max_lat]).map(|s| i32::from_str(&s).unwrap()),
+                            parse_sql_null_string(columns[*min_lon]).map(|s| i32::from_str(&s).unwrap()),
+                            parse_sql_null_string(columns[*max_lon]).map(|s| i32::from_str(&s).unwrap()),
                             parse_sql_time(columns[*closed_at]).unwrap(),
                             i32::from_str(columns[*num_changes]).unwrap(),
                         )
@@ -391,6 +409,58 @@ impl TableReader {
         }
     }
 
+    fn create_changeset_tag(line: &str, table_def: &TableDef, line_number: usize) -> Option<TableRecord> {
+        let columns: Vec<&str> = line.trim_end_matches('\n').split('\t').collect();
+        match table_def.fields_ref() {
+            TableFields::ChangesetTags { changeset_id, k, v } => {
+                assert!(*changeset_id < columns.len(), "column {} for field (changeset_id) is missing in {}:{}", *changeset_id + 1, table_def.path().display(), line_number);
+                assert!(*k < columns.len(), "column {} for field (k) is missing in {}:{}", *k + 1, table_def.path().display(), line_number);
+                assert!(*v < columns.len(), "column {} for field (v) is missing in {}:{}", *v + 1, table_def.path().display(), line_number);
+                Some(
+                    TableRecord::ChangesetTag {
+                        changeset_tag_record: ChangesetTagRecord::new(
+                            i64::from_str(columns[*changeset_id]).unwrap(),
+                            unescape(columns[*k]).unwrap_or(columns[*k].to_string()),
+                            unescape(columns[*v]).unwrap_or(columns[*v].to_string()),
+                        )
+                    }
+                )
+            }
+            _ => {
+                None
+            }
+        }
+    }
+
+    fn create_changeset_comment(line: &str, table_def: &TableDef, line_number: usize) -> Option<TableRecord> {
+        let columns: Vec<&str> = line.trim_end_matches('\n').split('\t').collect();
+        match table_def.fields_ref() {
+            TableFields::ChangesetComments { id, changeset_id, author_id, body, created_at, visible } => {
+                assert!(*id < columns.len(), "column {} for field (id) is missing in {}:{}", *id + 1, table_def.path().display(), line_number);
+                assert!(*changeset_id < columns.len(), "column {} for field (changeset_id) is missing in {}:{}", *changeset_id + 1, table_def.path().display(), line_number);
+                assert!(*author_id < columns.len(), "column {} for field (author_id) is missing in {}:{}", *author_id + 1, table_def.path().display(), line_number);
+                assert!(*body < columns.len(), "column {} for field (body) is missing in {}:{}", *body + 1, table_def.path().display(), line_number);
+                assert!(*created_at < columns.len(), "column {} for field (created_at) is missing in {}:{}", *created_at + 1, table_def.path().display(), line_number);
+                assert!(*visible < columns.len(), "column {} for field (visible) is missing in {}:{}", *visible + 1, table_def.path().display(), line_number);
+                Some(
+                    TableRecord::ChangesetComment {
+                        changeset_comment_record: ChangesetCommentRecord::new(
+                            i64::from_str(columns[*id]).unwrap(),
+                            i64::from_str(columns[*changeset_id]).unwrap(),
+                            i64::from_str(columns[*author_id]).unwrap(),
+                            unescape(columns[*body]).unwrap_or(columns[*body].to_string()),
+                            parse_sql_time(columns[*created_at]).unwrap(),
+                            parse_sql_bool(columns[*visible]).unwrap(),
+                        )
+                    }
+                )
+            }
+            _ => {
+                None
+            }
+        }
+    }
+
     fn create_user(line: &str, table_def: &TableDef, line_number: usize) -> Option<TableRecord> {
         let columns: Vec<&str> = line.trim_end_matches('\n').split('\t').collect();
         match table_def.fields_ref() {
@@ -1,4 +1,6 @@
+use crate::osm::apidb_dump::read::changeset_comment_record::ChangesetCommentRecord;
 use crate::osm::apidb_dump::read::changeset_record::ChangesetRecord;
+use crate::osm::apidb_dump::read::changeset_tag_record::ChangesetTagRecord;
 use crate::osm::apidb_dump::read::node_record::NodeRecord;
 use crate::osm::apidb_dump::read::node_tag_record::NodeTagRecord;
 use crate::osm::apidb_dump::read::relation_member_record::RelationMemberRecord;
@@ -39,6 +41,12 @@ pub(crate) enum TableRecord {
     Changeset {
         changeset_record: ChangesetRecord,
     },
+    ChangesetTag {
+        changeset_tag_record: ChangesetTagRecord,
+    },
+    ChangesetComment {
+        changeset_comment_record: ChangesetCommentRecord,
+    },
     User {
         user_record: UserRecord,
     },
@@ -1,14 +1,38 @@
 use std::fs;
 use std::path::PathBuf;
 use anyhow::Context;
+use chrono::{Datelike, NaiveDateTime, Timelike};
 use json::JsonValue;
 
 static TOC: &[u8] = include_bytes!("./toc/toc.dat");
 static MAPPING: &str = include_str!("./toc/mapping.json");
 
-pub(crate) fn write_toc(path: &PathBuf) -> Result<(), anyhow::Error> {
+/// Offset of the creation time in the header of the template, after the magic, the version, the
+/// int size, the offset size, the format and the compression
+const CREATION_TIME_OFFSET: usize = 16;
+/// The int size of the template. Ints are written as a sign byte followed by little endian bytes
+const INT_SIZE: usize = 4;
+
+/// Write the template toc.dat with the creation time of the dump
+pub(crate) fn write_toc(path: &PathBuf, creation_time: NaiveDateTime) -> Result<(), anyhow::Error> {
     let toc_path = path.join(PathBuf::from("toc.dat"));
-    fs::write(&toc_path, TOC).with_context(|| format!("write {:?} to {:?}", &toc_path, path))?;
+    let mut toc = TOC.to_vec();
+    // struct tm fields: seconds, minutes, hours, day of month, months since January and years
+    // since 1900, followed by the unchanged isdst
+    let fields = [
+        creation_time.second(),
+        creation_time.minute(),
+        creation_time.hour(),
+        creation_time.day(),
+        creation_time.month0(),
+        (creation_time.year() - 1900) as u32,
+    ];
+    for (i, field) in fields.iter().enumerate() {
+        let start = CREATION_TIME_OFFSET + i * (1 + INT_SIZE);
+        toc[start] = 0;
+        toc[start + 1..start + 1 + INT_SIZE].copy_from_slice(&field.to_le_bytes());
+    }
+    fs::write(&toc_path, toc).with_context(|| format!("write {:?} to {:?}", &toc_path, path))?;
     Ok(())
 }
 
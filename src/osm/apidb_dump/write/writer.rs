@@ -18,7 +18,6 @@ use crate::osm::model::way::Way;
 ///
 /// Writer of apidb schema dump that can be loaded using pg_restore into a Postgresql database
 pub struct Writer {
-    #[allow(dead_code)]
     output_path: PathBuf,
     #[allow(dead_code)]
     compression_level: i8,
@@ -539,6 +538,8 @@ impl Writer {
         self.write_users()?;
         self.write_changesets()?;
         self.writers.close()?;
+        // written last so that the changesets are closed at the creation time of the dump
+        write_toc(&self.output_path, chrono::offset::Utc::now().naive_utc())?;
         Ok(())
     }
 
@@ -549,7 +550,6 @@ impl Writer {
 
     fn create_result_dir(output_path: &PathBuf) -> Result<(), Error> {
         fs::create_dir_all(output_path).with_context(|| format!("Failed to create dir: {:?}", output_path))?;
-        write_toc(output_path)?;
 
         Ok(())
     }
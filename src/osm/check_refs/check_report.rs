@@ -0,0 +1,133 @@
+use std::fmt::{Display, Formatter};
+
+use json::JsonValue;
+
+use crate::osm::check_refs::problem::Problem;
+
+/// The result of [crate::osm::check_refs::checker::CheckRefs]
+#[derive(Debug, Clone)]
+pub struct CheckReport {
+    pub(crate) nodes: u64,
+    pub(crate) ways: u64,
+    pub(crate) relations: u64,
+    pub(crate) missing_way_nodes: Problem,
+    pub(crate) missing_node_members: Problem,
+    pub(crate) missing_way_members: Problem,
+    pub(crate) missing_relation_members: Problem,
+    pub(crate) duplicate_nodes: Problem,
+    pub(crate) duplicate_ways: Problem,
+    pub(crate) duplicate_relations: Problem,
+}
+
+impl CheckReport {
+    pub(crate) fn new(max_examples: usize) -> CheckReport {
+        CheckReport {
+            nodes: 0,
+            ways: 0,
+            relations: 0,
+            missing_way_nodes: Problem::new("nodes referenced by ways missing", max_examples),
+            missing_node_members: Problem::new("node members of relations missing", max_examples),
+            missing_way_members: Problem::new("way members of relations missing", max_examples),
+            missing_relation_members: Problem::new("relation members of relations missing", max_examples),
+            duplicate_nodes: Problem::new("duplicate node versions", max_examples),
+            duplicate_ways: Problem::new("duplicate way versions", max_examples),
+            duplicate_relations: Problem::new("duplicate relation versions", max_examples),
+        }
+    }
+
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    pub fn ways(&self) -> u64 {
+        self.ways
+    }
+
+    pub fn relations(&self) -> u64 {
+        self.relations
+    }
+
+    /// Node ids referenced by ways that are not in the input
+    pub fn missing_way_nodes(&self) -> &Problem {
+        &self.missing_way_nodes
+    }
+
+    /// Node ids referenced by relations that are not in the input
+    pub fn missing_node_members(&self) -> &Problem {
+        &self.missing_node_members
+    }
+
+    /// Way ids referenced by relations that are not in the input
+    pub fn missing_way_members(&self) -> &Problem {
+        &self.missing_way_members
+    }
+
+    /// Relation ids referenced by relations that are not in the input
+    pub fn missing_relation_members(&self) -> &Problem {
+        &self.missing_relation_members
+    }
+
+    /// Node ids with the same version more than once
+    pub fn duplicate_nodes(&self) -> &Problem {
+        &self.duplicate_nodes
+    }
+
+    /// Way ids with the same version more than once
+    pub fn duplicate_ways(&self) -> &Problem {
+        &self.duplicate_ways
+    }
+
+    /// Relation ids with the same version more than once
+    pub fn duplicate_relations(&self) -> &Problem {
+        &self.duplicate_relations
+    }
+
+    /// All the problems, in report order
+    pub fn problems(&self) -> Vec<&Problem> {
+        vec![
+            &self.missing_way_nodes,
+            &self.missing_node_members,
+            &self.missing_way_members,
+            &self.missing_relation_members,
+            &self.duplicate_nodes,
+            &self.duplicate_ways,
+            &self.duplicate_relations,
+        ]
+    }
+
+    /// No problems were found
+    pub fn is_ok(&self) -> bool {
+        self.problems().iter().all(|problem| problem.count() == 0)
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        let mut problems = JsonValue::new_array();
+        for problem in self.problems() {
+            // pushing into an array can not fail
+            problems.push(problem.to_json()).unwrap();
+        }
+        json::object! {
+            "nodes": self.nodes,
+            "ways": self.ways,
+            "relations": self.relations,
+            "ok": self.is_ok(),
+            "problems": problems,
+        }
+    }
+}
+
+impl Display for CheckReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "nodes: {}", self.nodes)?;
+        writeln!(f, "ways: {}", self.ways)?;
+        writeln!(f, "relations: {}", self.relations)?;
+        for problem in self.problems() {
+            write!(f, "{}: {}", problem.description(), problem.count())?;
+            if !problem.examples().is_empty() {
+                write!(f, ", e.g. {}", problem.examples().join(", "))?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "{}", if self.is_ok() { "OK" } else { "FAILED" })
+    }
+}
@@ -0,0 +1,155 @@
+use crate::osm::apidb_dump;
+use crate::osm::check_refs::check_report::CheckReport;
+use crate::osm::filter::get_id::{ObjectType, TypedId};
+use crate::osm::index::id_set::IdSet;
+use crate::osm::model::element::Element;
+use crate::osm::model::relation::Member;
+use crate::osm::pbf;
+
+/// Check the referential integrity of OSM data
+///
+/// Reports the nodes referenced by ways, and the members referenced by relations, that are not in
+/// the input, as well as objects that have the same version more than once. Works with current
+/// and history data. The input is read once. References to objects that were not seen yet are kept
+/// until the end of the input, input sorted by type then id, such as *.osm.pbf files and apidb
+/// dumps, only keeps the forward references between relations. Duplicates are detected between
+/// consecutive versions of the same object so they are only found in sorted input.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::check_refs::checker::CheckRefs;
+/// use osm_io::osm::pbf;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let report = CheckRefs::new().check_pbf(&reader)?;
+///     println!("{}", report);
+///     println!("{}", report.to_json().pretty(2));
+///     Ok(())
+/// }
+/// ```
+pub struct CheckRefs {
+    max_examples: usize,
+}
+
+impl Default for CheckRefs {
+    fn default() -> Self {
+        CheckRefs::new()
+    }
+}
+
+impl CheckRefs {
+    pub fn new() -> CheckRefs {
+        CheckRefs {
+            max_examples: 10,
+        }
+    }
+
+    /// Maximal number of example ids for each problem, 10 by default
+    pub fn with_max_examples(&mut self, max_examples: usize) {
+        self.max_examples = max_examples;
+    }
+
+    pub fn check_pbf(&self, reader: &pbf::reader::Reader) -> Result<CheckReport, anyhow::Error> {
+        Ok(self.check(reader.elements()?))
+    }
+
+    pub fn check_apidb(&self, reader: &apidb_dump::read::reader::Reader) -> Result<CheckReport, anyhow::Error> {
+        Ok(self.check(reader.elements()?))
+    }
+
+    pub fn check(&self, elements: impl Iterator<Item=Element>) -> CheckReport {
+        let mut report = CheckReport::new(self.max_examples);
+        let mut nodes = IdSet::new();
+        let mut ways = IdSet::new();
+        let mut relations = IdSet::new();
+        // (referencing object, referenced object) not seen yet
+        let mut pending: Vec<(TypedId, TypedId)> = Vec::new();
+        let mut last: Option<TypedId> = None;
+        let mut last_versions: Vec<i32> = Vec::new();
+
+        for element in elements {
+            let (typed_id, version) = match &element {
+                Element::Node { node } => {
+                    report.nodes += 1;
+                    nodes.insert(node.id());
+                    (TypedId::new(ObjectType::Node, node.id()), node.version())
+                }
+                Element::Way { way } => {
+                    report.ways += 1;
+                    ways.insert(way.id());
+                    let typed_id = TypedId::new(ObjectType::Way, way.id());
+                    for id in way.refs() {
+                        if !nodes.contains(*id) {
+                            pending.push((typed_id, TypedId::new(ObjectType::Node, *id)));
+                        }
+                    }
+                    (typed_id, way.version())
+                }
+                Element::Relation { relation } => {
+                    report.relations += 1;
+                    relations.insert(relation.id());
+                    let typed_id = TypedId::new(ObjectType::Relation, relation.id());
+                    for member in relation.members() {
+                        let (seen, member_id) = match member {
+                            Member::Node { member } => {
+                                (nodes.contains(member.id()), TypedId::new(ObjectType::Node, member.id()))
+                            }
+                            Member::Way { member } => {
+                                (ways.contains(member.id()), TypedId::new(ObjectType::Way, member.id()))
+                            }
+                            Member::Relation { member } => {
+                                (relations.contains(member.id()), TypedId::new(ObjectType::Relation, member.id()))
+                            }
+                        };
+                        if !seen {
+                            pending.push((typed_id, member_id));
+                        }
+                    }
+                    (typed_id, relation.version())
+                }
+                Element::Sentinel => {
+                    continue;
+                }
+            };
+
+            if last == Some(typed_id) {
+                if last_versions.contains(&version) {
+                    let duplicates = match typed_id.object_type() {
+                        ObjectType::Node => &mut report.duplicate_nodes,
+                        ObjectType::Way => &mut report.duplicate_ways,
+                        ObjectType::Relation => &mut report.duplicate_relations,
+                    };
+                    duplicates.record(typed_id.id(), || format!("{} v{}", typed_id, version));
+                } else {
+                    last_versions.push(version);
+                }
+            } else {
+                last = Some(typed_id);
+                last_versions.clear();
+                last_versions.push(version);
+            }
+        }
+
+        for (from, to) in pending {
+            let (seen, missing) = match (from.object_type(), to.object_type()) {
+                (ObjectType::Way, _) => {
+                    (nodes.contains(to.id()), &mut report.missing_way_nodes)
+                }
+                (_, ObjectType::Node) => {
+                    (nodes.contains(to.id()), &mut report.missing_node_members)
+                }
+                (_, ObjectType::Way) => {
+                    (ways.contains(to.id()), &mut report.missing_way_members)
+                }
+                (_, ObjectType::Relation) => {
+                    (relations.contains(to.id()), &mut report.missing_relation_members)
+                }
+            };
+            if !seen {
+                missing.record(to.id(), || format!("{} in {}", to, from));
+            }
+        }
+        report
+    }
+}
@@ -0,0 +1,3 @@
+pub mod problem;
+pub mod check_report;
+pub mod checker;
@@ -0,0 +1,53 @@
+use json::JsonValue;
+
+use crate::osm::index::id_set::IdSet;
+
+/// A kind of referential integrity problem, the number of distinct ids involved and a few
+/// examples
+#[derive(Debug, Clone)]
+pub struct Problem {
+    description: String,
+    ids: IdSet,
+    examples: Vec<String>,
+    max_examples: usize,
+}
+
+impl Problem {
+    pub(crate) fn new(description: &str, max_examples: usize) -> Problem {
+        Problem {
+            description: description.to_string(),
+            ids: IdSet::new(),
+            examples: Vec::new(),
+            max_examples,
+        }
+    }
+
+    /// Record the problem for an id. Only the first occurrence of each id is counted
+    pub(crate) fn record(&mut self, id: i64, example: impl FnOnce() -> String) {
+        if self.ids.insert(id) && self.examples.len() < self.max_examples {
+            self.examples.push(example());
+        }
+    }
+
+    pub fn description(&self) -> &String {
+        &self.description
+    }
+
+    /// Number of distinct ids with the problem
+    pub fn count(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// The first occurrences of the problem
+    pub fn examples(&self) -> &Vec<String> {
+        &self.examples
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        json::object! {
+            "description": self.description.clone(),
+            "count": self.count(),
+            "examples": self.examples.clone(),
+        }
+    }
+}
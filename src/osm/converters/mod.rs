@@ -7,9 +7,15 @@ pub fn timestamp_to_iso8601_seconds(usec: i64) -> Result<String, Error> {
     Ok(datetime.to_rfc3339_opts(SecondsFormat::Secs, true))
 }
 
+pub fn iso8601_to_timestamp_millis(iso8601: &str) -> Result<i64, Error> {
+    let datetime = DateTime::parse_from_rfc3339(iso8601)
+        .map_err(|e| anyhow!("Invalid timestamp {}, error: {}", iso8601, e))?;
+    Ok(datetime.timestamp_millis())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::osm::converters::timestamp_to_iso8601_seconds;
+    use crate::osm::converters::{iso8601_to_timestamp_millis, timestamp_to_iso8601_seconds};
 
     #[test]
     fn test_timestamp_to_iso8601_seconds() {
@@ -22,4 +28,11 @@ mod tests {
     fn test_timestamp_to_iso8601_seconds_failure() {
         timestamp_to_iso8601_seconds(i64::MAX).expect("Invalid input");
     }
+
+    #[test]
+    fn test_iso8601_to_timestamp_millis() {
+        assert_eq!(iso8601_to_timestamp_millis("1970-01-01T00:00:00Z").unwrap(), 0);
+        assert_eq!(iso8601_to_timestamp_millis("2009-03-25T00:53:03Z").unwrap(), 1237942383000);
+        assert!(iso8601_to_timestamp_millis("yesterday").is_err());
+    }
 }
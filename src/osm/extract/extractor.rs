@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use crate::osm::extract::region::Region;
+use crate::osm::extract::region_extract::RegionExtract;
+use crate::osm::extract::strategy::Strategy;
+use crate::osm::model::element::Element;
+use crate::osm::pbf;
+use crate::osm::pbf::compression_type::CompressionType;
+use crate::osm::pbf::file_info::FileInfo;
+
+/// Header of an extract: the input header with the bounding box set to the region envelope
+pub fn extract_file_info(file_info: &FileInfo, region: &dyn Region) -> FileInfo {
+    let mut result = file_info.clone();
+    result.with_bounding_box(&Some(region.bounding_box()));
+    result
+}
+
+/// Extract the data of a region from a *.osm.pbf file
+///
+/// * reader - *.osm.pbf reader, the input is read two or three times depending on the strategy
+/// * region - the region to extract, for example a [crate::osm::model::bounding_box::BoundingBox]
+/// * strategy - how objects crossing the region boundary are handled
+/// * output_path - *.osm.pbf output, the header bounding box is set to the region envelope
+/// * compression_type - output compression
+///
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use std::str::FromStr;
+/// use osm_io::osm::extract::extractor::extract;
+/// use osm_io::osm::extract::strategy::Strategy;
+/// use osm_io::osm::model::bounding_box::BoundingBox;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::pbf::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/alofi.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let bounding_box = BoundingBox::from_str("-169.95,-19.1,-169.85,-19.0")?;
+///     extract(&reader, Box::new(bounding_box), Strategy::CompleteWays, output_path, CompressionType::Zlib)?;
+///     Ok(())
+/// }
+/// ```
+pub fn extract(
+    reader: &pbf::reader::Reader,
+    region: Box<dyn Region>,
+    strategy: Strategy,
+    output_path: PathBuf,
+    compression_type: CompressionType,
+) -> Result<(), anyhow::Error> {
+    let mut region_extract = RegionExtract::new(region, strategy);
+    for element in reader.elements()? {
+        region_extract.first_pass(&element);
+    }
+    region_extract.finish_first_pass();
+
+    if region_extract.needs_second_pass() {
+        for element in reader.elements()? {
+            match element {
+                Element::Way { .. } => {
+                    region_extract.second_pass(&element);
+                }
+                Element::Relation { .. } => {
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut writer = pbf::writer::Writer::from_file_info(
+        output_path,
+        extract_file_info(reader.info(), region_extract.region()),
+        compression_type,
+    )?;
+    writer.write_header()?;
+    for element in reader.elements()? {
+        if region_extract.contains(&element) {
+            writer.write_element(element)?;
+        }
+    }
+    writer.close()
+}
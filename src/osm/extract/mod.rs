@@ -0,0 +1,7 @@
+pub mod strategy;
+pub mod region;
+pub mod polygon_region;
+pub mod poly_file;
+pub mod region_extract;
+pub mod extractor;
+pub mod multi_extract;
@@ -0,0 +1,310 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use json::JsonValue;
+
+use crate::osm::extract::extractor::extract_file_info;
+use crate::osm::extract::poly_file;
+use crate::osm::extract::polygon_region::PolygonRegion;
+use crate::osm::extract::region::Region;
+use crate::osm::extract::region_extract::RegionExtract;
+use crate::osm::extract::strategy::Strategy;
+use crate::osm::geometry::polygon::Polygon;
+use crate::osm::geometry::ring::Ring;
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::coordinate::Coordinate;
+use crate::osm::model::element::Element;
+use crate::osm::pbf;
+use crate::osm::pbf::compression_type::CompressionType;
+
+/// A single extract of [ExtractConfig]
+pub struct ExtractDefinition {
+    output: PathBuf,
+    description: Option<String>,
+    region: Box<dyn Region>,
+}
+
+impl ExtractDefinition {
+    pub fn new(output: PathBuf, description: Option<String>, region: Box<dyn Region>) -> ExtractDefinition {
+        ExtractDefinition {
+            output,
+            description,
+            region,
+        }
+    }
+
+    /// Output *.osm.pbf path
+    pub fn output(&self) -> &PathBuf {
+        &self.output
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn region(&self) -> &dyn Region {
+        self.region.as_ref()
+    }
+
+    pub fn into_region(self) -> Box<dyn Region> {
+        self.region
+    }
+}
+
+/// Configuration of a multi region extract, in the JSON format of osmium extract
+///
+/// ```json
+/// {
+///     "directory": "./target/results",
+///     "strategy": "complete_ways",
+///     "extracts": [
+///         {"output": "a.osm.pbf", "description": "bbox as an array", "bbox": [-169.95, -19.1, -169.85, -19.0]},
+///         {"output": "b.osm.pbf", "bbox": {"left": -169.95, "bottom": -19.1, "right": -169.85, "top": -19.0}},
+///         {"output": "c.osm.pbf", "polygon": {"file_name": "alofi.poly", "file_type": "poly"}},
+///         {"output": "d.osm.pbf", "polygon": [[[-169.95, -19.1], [-169.85, -19.1], [-169.85, -19.0]]]},
+///         {"output": "e.osm.pbf", "multipolygon": [[[[-169.95, -19.1], [-169.85, -19.1], [-169.85, -19.0]]]]}
+///     ]
+/// }
+/// ```
+/// The output paths are relative to the directory, poly file names are relative to the
+/// directory of the configuration file. Inline polygons are GeoJSON style arrays of rings, the
+/// first ring is the outer ring and the following rings are holes. The strategy is optional and
+/// defaults to complete_ways, the same as in osmium.
+pub struct ExtractConfig {
+    directory: PathBuf,
+    strategy: Strategy,
+    extracts: Vec<ExtractDefinition>,
+}
+
+impl ExtractConfig {
+    pub fn new(directory: PathBuf, strategy: Strategy, extracts: Vec<ExtractDefinition>) -> ExtractConfig {
+        ExtractConfig {
+            directory,
+            strategy,
+            extracts,
+        }
+    }
+
+    /// Read the configuration from a JSON file
+    pub fn from_file(path: &Path) -> Result<ExtractConfig, anyhow::Error> {
+        let text = fs::read_to_string(path)
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        let base_directory = path.parent().unwrap_or(Path::new("."));
+        Self::parse(&text, base_directory)
+            .with_context(|| anyhow!("path: {}", path.display()))
+    }
+
+    /// Parse the configuration, poly file names are relative to base_directory
+    pub fn parse(text: &str, base_directory: &Path) -> Result<ExtractConfig, anyhow::Error> {
+        let value = json::parse(text)?;
+        let directory = PathBuf::from(value["directory"].as_str().unwrap_or("."));
+        let strategy = match value["strategy"].as_str() {
+            None => {
+                Strategy::CompleteWays
+            }
+            Some(strategy) => {
+                Strategy::from_str(strategy)?
+            }
+        };
+        if !value["extracts"].is_array() {
+            return Err(anyhow!("Missing extracts array"));
+        }
+        let mut extracts = Vec::new();
+        for (i, extract) in value["extracts"].members().enumerate() {
+            extracts.push(
+                Self::parse_extract(extract, base_directory)
+                    .with_context(|| anyhow!("extract {}", i))?
+            );
+        }
+        Ok(ExtractConfig::new(directory, strategy, extracts))
+    }
+
+    fn parse_extract(value: &JsonValue, base_directory: &Path) -> Result<ExtractDefinition, anyhow::Error> {
+        let output = value["output"].as_str()
+            .ok_or(anyhow!("Missing output"))?;
+        if let Some(output_format) = value["output_format"].as_str() {
+            if output_format != "pbf" {
+                return Err(anyhow!("Unsupported output format: {}, only pbf is supported", output_format));
+            }
+        }
+        let description = value["description"].as_str().map(|s| s.to_string());
+        let region: Box<dyn Region> = if !value["bbox"].is_null() {
+            Box::new(parse_bbox(&value["bbox"])?)
+        } else if value["polygon"].is_object() {
+            let file_name = value["polygon"]["file_name"].as_str()
+                .ok_or(anyhow!("Missing polygon file_name"))?;
+            let file_type = value["polygon"]["file_type"].as_str().unwrap_or("poly");
+            if file_type != "poly" {
+                return Err(anyhow!("Unsupported polygon file type: {}, only poly is supported", file_type));
+            }
+            Box::new(poly_file::read(&base_directory.join(file_name))?)
+        } else if value["polygon"].is_array() {
+            Box::new(PolygonRegion::new(output.to_string(), vec![parse_polygon(&value["polygon"])?])?)
+        } else if value["multipolygon"].is_array() {
+            let polygons = value["multipolygon"].members()
+                .map(parse_polygon)
+                .collect::<Result<Vec<Polygon>, anyhow::Error>>()?;
+            Box::new(PolygonRegion::new(output.to_string(), polygons)?)
+        } else {
+            return Err(anyhow!("Missing bbox, polygon or multipolygon of {}", output));
+        };
+        Ok(ExtractDefinition::new(PathBuf::from(output), description, region))
+    }
+
+    pub fn directory(&self) -> &PathBuf {
+        &self.directory
+    }
+
+    /// Override the output directory
+    pub fn with_directory(&mut self, directory: PathBuf) {
+        self.directory = directory;
+    }
+
+    pub fn strategy(&self) -> &Strategy {
+        &self.strategy
+    }
+
+    pub fn with_strategy(&mut self, strategy: Strategy) {
+        self.strategy = strategy;
+    }
+
+    pub fn extracts(&self) -> &Vec<ExtractDefinition> {
+        &self.extracts
+    }
+
+    pub fn into_extracts(self) -> Vec<ExtractDefinition> {
+        self.extracts
+    }
+}
+
+fn parse_number(value: &JsonValue, name: &str) -> Result<f64, anyhow::Error> {
+    value.as_f64().ok_or(anyhow!("Expected a number for {}, got {}", name, value.dump()))
+}
+
+fn parse_bbox(value: &JsonValue) -> Result<BoundingBox, anyhow::Error> {
+    let text = if value.is_array() && value.len() == 4 {
+        format!(
+            "{},{},{},{}",
+            parse_number(&value[0], "left")?,
+            parse_number(&value[1], "bottom")?,
+            parse_number(&value[2], "right")?,
+            parse_number(&value[3], "top")?,
+        )
+    } else if value.is_object() {
+        format!(
+            "{},{},{},{}",
+            parse_number(&value["left"], "left")?,
+            parse_number(&value["bottom"], "bottom")?,
+            parse_number(&value["right"], "right")?,
+            parse_number(&value["top"], "top")?,
+        )
+    } else if let Some(text) = value.as_str() {
+        text.to_string()
+    } else {
+        return Err(anyhow!("Invalid bbox: {}", value.dump()));
+    };
+    BoundingBox::from_str(&text)
+}
+
+fn parse_ring(value: &JsonValue) -> Result<Ring, anyhow::Error> {
+    let mut coordinates = Vec::with_capacity(value.len());
+    for position in value.members() {
+        coordinates.push(
+            Coordinate::new(
+                parse_number(&position[1], "latitude")?,
+                parse_number(&position[0], "longitude")?,
+            )
+        );
+    }
+    if coordinates.len() < 3 {
+        return Err(anyhow!("A ring must have at least 3 points, got {}", value.dump()));
+    }
+    Ok(Ring::from_coordinates(coordinates))
+}
+
+fn parse_polygon(value: &JsonValue) -> Result<Polygon, anyhow::Error> {
+    let mut rings = value.members()
+        .map(parse_ring)
+        .collect::<Result<Vec<Ring>, anyhow::Error>>()?
+        .into_iter();
+    let outer = rings.next()
+        .ok_or(anyhow!("A polygon must have an outer ring"))?;
+    let mut polygon = Polygon::new(outer, rings.collect());
+    polygon.normalize_orientation();
+    Ok(polygon)
+}
+
+/// Extract many regions from a *.osm.pbf file
+///
+/// All regions are processed together, so the number of passes over the input does not depend
+/// on the number of regions. The last pass writes to all outputs at the same time. Each output is
+/// the same as that of [crate::osm::extract::extractor::extract] for its region.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::extract::multi_extract::{ExtractConfig, multi_extract};
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::pbf::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let config = ExtractConfig::from_file(&PathBuf::from("./tests/fixtures/extracts.json"))?;
+///     multi_extract(&reader, config, CompressionType::Zlib)?;
+///     Ok(())
+/// }
+/// ```
+pub fn multi_extract(reader: &pbf::reader::Reader, config: ExtractConfig, compression_type: CompressionType) -> Result<(), anyhow::Error> {
+    let mut writers = Vec::with_capacity(config.extracts.len());
+    let mut region_extracts = Vec::with_capacity(config.extracts.len());
+    for extract in config.extracts {
+        writers.push(
+            pbf::writer::Writer::from_file_info(
+                config.directory.join(&extract.output),
+                extract_file_info(reader.info(), extract.region.as_ref()),
+                compression_type.clone(),
+            )?
+        );
+        region_extracts.push(RegionExtract::new(extract.region, config.strategy.clone()));
+    }
+
+    for element in reader.elements()? {
+        for region_extract in &mut region_extracts {
+            region_extract.first_pass(&element);
+        }
+    }
+    for region_extract in &mut region_extracts {
+        region_extract.finish_first_pass();
+    }
+    if region_extracts.iter().any(|region_extract| region_extract.needs_second_pass()) {
+        for element in reader.elements()? {
+            match element {
+                Element::Way { .. } => {
+                    for region_extract in &mut region_extracts {
+                        region_extract.second_pass(&element);
+                    }
+                }
+                Element::Relation { .. } => {
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for writer in &mut writers {
+        writer.write_header()?;
+    }
+    for element in reader.elements()? {
+        for (region_extract, writer) in region_extracts.iter().zip(writers.iter_mut()) {
+            if region_extract.contains(&element) {
+                writer.write_element(element.clone())?;
+            }
+        }
+    }
+    for writer in &mut writers {
+        writer.close()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+use crate::osm::extract::polygon_region::PolygonRegion;
+use crate::osm::geometry::polygon::Polygon;
+use crate::osm::geometry::ring::Ring;
+use crate::osm::model::coordinate::Coordinate;
+
+/// Read an Osmosis polygon filter file (*.poly)
+///
+/// The file starts with the region name followed by sections, each with a name line, a line per
+/// point with longitude and latitude, and an END line. Sections whose name starts with ! are
+/// holes. The file ends with an END line.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::extract::extractor::extract;
+/// use osm_io::osm::extract::poly_file;
+/// use osm_io::osm::extract::strategy::Strategy;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::pbf::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/alofi.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let region = poly_file::read(&PathBuf::from("./tests/fixtures/alofi.poly"))?;
+///     extract(&reader, Box::new(region), Strategy::Smart, output_path, CompressionType::Zlib)?;
+///     Ok(())
+/// }
+/// ```
+pub fn read(path: &Path) -> Result<PolygonRegion, anyhow::Error> {
+    let text = fs::read_to_string(path)
+        .with_context(|| anyhow!("path: {}", path.display()))?;
+    parse(&text)
+        .with_context(|| anyhow!("path: {}", path.display()))
+}
+
+/// Parse the content of an Osmosis polygon filter file, see [read]
+///
+/// Each hole is assigned to the smallest outer ring that contains it.
+pub fn parse(text: &str) -> Result<PolygonRegion, anyhow::Error> {
+    let mut lines = text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+    let (_, name) = lines.next()
+        .ok_or(anyhow!("Empty polygon file"))?;
+
+    let mut outers: Vec<Ring> = Vec::new();
+    let mut inners: Vec<Ring> = Vec::new();
+    let mut closed = false;
+    while let Some((line_number, section)) = lines.next() {
+        if section == "END" {
+            closed = true;
+            break;
+        }
+        let mut coordinates = Vec::new();
+        loop {
+            let (line_number, line) = lines.next()
+                .ok_or(anyhow!("Unterminated section {} at line {}", section, line_number))?;
+            if line == "END" {
+                break;
+            }
+            coordinates.push(
+                parse_coordinate(line)
+                    .with_context(|| anyhow!("line {}: {}", line_number, line))?
+            );
+        }
+        if coordinates.len() < 3 {
+            return Err(anyhow!("Section {} at line {} has less than 3 points", section, line_number));
+        }
+        let ring = Ring::from_coordinates(coordinates);
+        if section.starts_with('!') {
+            inners.push(ring);
+        } else {
+            outers.push(ring);
+        }
+    }
+    if !closed {
+        return Err(anyhow!("Missing END line of polygon file {}", name));
+    }
+
+    let mut polygons: Vec<Polygon> = outers.into_iter()
+        .map(|outer| Polygon::new(outer, Vec::new()))
+        .collect();
+    for inner in inners {
+        let point = &inner.coordinates()[0];
+        let polygon = polygons.iter_mut()
+            .filter(|polygon| polygon.outer().contains(point))
+            .min_by(|a, b| a.outer().area().total_cmp(&b.outer().area()))
+            .ok_or(anyhow!("Hole at lon: {}, lat: {} is outside of all outer rings", point.lon(), point.lat()))?;
+        polygon.add_inner(inner);
+    }
+    for polygon in &mut polygons {
+        polygon.normalize_orientation();
+    }
+    PolygonRegion::new(name.to_string(), polygons)
+}
+
+fn parse_coordinate(line: &str) -> Result<Coordinate, anyhow::Error> {
+    let mut parts = line.split_whitespace();
+    let lon = f64::from_str(parts.next().ok_or(anyhow!("Missing longitude"))?)?;
+    let lat = f64::from_str(parts.next().ok_or(anyhow!("Missing latitude"))?)?;
+    if parts.next().is_some() {
+        return Err(anyhow!("Expected longitude and latitude"));
+    }
+    Ok(Coordinate::new(lat, lon))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::osm::extract::region::Region;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), anyhow::Error> {
+        let region = parse(
+            "square\n1\n  0.0E+00 0.0E+00\n  1.0E+01 0.0E+00\n  1.0E+01 1.0E+01\n  0.0E+00 1.0E+01\nEND\n\
+            !1\n  4 4\n  6 4\n  6 6\n  4 6\nEND\n2\n  20 20\n  21 20\n  21 21\nEND\nEND\n"
+        )?;
+        assert_eq!(region.name(), "square");
+        assert_eq!(region.polygons().len(), 2);
+        assert_eq!(region.polygons()[0].inners().len(), 1);
+        assert!(region.contains(&Coordinate::new(1.0, 1.0)));
+        assert!(!region.contains(&Coordinate::new(5.0, 5.0)));
+        assert!(region.contains(&Coordinate::new(20.2, 20.5)));
+        assert!(!region.contains(&Coordinate::new(20.8, 20.5)));
+        assert!(!region.contains(&Coordinate::new(15.0, 15.0)));
+        let bounding_box = region.bounding_box();
+        assert_eq!(bounding_box.left(), 0.0);
+        assert_eq!(bounding_box.top(), 21.0);
+
+        assert!(parse("square\n1\n  0 0\n  1 0\n  1 1\nEND\n").is_err());
+        assert!(parse("square\n1\n  0 0\n  1 0\nEND\nEND\n").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use anyhow::anyhow;
+
+use crate::osm::extract::region::Region;
+use crate::osm::geometry::polygon::Polygon;
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::coordinate::Coordinate;
+
+#[derive(Debug, Clone)]
+struct Edge {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+}
+
+/// A [Region] bounded by polygons with holes
+///
+/// Point in polygon tests are done by ray casting with the even-odd rule over all rings. The
+/// edges are distributed into horizontal bands so that a test only looks at the edges that
+/// overlap the latitude of the point, which keeps the tests fast for boundaries with many
+/// thousands of points.
+#[derive(Debug, Clone)]
+pub struct PolygonRegion {
+    name: String,
+    polygons: Vec<Polygon>,
+    bounding_box: BoundingBox,
+    band_height: f64,
+    bands: Vec<Vec<Edge>>,
+}
+
+impl PolygonRegion {
+    /// Create a region from polygons. Fails if there are no polygons
+    pub fn new(name: String, polygons: Vec<Polygon>) -> Result<PolygonRegion, anyhow::Error> {
+        let mut bounding_box: Option<BoundingBox> = None;
+        for polygon_box in polygons.iter().filter_map(|polygon| polygon.bounding_box()) {
+            match bounding_box.as_mut() {
+                None => {
+                    bounding_box = Some(polygon_box);
+                }
+                Some(bounding_box) => {
+                    bounding_box.merge_bounding_box(&polygon_box);
+                }
+            }
+        }
+        let bounding_box = bounding_box
+            .ok_or(anyhow!("Polygon region {} has no polygons", name))?;
+
+        let mut edges = Vec::new();
+        for polygon in &polygons {
+            for ring in std::iter::once(polygon.outer()).chain(polygon.inners().iter()) {
+                for pair in ring.coordinates().windows(2) {
+                    edges.push(
+                        Edge {
+                            x1: pair[0].lon(),
+                            y1: pair[0].lat(),
+                            x2: pair[1].lon(),
+                            y2: pair[1].lat(),
+                        }
+                    );
+                }
+            }
+        }
+
+        let band_count = (edges.len() / 8).clamp(1, 16384);
+        let band_height = (bounding_box.top() - bounding_box.bottom()) / band_count as f64;
+        let mut bands = vec![Vec::new(); band_count];
+        for edge in edges {
+            let first = Self::band_of(edge.y1.min(edge.y2), bounding_box.bottom(), band_height, band_count);
+            let last = Self::band_of(edge.y1.max(edge.y2), bounding_box.bottom(), band_height, band_count);
+            for band in &mut bands[first..=last] {
+                band.push(edge.clone());
+            }
+        }
+
+        Ok(
+            PolygonRegion {
+                name,
+                polygons,
+                bounding_box,
+                band_height,
+                bands,
+            }
+        )
+    }
+
+    fn band_of(y: f64, bottom: f64, band_height: f64, band_count: usize) -> usize {
+        if band_height <= 0.0 {
+            0
+        } else {
+            (((y - bottom) / band_height) as usize).min(band_count - 1)
+        }
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn polygons(&self) -> &Vec<Polygon> {
+        &self.polygons
+    }
+}
+
+impl Region for PolygonRegion {
+    fn contains(&self, coordinate: &Coordinate) -> bool {
+        if !self.bounding_box.contains(coordinate) {
+            return false;
+        }
+        let (x, y) = (coordinate.lon(), coordinate.lat());
+        let band = Self::band_of(y, self.bounding_box.bottom(), self.band_height, self.bands.len());
+        let mut inside = false;
+        for edge in &self.bands[band] {
+            if (edge.y1 > y) != (edge.y2 > y) && x < (edge.x2 - edge.x1) * (y - edge.y1) / (edge.y2 - edge.y1) + edge.x1 {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        self.bounding_box.clone()
+    }
+}
@@ -0,0 +1,21 @@
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::coordinate::Coordinate;
+
+/// An area of the world to extract data for
+pub trait Region: Send + Sync {
+    /// The coordinate is inside the region
+    fn contains(&self, coordinate: &Coordinate) -> bool;
+
+    /// The envelope of the region, written to the header of the extract
+    fn bounding_box(&self) -> BoundingBox;
+}
+
+impl Region for BoundingBox {
+    fn contains(&self, coordinate: &Coordinate) -> bool {
+        BoundingBox::contains(self, coordinate)
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        self.clone()
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use crate::osm::extract::region::Region;
+use crate::osm::extract::strategy::Strategy;
+use crate::osm::index::id_set::IdSet;
+use crate::osm::model::element::Element;
+use crate::osm::model::relation::{Member, Relation};
+
+/// Selection of the objects that belong to the extract of a single region
+///
+/// The selection is made in passes over the input, which must be sorted by type and id:
+/// * first pass - [RegionExtract::first_pass] for all elements, collects the nodes inside the
+///   region, the ways and relations that reference them and, depending on the strategy, the
+///   nodes needed to complete the ways
+/// * second pass - only if [RegionExtract::needs_second_pass], [RegionExtract::second_pass] for
+///   all ways, collects the nodes of the member ways of multipolygon relations
+/// * write pass - elements for which [RegionExtract::contains] is true are written
+pub struct RegionExtract {
+    region: Box<dyn Region>,
+    strategy: Strategy,
+    nodes: IdSet,
+    extra_nodes: IdSet,
+    ways: IdSet,
+    extra_ways: IdSet,
+    relations: IdSet,
+    parent_relations: HashMap<i64, Relation>,
+}
+
+impl RegionExtract {
+    pub fn new(region: Box<dyn Region>, strategy: Strategy) -> RegionExtract {
+        RegionExtract {
+            region,
+            strategy,
+            nodes: IdSet::new(),
+            extra_nodes: IdSet::new(),
+            ways: IdSet::new(),
+            extra_ways: IdSet::new(),
+            relations: IdSet::new(),
+            parent_relations: HashMap::new(),
+        }
+    }
+
+    pub fn region(&self) -> &dyn Region {
+        self.region.as_ref()
+    }
+
+    pub fn strategy(&self) -> &Strategy {
+        &self.strategy
+    }
+
+    /// Collect the element if it belongs to the extract
+    ///
+    /// Relations that have relation members are kept until [RegionExtract::finish_first_pass]
+    /// because their members may come later in the input.
+    pub fn first_pass(&mut self, element: &Element) {
+        match element {
+            Element::Node { node } => {
+                if node.visible() && self.region.contains(node.coordinate()) {
+                    self.nodes.insert(node.id());
+                }
+            }
+            Element::Way { way } => {
+                if way.refs().iter().any(|id| self.nodes.contains(*id)) {
+                    self.ways.insert(way.id());
+                    if self.strategy != Strategy::Simple {
+                        for id in way.refs() {
+                            if !self.nodes.contains(*id) {
+                                self.extra_nodes.insert(*id);
+                            }
+                        }
+                    }
+                }
+            }
+            Element::Relation { relation } => {
+                let included = relation.members().iter().any(|member| {
+                    match member {
+                        Member::Node { member } => {
+                            self.nodes.contains(member.id())
+                        }
+                        Member::Way { member } => {
+                            self.ways.contains(member.id())
+                        }
+                        Member::Relation { member } => {
+                            self.relations.contains(member.id())
+                        }
+                    }
+                });
+                if included {
+                    self.include_relation(relation);
+                } else if relation.members().iter().any(|member| matches!(member, Member::Relation { .. })) {
+                    self.parent_relations.insert(relation.id(), relation.clone());
+                }
+            }
+            Element::Sentinel => {}
+        }
+    }
+
+    /// Include the relations whose relation members were included, repeatedly, until no more
+    /// relations are added. The relations are included as in the first pass, so the member ways
+    /// of multipolygon relations are completed by the smart strategy
+    pub fn finish_first_pass(&mut self) {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            let relations = &self.relations;
+            let included: Vec<i64> = self.parent_relations.iter()
+                .filter(|(_, relation)| {
+                    relation.members().iter().any(|member| {
+                        matches!(member, Member::Relation { member } if relations.contains(member.id()))
+                    })
+                })
+                .map(|(id, _)| *id)
+                .collect();
+            for id in included {
+                if let Some(relation) = self.parent_relations.remove(&id) {
+                    self.include_relation(&relation);
+                    changed = true;
+                }
+            }
+        }
+        self.parent_relations.clear();
+    }
+
+    /// The smart strategy added member ways of multipolygon relations that need their nodes
+    pub fn needs_second_pass(&self) -> bool {
+        !self.extra_ways.is_empty()
+    }
+
+    /// Collect the nodes of the ways added to complete multipolygon relations
+    pub fn second_pass(&mut self, element: &Element) {
+        if let Element::Way { way } = element {
+            if self.extra_ways.contains(way.id()) {
+                for id in way.refs() {
+                    if !self.nodes.contains(*id) {
+                        self.extra_nodes.insert(*id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The element belongs to the extract
+    pub fn contains(&self, element: &Element) -> bool {
+        match element {
+            Element::Node { node } => {
+                self.nodes.contains(node.id()) || self.extra_nodes.contains(node.id())
+            }
+            Element::Way { way } => {
+                self.ways.contains(way.id()) || self.extra_ways.contains(way.id())
+            }
+            Element::Relation { relation } => {
+                self.relations.contains(relation.id())
+            }
+            Element::Sentinel => {
+                false
+            }
+        }
+    }
+
+    fn include_relation(&mut self, relation: &Relation) {
+        self.relations.insert(relation.id());
+        if self.strategy == Strategy::Smart && is_multipolygon(relation) {
+            for member in relation.members() {
+                if let Member::Way { member } = member {
+                    if !self.ways.contains(member.id()) {
+                        self.extra_ways.insert(member.id());
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn is_multipolygon(relation: &Relation) -> bool {
+    relation.tags().iter().any(|tag| tag.k() == "type" && tag.v() == "multipolygon")
+}
@@ -0,0 +1,56 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+
+/// How objects crossing the region boundary are handled, the same as the osmium extract
+/// strategies
+#[derive(Clone, Debug, PartialEq)]
+pub enum Strategy {
+    /// Nodes inside the region, ways with at least one node inside the region and relations with
+    /// at least one included member. Ways crossing the boundary are incomplete
+    Simple,
+    /// As [Strategy::Simple], additionally all nodes of the included ways, so that the ways are
+    /// complete
+    CompleteWays,
+    /// As [Strategy::CompleteWays], additionally all member ways of the included multipolygon
+    /// relations with all their nodes, so that the multipolygons are complete
+    Smart,
+}
+
+impl Display for Strategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Strategy::Simple => {
+                write!(f, "simple")
+            }
+            Strategy::CompleteWays => {
+                write!(f, "complete_ways")
+            }
+            Strategy::Smart => {
+                write!(f, "smart")
+            }
+        }
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "simple" => {
+                Ok(Strategy::Simple)
+            }
+            "complete_ways" => {
+                Ok(Strategy::CompleteWays)
+            }
+            "smart" => {
+                Ok(Strategy::Smart)
+            }
+            _ => {
+                Err(anyhow!("Unknown extract strategy: {}, expected one of simple, complete_ways, smart", s))
+            }
+        }
+    }
+}
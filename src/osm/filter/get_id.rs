@@ -0,0 +1,319 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+use crate::osm::index::id_set::IdSet;
+use crate::osm::model::element::Element;
+use crate::osm::model::relation::Member;
+use crate::osm::pbf;
+use crate::osm::pbf::block_index::BlockIndex;
+use crate::osm::pbf::compression_type::CompressionType;
+
+/// OSM object type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ObjectType {
+    Node,
+    Way,
+    Relation,
+}
+
+/// An OSM object id with its type, written as n123, w456 or r789
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TypedId {
+    object_type: ObjectType,
+    id: i64,
+}
+
+impl TypedId {
+    pub fn new(object_type: ObjectType, id: i64) -> TypedId {
+        TypedId {
+            object_type,
+            id,
+        }
+    }
+
+    pub fn object_type(&self) -> ObjectType {
+        self.object_type
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// The typed id of an element, None for [Element::Sentinel]
+    pub fn of(element: &Element) -> Option<TypedId> {
+        match element {
+            Element::Node { node } => {
+                Some(TypedId::new(ObjectType::Node, node.id()))
+            }
+            Element::Way { way } => {
+                Some(TypedId::new(ObjectType::Way, way.id()))
+            }
+            Element::Relation { relation } => {
+                Some(TypedId::new(ObjectType::Relation, relation.id()))
+            }
+            Element::Sentinel => {
+                None
+            }
+        }
+    }
+}
+
+impl Display for TypedId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let prefix = match self.object_type {
+            ObjectType::Node => "n",
+            ObjectType::Way => "w",
+            ObjectType::Relation => "r",
+        };
+        write!(f, "{}{}", prefix, self.id)
+    }
+}
+
+impl FromStr for TypedId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (object_type, id) = match s.chars().next() {
+            Some('n') => {
+                (ObjectType::Node, &s[1..])
+            }
+            Some('w') => {
+                (ObjectType::Way, &s[1..])
+            }
+            Some('r') => {
+                (ObjectType::Relation, &s[1..])
+            }
+            _ => {
+                return Err(anyhow!("Typed id must be in the form of n123, w456 or r789. Got {} instead", s));
+            }
+        };
+        let id = i64::from_str(id)
+            .with_context(|| anyhow!("Typed id must be in the form of n123, w456 or r789. Got {} instead", s))?;
+        Ok(TypedId::new(object_type, id))
+    }
+}
+
+/// Read typed ids from a file, one id per line. Text after the id separated by white space, and
+/// lines starting with #, are ignored
+pub fn read_ids(path: &Path) -> Result<Vec<TypedId>, anyhow::Error> {
+    let text = fs::read_to_string(path)
+        .with_context(|| anyhow!("path: {}", path.display()))?;
+    let mut ids = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let id = line.split_whitespace().next().unwrap_or("");
+        ids.push(
+            TypedId::from_str(id)
+                .with_context(|| anyhow!("path: {}, line: {}", path.display(), i + 1))?
+        );
+    }
+    Ok(ids)
+}
+
+/// Test whether the id range of a block may contain any of the sorted ids
+fn any_in_range(sorted_ids: &[i64], range: Option<(i64, i64)>) -> bool {
+    match range {
+        None => {
+            false
+        }
+        Some((min, max)) => {
+            let position = sorted_ids.partition_point(|id| *id < min);
+            position < sorted_ids.len() && sorted_ids[position] <= max
+        }
+    }
+}
+
+/// Write the objects with the given ids, optionally with all the objects they reference
+///
+/// With [GetId::with_add_referenced] the nodes of the ways and the members of the relations are
+/// added, recursively for member relations. When a [BlockIndex] is given only the blocks that may
+/// contain the objects are decoded, otherwise the whole input is read.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use std::str::FromStr;
+/// use osm_io::osm::filter::get_id::{GetId, TypedId};
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::pbf::block_index::BlockIndex;
+/// use osm_io::osm::pbf::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109-getid.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let mut get_id = GetId::new(vec![TypedId::from_str("w16338233")?, TypedId::from_str("r6126855")?]);
+///     get_id.with_add_referenced(true);
+///     get_id.with_block_index(BlockIndex::find(&reader)?);
+///     let missing = get_id.run(&reader, output_path, CompressionType::Zlib)?;
+///     for id in missing {
+///         println!("missing: {}", id);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct GetId {
+    ids: Vec<TypedId>,
+    add_referenced: bool,
+    block_index: Option<BlockIndex>,
+}
+
+impl GetId {
+    pub fn new(ids: Vec<TypedId>) -> GetId {
+        GetId {
+            ids,
+            add_referenced: false,
+            block_index: None,
+        }
+    }
+
+    /// Add the nodes of the ways and the members of the relations, recursively
+    pub fn with_add_referenced(&mut self, add_referenced: bool) {
+        self.add_referenced = add_referenced;
+    }
+
+    /// Decode only the blocks that may contain the objects
+    pub fn with_block_index(&mut self, block_index: Option<BlockIndex>) {
+        self.block_index = block_index;
+    }
+
+    pub fn ids(&self) -> &Vec<TypedId> {
+        &self.ids
+    }
+
+    /// Elements of the blocks that may contain any of the ids, or all elements without an index
+    fn elements(&self, reader: &pbf::reader::Reader, nodes: &IdSet, ways: &IdSet, relations: &IdSet) -> Result<Box<dyn Iterator<Item=Element>>, anyhow::Error> {
+        match &self.block_index {
+            None => {
+                Ok(Box::new(reader.elements()?))
+            }
+            Some(block_index) => {
+                let nodes = nodes.ids();
+                let ways = ways.ids();
+                let relations = relations.ids();
+                Ok(
+                    Box::new(
+                        block_index.elements(move |entry| {
+                            any_in_range(&nodes, entry.nodes())
+                                || any_in_range(&ways, entry.ways())
+                                || any_in_range(&relations, entry.relations())
+                        })
+                    )
+                )
+            }
+        }
+    }
+
+    /// Write the objects, returns the ids that were not found in the input
+    pub fn run(&self, reader: &pbf::reader::Reader, output_path: PathBuf, compression_type: CompressionType) -> Result<Vec<TypedId>, anyhow::Error> {
+        let mut nodes = IdSet::new();
+        let mut ways = IdSet::new();
+        let mut relations = IdSet::new();
+        for id in &self.ids {
+            match id.object_type {
+                ObjectType::Node => {
+                    nodes.insert(id.id);
+                }
+                ObjectType::Way => {
+                    ways.insert(id.id);
+                }
+                ObjectType::Relation => {
+                    relations.insert(id.id);
+                }
+            }
+        }
+
+        if self.add_referenced {
+            self.add_relation_members(reader, &mut nodes, &mut ways, &mut relations)?;
+            if !ways.is_empty() {
+                for element in self.elements(reader, &IdSet::new(), &ways, &IdSet::new())? {
+                    if let Element::Way { way } = &element {
+                        if ways.contains(way.id()) {
+                            for id in way.refs() {
+                                nodes.insert(*id);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut writer = pbf::writer::Writer::from_file_info(output_path, reader.info().clone(), compression_type)?;
+        writer.write_header()?;
+        let mut found = HashSet::new();
+        for element in self.elements(reader, &nodes, &ways, &relations)? {
+            let wanted = match &element {
+                Element::Node { node } => {
+                    nodes.contains(node.id())
+                }
+                Element::Way { way } => {
+                    ways.contains(way.id())
+                }
+                Element::Relation { relation } => {
+                    relations.contains(relation.id())
+                }
+                Element::Sentinel => {
+                    false
+                }
+            };
+            if wanted {
+                if let Some(id) = TypedId::of(&element) {
+                    found.insert(id);
+                }
+                writer.write_element(element)?;
+            }
+        }
+        writer.close()?;
+
+        Ok(
+            self.ids.iter()
+                .filter(|id| !found.contains(id))
+                .cloned()
+                .collect()
+        )
+    }
+
+    /// Collect the members of the relations, recursively for member relations
+    fn add_relation_members(&self, reader: &pbf::reader::Reader, nodes: &mut IdSet, ways: &mut IdSet, relations: &mut IdSet) -> Result<(), anyhow::Error> {
+        let mut done = IdSet::new();
+        loop {
+            let pending: IdSet = relations.ids().into_iter()
+                .filter(|id| !done.contains(*id))
+                .collect();
+            if pending.is_empty() {
+                break;
+            }
+            for element in self.elements(reader, &IdSet::new(), &IdSet::new(), &pending)? {
+                if let Element::Relation { relation } = &element {
+                    if relations.contains(relation.id()) && done.insert(relation.id()) {
+                        for member in relation.members() {
+                            match member {
+                                Member::Node { member } => {
+                                    nodes.insert(member.id());
+                                }
+                                Member::Way { member } => {
+                                    ways.insert(member.id());
+                                }
+                                Member::Relation { member } => {
+                                    relations.insert(member.id());
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            // relations missing in the input
+            for id in pending.ids() {
+                done.insert(id);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+pub mod tag_expression;
+pub mod tag_filter;
+pub mod reference_complete;
+pub mod get_id;
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::osm::filter::tag_filter::TagFilter;
+use crate::osm::index::id_set::IdSet;
+use crate::osm::model::element::Element;
+use crate::osm::model::relation::Member;
+use crate::osm::pbf;
+use crate::osm::pbf::compression_type::CompressionType;
+
+/// Number of objects written by [ReferenceCompleteFilter::run]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilterCounts {
+    pub matched: u64,
+    pub referenced: u64,
+}
+
+/// Filter by tags and keep everything the matching objects reference
+///
+/// The output is referentially complete: it contains the nodes of the matching ways and all the
+/// members of the matching relations, recursively for member relations, including the nodes of
+/// the member ways. The input is read four times:
+/// * collect the matching relations and the relation members of all relations
+/// * collect the node and way members of the matching relations and their member relations
+/// * collect the nodes of the matching and the referenced ways
+/// * write
+///
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::filter::reference_complete::ReferenceCompleteFilter;
+/// use osm_io::osm::filter::tag_filter::TagFilter;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::pbf::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109-highways.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let mut reference_complete = ReferenceCompleteFilter::new(TagFilter::parse(&["w/highway"])?);
+///     reference_complete.with_omit_referenced_tags(true);
+///     reference_complete.run(&reader, output_path, CompressionType::Zlib)?;
+///     Ok(())
+/// }
+/// ```
+pub struct ReferenceCompleteFilter {
+    tag_filter: TagFilter,
+    omit_referenced_tags: bool,
+}
+
+impl ReferenceCompleteFilter {
+    pub fn new(tag_filter: TagFilter) -> ReferenceCompleteFilter {
+        ReferenceCompleteFilter {
+            tag_filter,
+            omit_referenced_tags: false,
+        }
+    }
+
+    /// Write the objects that are only referenced, and do not match the filter themselves,
+    /// without tags
+    pub fn with_omit_referenced_tags(&mut self, omit_referenced_tags: bool) {
+        self.omit_referenced_tags = omit_referenced_tags;
+    }
+
+    pub fn tag_filter(&self) -> &TagFilter {
+        &self.tag_filter
+    }
+
+    pub fn run(&self, reader: &pbf::reader::Reader, output_path: PathBuf, compression_type: CompressionType) -> Result<FilterCounts, anyhow::Error> {
+        // matching and referenced relations
+        let mut relations = IdSet::new();
+        let mut child_relations: HashMap<i64, Vec<i64>> = HashMap::new();
+        let mut pending = Vec::new();
+        for element in reader.elements()? {
+            if let Element::Relation { relation } = &element {
+                let children: Vec<i64> = relation.members().iter()
+                    .filter_map(|member| {
+                        match member {
+                            Member::Relation { member } => {
+                                Some(member.id())
+                            }
+                            _ => {
+                                None
+                            }
+                        }
+                    })
+                    .collect();
+                if !children.is_empty() {
+                    child_relations.insert(relation.id(), children);
+                }
+                if self.tag_filter.matches(&element) {
+                    relations.insert(relation.id());
+                    pending.push(relation.id());
+                }
+            }
+        }
+        while let Some(id) = pending.pop() {
+            if let Some(children) = child_relations.remove(&id) {
+                for child in children {
+                    if relations.insert(child) {
+                        pending.push(child);
+                    }
+                }
+            }
+        }
+        drop(child_relations);
+
+        // node and way members of the relations
+        let mut nodes = IdSet::new();
+        let mut ways = IdSet::new();
+        if !relations.is_empty() {
+            for element in reader.elements()? {
+                if let Element::Relation { relation } = &element {
+                    if relations.contains(relation.id()) {
+                        for member in relation.members() {
+                            match member {
+                                Member::Node { member } => {
+                                    nodes.insert(member.id());
+                                }
+                                Member::Way { member } => {
+                                    ways.insert(member.id());
+                                }
+                                Member::Relation { .. } => {}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // nodes of the matching and referenced ways
+        for element in reader.elements()? {
+            match &element {
+                Element::Way { way } if ways.contains(way.id()) || self.tag_filter.matches(&element) => {
+                    ways.insert(way.id());
+                    for id in way.refs() {
+                        nodes.insert(*id);
+                    }
+                }
+                Element::Relation { .. } => {
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let mut writer = pbf::writer::Writer::from_file_info(output_path, reader.info().clone(), compression_type)?;
+        writer.write_header()?;
+        let mut counts = FilterCounts::default();
+        for element in reader.elements()? {
+            if let Element::Sentinel = element {
+                continue;
+            }
+            if self.tag_filter.matches(&element) {
+                counts.matched += 1;
+                writer.write_element(element)?;
+            } else {
+                let referenced = match &element {
+                    Element::Node { node } => {
+                        nodes.contains(node.id())
+                    }
+                    Element::Way { way } => {
+                        ways.contains(way.id())
+                    }
+                    Element::Relation { relation } => {
+                        relations.contains(relation.id())
+                    }
+                    Element::Sentinel => {
+                        false
+                    }
+                };
+                if referenced {
+                    counts.referenced += 1;
+                    writer.write_element(self.referenced_element(element))?;
+                }
+            }
+        }
+        writer.close()?;
+        Ok(counts)
+    }
+
+    fn referenced_element(&self, mut element: Element) -> Element {
+        if self.omit_referenced_tags {
+            match &mut element {
+                Element::Node { node } => {
+                    node.take_tags();
+                }
+                Element::Way { way } => {
+                    way.take_tags();
+                }
+                Element::Relation { relation } => {
+                    relation.take_tags();
+                }
+                Element::Sentinel => {}
+            }
+        }
+        element
+    }
+}
@@ -0,0 +1,283 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use regex::Regex;
+
+use crate::osm::model::element::Element;
+use crate::osm::model::tag::Tag;
+
+/// Matches a key or a value either exactly or by a wildcard pattern where * matches any sequence
+/// of characters
+#[derive(Debug, Clone)]
+enum TextMatcher {
+    Exact(String),
+    Wildcard(Regex),
+}
+
+impl TextMatcher {
+    fn parse(s: &str) -> Result<TextMatcher, anyhow::Error> {
+        if s.contains('*') {
+            let pattern = s.split('*')
+                .map(regex::escape)
+                .collect::<Vec<String>>()
+                .join(".*");
+            Ok(TextMatcher::Wildcard(Regex::new(&format!("^{}$", pattern))?))
+        } else {
+            Ok(TextMatcher::Exact(s.to_string()))
+        }
+    }
+
+    fn matches(&self, s: &str) -> bool {
+        match self {
+            TextMatcher::Exact(text) => {
+                text == s
+            }
+            TextMatcher::Wildcard(regex) => {
+                regex.is_match(s)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ValueMatcher {
+    Any,
+    OneOf(Vec<TextMatcher>),
+    NoneOf(Vec<TextMatcher>),
+    Regex(Regex),
+    NotRegex(Regex),
+}
+
+impl ValueMatcher {
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            ValueMatcher::Any => {
+                true
+            }
+            ValueMatcher::OneOf(matchers) => {
+                matchers.iter().any(|matcher| matcher.matches(value))
+            }
+            ValueMatcher::NoneOf(matchers) => {
+                !matchers.iter().any(|matcher| matcher.matches(value))
+            }
+            ValueMatcher::Regex(regex) => {
+                regex.is_match(value)
+            }
+            ValueMatcher::NotRegex(regex) => {
+                !regex.is_match(value)
+            }
+        }
+    }
+}
+
+/// A tag filter expression
+///
+/// The syntax is `[types/][!]key[operator values]`:
+/// * types - any combination of n, w and r, the expression matches only nodes, ways or relations
+///   respectively. Without types the expression matches all element types
+/// * ! - negate the expression, e.g. `w/!highway` matches ways without a highway tag
+/// * key - the tag key, * matches any sequence of characters, e.g. `addr:*`
+/// * no operator - the key exists, the same as `key=*`
+/// * `=` values - a comma separated list of values, one of them must match, * is a wildcard, e.g.
+///   `highway=primary,secondary` or `name=*Street`
+/// * `!=` values - the key exists with a value that matches none of the values
+/// * `~` regex - the key exists with a value that matches the regular expression, e.g.
+///   `name~^[A-C]`
+/// * `!~` regex - the key exists with a value that does not match the regular expression
+///
+/// Example:
+/// ```
+/// use std::str::FromStr;
+/// use osm_io::osm::filter::tag_expression::TagExpression;
+/// use osm_io::osm::model::tag::Tag;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let expression = TagExpression::from_str("w/highway=primary,secondary")?;
+///     let tags = vec![Tag::new("highway".to_string(), "primary".to_string())];
+///     assert!(expression.matches_tags(&tags));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TagExpression {
+    expression: String,
+    nodes: bool,
+    ways: bool,
+    relations: bool,
+    negated: bool,
+    key: TextMatcher,
+    value: ValueMatcher,
+}
+
+impl TagExpression {
+    /// The expression applies to nodes
+    pub fn nodes(&self) -> bool {
+        self.nodes
+    }
+
+    /// The expression applies to ways
+    pub fn ways(&self) -> bool {
+        self.ways
+    }
+
+    /// The expression applies to relations
+    pub fn relations(&self) -> bool {
+        self.relations
+    }
+
+    /// Evaluate the expression on tags regardless of the element type
+    pub fn matches_tags(&self, tags: &[Tag]) -> bool {
+        let found = tags.iter()
+            .any(|tag| self.key.matches(tag.k()) && self.value.matches(tag.v()));
+        found != self.negated
+    }
+
+    /// Evaluate the expression on an element. Elements of the types the expression does not
+    /// apply to, and [Element::Sentinel], never match
+    pub fn matches(&self, element: &Element) -> bool {
+        match element {
+            Element::Node { node } => {
+                self.nodes && self.matches_tags(node.tags())
+            }
+            Element::Way { way } => {
+                self.ways && self.matches_tags(way.tags())
+            }
+            Element::Relation { relation } => {
+                self.relations && self.matches_tags(relation.tags())
+            }
+            Element::Sentinel => {
+                false
+            }
+        }
+    }
+
+    fn parse_values(values: &str) -> Result<Vec<TextMatcher>, anyhow::Error> {
+        values.split(',')
+            .map(TextMatcher::parse)
+            .collect()
+    }
+}
+
+impl FromStr for TagExpression {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = s.trim();
+        let mut rest = expression;
+        let (mut nodes, mut ways, mut relations) = (true, true, true);
+        if let Some((types, tail)) = rest.split_once('/') {
+            if !types.is_empty() && types.chars().all(|c| c == 'n' || c == 'w' || c == 'r') {
+                nodes = types.contains('n');
+                ways = types.contains('w');
+                relations = types.contains('r');
+                rest = tail;
+            }
+        }
+
+        let negated = rest.starts_with('!');
+        if negated {
+            rest = &rest[1..];
+        }
+
+        let operator_position = rest.find(['=', '~', '!']);
+        let (key, value) = match operator_position {
+            None => {
+                (rest, ValueMatcher::Any)
+            }
+            Some(position) => {
+                let key = &rest[..position];
+                let tail = &rest[position..];
+                let value = if let Some(values) = tail.strip_prefix("!=") {
+                    ValueMatcher::NoneOf(Self::parse_values(values)?)
+                } else if let Some(regex) = tail.strip_prefix("!~") {
+                    ValueMatcher::NotRegex(Regex::new(regex)?)
+                } else if let Some(values) = tail.strip_prefix('=') {
+                    if values == "*" {
+                        ValueMatcher::Any
+                    } else {
+                        ValueMatcher::OneOf(Self::parse_values(values)?)
+                    }
+                } else if let Some(regex) = tail.strip_prefix('~') {
+                    ValueMatcher::Regex(Regex::new(regex)?)
+                } else {
+                    return Err(anyhow!("Invalid operator in tag expression: {}", expression));
+                };
+                (key, value)
+            }
+        };
+        if key.is_empty() {
+            return Err(anyhow!("Missing key in tag expression: {}", expression));
+        }
+
+        Ok(
+            TagExpression {
+                expression: expression.to_string(),
+                nodes,
+                ways,
+                relations,
+                negated,
+                key: TextMatcher::parse(key)
+                    .with_context(|| anyhow!("expression: {}", expression))?,
+                value,
+            }
+        )
+    }
+}
+
+impl Display for TagExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(pairs: &[(&str, &str)]) -> Vec<Tag> {
+        pairs.iter()
+            .map(|(k, v)| Tag::new(k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn matches(expression: &str, pairs: &[(&str, &str)]) -> bool {
+        TagExpression::from_str(expression).unwrap().matches_tags(&tags(pairs))
+    }
+
+    #[test]
+    fn test_matches_tags() {
+        assert!(matches("highway", &[("highway", "primary")]));
+        assert!(!matches("highway", &[("building", "yes")]));
+        assert!(matches("highway=*", &[("highway", "primary")]));
+        assert!(matches("highway=primary", &[("highway", "primary")]));
+        assert!(!matches("highway=primary", &[("highway", "secondary")]));
+        assert!(matches("highway=primary,secondary", &[("highway", "secondary")]));
+        assert!(matches("highway!=primary,secondary", &[("highway", "residential")]));
+        assert!(!matches("highway!=primary,secondary", &[("highway", "primary")]));
+        assert!(!matches("highway!=primary", &[("building", "yes")]));
+        assert!(matches("addr:*", &[("addr:street", "Main Street")]));
+        assert!(matches("name=*Street", &[("name", "Main Street")]));
+        assert!(!matches("name=*Street", &[("name", "Main Road")]));
+        assert!(matches("name~^Ma.n", &[("name", "Main Street")]));
+        assert!(!matches("name!~^Ma.n", &[("name", "Main Street")]));
+        assert!(matches("!highway", &[("building", "yes")]));
+        assert!(!matches("!highway", &[("highway", "primary")]));
+        assert!(matches("!highway=primary", &[("highway", "secondary")]));
+    }
+
+    #[test]
+    fn test_types() -> Result<(), anyhow::Error> {
+        let expression = TagExpression::from_str("nw/highway")?;
+        assert!(expression.nodes() && expression.ways() && !expression.relations());
+        let expression = TagExpression::from_str("r/type=multipolygon")?;
+        assert!(!expression.nodes() && !expression.ways() && expression.relations());
+        let expression = TagExpression::from_str("source/url")?;
+        assert!(expression.nodes() && expression.ways() && expression.relations());
+        assert_eq!(expression.to_string(), "source/url");
+        assert!(TagExpression::from_str("w/").is_err());
+        assert!(TagExpression::from_str("=primary").is_err());
+        assert!(TagExpression::from_str("highway!primary").is_err());
+        assert!(TagExpression::from_str("name~[").is_err());
+        Ok(())
+    }
+}
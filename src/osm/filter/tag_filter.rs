@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+use crate::osm::filter::tag_expression::TagExpression;
+use crate::osm::model::element::Element;
+use crate::osm::pbf;
+use crate::osm::pbf::compression_type::CompressionType;
+
+/// A list of [TagExpression]s, an element matches the filter if it matches any of the expressions
+///
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::filter::tag_filter::{filter, TagFilter};
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::pbf::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109-highways.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let tag_filter = TagFilter::parse(&["w/highway", "n/amenity=cafe,restaurant"])?;
+///     filter(&reader, &tag_filter, output_path, CompressionType::Zlib)?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TagFilter {
+    expressions: Vec<TagExpression>,
+}
+
+impl TagFilter {
+    pub fn new(expressions: Vec<TagExpression>) -> TagFilter {
+        TagFilter {
+            expressions,
+        }
+    }
+
+    /// Parse expressions, see [TagExpression] for the syntax
+    pub fn parse(expressions: &[&str]) -> Result<TagFilter, anyhow::Error> {
+        let expressions = expressions.iter()
+            .map(|expression| TagExpression::from_str(expression))
+            .collect::<Result<Vec<TagExpression>, anyhow::Error>>()?;
+        Ok(TagFilter::new(expressions))
+    }
+
+    /// Read expressions from a file, one expression per line. Empty lines are ignored, a comment
+    /// starts with a # at the start of the line or after whitespace, so values may contain #
+    pub fn from_file(path: &Path) -> Result<TagFilter, anyhow::Error> {
+        let text = fs::read_to_string(path)
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        let expressions: Vec<&str> = text.lines()
+            .map(|line| strip_comment(line).trim())
+            .filter(|line| !line.is_empty())
+            .collect();
+        Self::parse(&expressions)
+            .with_context(|| anyhow!("path: {}", path.display()))
+    }
+
+    pub fn expressions(&self) -> &Vec<TagExpression> {
+        &self.expressions
+    }
+
+    pub fn add_expression(&mut self, expression: TagExpression) {
+        self.expressions.push(expression);
+    }
+
+    /// The element matches any of the expressions
+    pub fn matches(&self, element: &Element) -> bool {
+        self.expressions.iter().any(|expression| expression.matches(element))
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut previous = None;
+    for (i, c) in line.char_indices() {
+        if c == '#' && previous.is_none_or(char::is_whitespace) {
+            return &line[..i];
+        }
+        previous = Some(c);
+    }
+    line
+}
+
+/// Write the elements that match the filter
+///
+/// Returns the number of written elements.
+pub fn filter(reader: &pbf::reader::Reader, tag_filter: &TagFilter, output_path: PathBuf, compression_type: CompressionType) -> Result<u64, anyhow::Error> {
+    let mut writer = pbf::writer::Writer::from_file_info(output_path, reader.info().clone(), compression_type)?;
+    writer.write_header()?;
+    let mut count = 0;
+    for element in reader.elements()? {
+        if tag_filter.matches(&element) {
+            writer.write_element(element)?;
+            count += 1;
+        }
+    }
+    writer.close()?;
+    Ok(count)
+}
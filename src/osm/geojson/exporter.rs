@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use json::JsonValue;
+
+use crate::osm::converters::timestamp_to_iso8601_seconds;
+use crate::osm::geojson::writer::Writer;
+use crate::osm::geometry::area::{Area, AreaSource};
+use crate::osm::geometry::area_assembler::{AreaAssembler, GeometryProblem, InvalidGeometry};
+use crate::osm::geometry::polygon::Polygon;
+use crate::osm::geometry::ring::Ring;
+use crate::osm::index::node_location_index::NodeLocationIndex;
+use crate::osm::model::coordinate::Coordinate;
+use crate::osm::model::element::Element;
+use crate::osm::model::relation::Relation;
+use crate::osm::model::tag::Tag;
+use crate::osm::model::way::Way;
+use crate::osm::pbf;
+
+/// Convert OSM elements into GeoJSON features, the equivalent of osmium export
+///
+/// * nodes with tags become Points
+/// * ways become Polygons if they are areas, see
+///   [crate::osm::geometry::area_assembler::is_area_way], and LineStrings otherwise
+/// * multipolygon and boundary relations become MultiPolygons
+///
+/// Tags are written as feature properties. Optionally, the element metadata is added to the
+/// properties as @type, @id, @version, @changeset, @timestamp, @uid and @user attributes. The
+/// feature id is the element type initial followed by the element id, e.g. "w123".
+///
+/// Relations must be registered with [Exporter::add_relation] before their member ways are seen,
+/// [Exporter::run] does that in a separate pass over the *.osm.pbf input.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use std::sync::Arc;
+/// use osm_io::osm::geojson::exporter::Exporter;
+/// use osm_io::osm::geojson::file_type::FileType;
+/// use osm_io::osm::geojson::writer::Writer;
+/// use osm_io::osm::index::sparse_mem_location_index::SparseMemLocationIndex;
+/// use osm_io::osm::xml::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109.geojsonseq");
+///     let mut writer = Writer::new(output_path, FileType::GeoJsonSeq, CompressionType::Uncompressed)?;
+///     let mut exporter = Exporter::new(Arc::new(SparseMemLocationIndex::new()));
+///     exporter.with_metadata(true);
+///     exporter.run(&input_path, &mut writer)?;
+///     for report in exporter.invalid() {
+///         println!("{}", report);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Exporter {
+    index: Arc<dyn NodeLocationIndex>,
+    assembler: AreaAssembler,
+    metadata: bool,
+    keep_untagged: bool,
+    relation_attributes: HashMap<i64, JsonValue>,
+    invalid: Vec<InvalidGeometry>,
+}
+
+impl Exporter {
+    /// Create a new exporter using the index to store the node locations
+    pub fn new(index: Arc<dyn NodeLocationIndex>) -> Exporter {
+        Exporter {
+            index: index.clone(),
+            assembler: AreaAssembler::new(index),
+            metadata: false,
+            keep_untagged: false,
+            relation_attributes: HashMap::new(),
+            invalid: Vec::new(),
+        }
+    }
+
+    /// Add or omit the @version, @changeset, @timestamp, @uid and @user properties. Missing
+    /// metadata is omitted in any case
+    pub fn with_metadata(&mut self, metadata: bool) {
+        self.metadata = metadata;
+    }
+
+    /// Export elements without tags. By default untagged nodes and ways are skipped
+    pub fn with_keep_untagged(&mut self, keep_untagged: bool) {
+        self.keep_untagged = keep_untagged;
+    }
+
+    /// Reports of the ways and relations that could not be exported because of their geometry
+    pub fn invalid(&self) -> &Vec<InvalidGeometry> {
+        &self.invalid
+    }
+
+    /// Register a relation to be exported by [Exporter::finish]. Returns false if the relation is
+    /// not a multipolygon or boundary relation
+    pub fn add_relation(&mut self, relation: &Relation) -> bool {
+        if !self.assembler.add_relation(relation) {
+            return false;
+        }
+        // kept regardless of the metadata flag, which is applied when the feature is emitted
+        let attributes = attributes("relation", relation.id(), relation.version(), relation.timestamp(), relation.changeset(), relation.uid(), relation.user());
+        self.relation_attributes.insert(relation.id(), attributes);
+        true
+    }
+
+    /// Convert a node or a way into a feature
+    ///
+    /// Node locations are stored in the index so nodes must be passed before the ways that
+    /// reference them. Returns None for untagged and deleted elements, for relations, and for ways
+    /// with invalid geometry. The latter are reported in [Exporter::invalid].
+    pub fn feature(&mut self, element: &Element) -> Result<Option<JsonValue>, anyhow::Error> {
+        match element {
+            Element::Node { node } => {
+                if !node.visible() {
+                    return Ok(None);
+                }
+                self.index.set(node.id(), node.coordinate())?;
+                if node.tags().is_empty() && !self.keep_untagged {
+                    return Ok(None);
+                }
+                let properties = self.properties(
+                    node.tags(),
+                    || attributes("node", node.id(), node.version(), node.timestamp(), node.changeset(), node.uid(), node.user()),
+                );
+                Ok(Some(feature(format!("n{}", node.id()), point(node.coordinate()), properties)))
+            }
+            Element::Way { way } => {
+                if !way.visible() {
+                    return Ok(None);
+                }
+                let area = self.assembler.add_way(way);
+                if way.tags().is_empty() && !self.keep_untagged {
+                    return Ok(None);
+                }
+                let geometry = match area {
+                    Some(Ok(area)) => {
+                        polygon(&area.polygons()[0])
+                    }
+                    Some(Err(report)) => {
+                        self.invalid.push(report);
+                        return Ok(None);
+                    }
+                    None => {
+                        match self.way_coordinates(way) {
+                            Ok(coordinates) => {
+                                line_string(&coordinates)
+                            }
+                            Err(problem) => {
+                                self.invalid.push(InvalidGeometry::new(AreaSource::Way(way.id()), problem));
+                                return Ok(None);
+                            }
+                        }
+                    }
+                };
+                let properties = self.properties(
+                    way.tags(),
+                    || attributes("way", way.id(), way.version(), way.timestamp(), way.changeset(), way.uid(), way.user()),
+                );
+                Ok(Some(feature(format!("w{}", way.id()), geometry, properties)))
+            }
+            Element::Relation { .. } | Element::Sentinel => {
+                Ok(None)
+            }
+        }
+    }
+
+    /// Assemble the registered relations into MultiPolygon features
+    ///
+    /// Must be called after all ways were passed to [Exporter::feature]
+    pub fn finish(&mut self) -> Vec<JsonValue> {
+        let mut features = Vec::new();
+        for result in self.assembler.assemble_relations() {
+            match result {
+                Ok(area) => {
+                    features.push(self.relation_feature(&area));
+                }
+                Err(report) => {
+                    self.invalid.push(report);
+                }
+            }
+        }
+        self.relation_attributes.clear();
+        features
+    }
+
+    /// Export a *.osm.pbf file
+    ///
+    /// The relations are registered in a first pass, the second pass exports nodes and ways and
+    /// finally the relations.
+    pub fn run(&mut self, input_path: &Path, writer: &mut Writer) -> Result<(), anyhow::Error> {
+        let reader = pbf::reader::Reader::new(input_path)?;
+        for element in reader.elements()? {
+            if let Element::Relation { relation } = element {
+                self.add_relation(&relation);
+            }
+        }
+
+        writer.write_header()?;
+        for element in reader.elements()? {
+            if let Element::Relation { .. } = element {
+                break;
+            }
+            if let Some(feature) = self.feature(&element)
+                .with_context(|| anyhow!("input: {}", input_path.display()))? {
+                writer.write_feature(&feature)?;
+            }
+        }
+        writer.write_features(&self.finish())?;
+        writer.close()
+    }
+
+    fn relation_feature(&mut self, area: &Area) -> JsonValue {
+        let id = match area.source() {
+            AreaSource::Way(id) | AreaSource::Relation(id) => {
+                *id
+            }
+        };
+        let attributes = self.relation_attributes.remove(&id);
+        let properties = self.properties(area.tags(), || attributes.unwrap_or(JsonValue::new_object()));
+        feature(format!("r{}", id), multi_polygon(area.polygons()), properties)
+    }
+
+    fn way_coordinates(&self, way: &Way) -> Result<Vec<Coordinate>, GeometryProblem> {
+        if !way.coordinates().is_empty() && way.coordinates().len() == way.refs().len() {
+            return Ok(way.coordinates().clone());
+        }
+        way.refs().iter()
+            .map(|node_id| {
+                self.index.get(*node_id)
+                    .ok_or(GeometryProblem::MissingNodeLocation { way_id: way.id(), node_id: *node_id })
+            })
+            .collect()
+    }
+
+    fn properties(&self, tags: &[Tag], attributes: impl FnOnce() -> JsonValue) -> JsonValue {
+        let mut properties = if self.metadata {
+            attributes()
+        } else {
+            JsonValue::new_object()
+        };
+        for tag in tags {
+            properties[tag.k().as_str()] = tag.v().as_str().into();
+        }
+        properties
+    }
+}
+
+fn attributes(element_type: &str, id: i64, version: i32, timestamp: i64, changeset: i64, uid: i32, user: &str) -> JsonValue {
+    let mut value = JsonValue::new_object();
+    value["@type"] = element_type.into();
+    value["@id"] = id.into();
+    if version > 0 {
+        value["@version"] = version.into();
+    }
+    if changeset >= 0 {
+        value["@changeset"] = changeset.into();
+    }
+    if timestamp >= 0 {
+        if let Ok(timestamp) = timestamp_to_iso8601_seconds(timestamp * 1000) {
+            value["@timestamp"] = timestamp.into();
+        }
+    }
+    if uid >= 0 {
+        value["@uid"] = uid.into();
+    }
+    if !user.is_empty() {
+        value["@user"] = user.into();
+    }
+    value
+}
+
+fn position(coordinate: &Coordinate) -> JsonValue {
+    json::array![coordinate.lon7() as f64 / 1E7, coordinate.lat7() as f64 / 1E7]
+}
+
+fn ring(ring: &Ring) -> JsonValue {
+    JsonValue::Array(ring.coordinates().iter().map(position).collect())
+}
+
+fn polygon_coordinates(polygon: &Polygon) -> JsonValue {
+    let mut rings = vec![ring(polygon.outer())];
+    rings.extend(polygon.inners().iter().map(ring));
+    JsonValue::Array(rings)
+}
+
+/// GeoJSON Point geometry
+pub fn point(coordinate: &Coordinate) -> JsonValue {
+    json::object! {
+        "type": "Point",
+        "coordinates": position(coordinate),
+    }
+}
+
+/// GeoJSON LineString geometry
+pub fn line_string(coordinates: &[Coordinate]) -> JsonValue {
+    json::object! {
+        "type": "LineString",
+        "coordinates": JsonValue::Array(coordinates.iter().map(position).collect()),
+    }
+}
+
+/// GeoJSON Polygon geometry, the outer ring followed by the inner rings
+pub fn polygon(polygon: &Polygon) -> JsonValue {
+    json::object! {
+        "type": "Polygon",
+        "coordinates": polygon_coordinates(polygon),
+    }
+}
+
+/// GeoJSON MultiPolygon geometry
+pub fn multi_polygon(polygons: &[Polygon]) -> JsonValue {
+    json::object! {
+        "type": "MultiPolygon",
+        "coordinates": JsonValue::Array(polygons.iter().map(polygon_coordinates).collect()),
+    }
+}
+
+/// GeoJSON Feature
+pub fn feature(id: String, geometry: JsonValue, properties: JsonValue) -> JsonValue {
+    json::object! {
+        "type": "Feature",
+        "id": id,
+        "geometry": geometry,
+        "properties": properties,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polygon() {
+        let outer = Ring::from_coordinates(
+            vec![
+                Coordinate::new(0.0, 0.0),
+                Coordinate::new(0.0, 1.5),
+                Coordinate::new(1.0, 1.5),
+            ]
+        );
+        let value = polygon(&Polygon::new(outer, Vec::new()));
+        assert_eq!(
+            value.dump(),
+            r#"{"type":"Polygon","coordinates":[[[0,0],[1.5,0],[1.5,1],[0,0]]]}"#
+        );
+    }
+}
@@ -0,0 +1,7 @@
+/// Distinguish between a single GeoJSON FeatureCollection (*.geojson) and newline delimited
+/// features (*.geojsonseq)
+#[derive(Clone, Debug, PartialEq)]
+pub enum FileType {
+    GeoJson,
+    GeoJsonSeq,
+}
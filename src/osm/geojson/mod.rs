@@ -0,0 +1,3 @@
+pub mod file_type;
+pub mod writer;
+pub mod exporter;
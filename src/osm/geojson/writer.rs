@@ -0,0 +1,113 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context};
+use json::JsonValue;
+
+use crate::osm::geojson::file_type::FileType;
+use crate::osm::xml::compression_type::CompressionType;
+use crate::osm::xml::writer::Output;
+
+/// ASCII record separator that starts every feature in GeoJSON text sequences, see RFC 8142
+const RECORD_SEPARATOR: u8 = 0x1e;
+
+/// *.geojson and *.geojsonseq file writer
+///
+/// Write GeoJSON features either as a single FeatureCollection, one feature per line, or as a
+/// GeoJSON text sequence, optionally compressed with gzip or bzip2. The features are produced by
+/// [crate::osm::geojson::exporter::Exporter].
+pub struct Writer {
+    path: PathBuf,
+    file_type: FileType,
+    record_separator: bool,
+    first: bool,
+    output: Option<Output>,
+}
+
+impl Writer {
+    /// Create a new [Writer]
+    ///
+    /// GeoJSON text sequences start every feature with the record separator by default, see
+    /// [Writer::with_record_separator]
+    pub fn new(
+        path: PathBuf,
+        file_type: FileType,
+        compression_type: CompressionType,
+    ) -> Result<Writer, anyhow::Error> {
+        let file = File::create(path.clone())
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        Ok(
+            Writer {
+                path,
+                file_type,
+                record_separator: true,
+                first: true,
+                output: Some(Output::new(file, &compression_type)),
+            }
+        )
+    }
+
+    /// Write or omit the record separator before every feature of a GeoJSON text sequence
+    pub fn with_record_separator(&mut self, record_separator: bool) {
+        self.record_separator = record_separator;
+    }
+
+    fn output(&mut self) -> Result<&mut Output, anyhow::Error> {
+        self.output.as_mut()
+            .ok_or(anyhow!("Writer is closed, path: {}", self.path.display()))
+    }
+
+    /// Open the FeatureCollection. Does nothing for GeoJSON text sequences
+    ///
+    /// Must be called before writing features.
+    pub fn write_header(&mut self) -> Result<(), anyhow::Error> {
+        if self.file_type == FileType::GeoJson {
+            self.output()?.write_all(b"{\"type\":\"FeatureCollection\",\"features\":[")?;
+        }
+        Ok(())
+    }
+
+    /// Write feature
+    pub fn write_feature(&mut self, feature: &JsonValue) -> Result<(), anyhow::Error> {
+        let (prefix, suffix): (&[u8], &[u8]) = match self.file_type {
+            FileType::GeoJson => {
+                (if self.first { b"\n" } else { b",\n" }, b"")
+            }
+            FileType::GeoJsonSeq => {
+                (if self.record_separator { &[RECORD_SEPARATOR] } else { b"" }, b"\n")
+            }
+        };
+        self.first = false;
+        let output = self.output()?;
+        output.write_all(prefix)?;
+        output.write_all(feature.dump().as_bytes())?;
+        output.write_all(suffix)?;
+        Ok(())
+    }
+
+    /// Write features
+    pub fn write_features(&mut self, features: &[JsonValue]) -> Result<(), anyhow::Error> {
+        for feature in features {
+            self.write_feature(feature)?;
+        }
+        Ok(())
+    }
+
+    /// Close the FeatureCollection and flush the compression stream
+    ///
+    /// Must be called in the end. The writer can not be used after it was closed
+    pub fn close(&mut self) -> Result<(), anyhow::Error> {
+        if self.file_type == FileType::GeoJson {
+            self.output()?.write_all(b"\n]}\n")?;
+        }
+        self.output.take()
+            .ok_or(anyhow!("Writer is closed, path: {}", self.path.display()))?
+            .finish()
+    }
+
+    /// Output path
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt::{Display, Formatter};
+
+use crate::osm::geometry::polygon::Polygon;
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::coordinate::Coordinate;
+use crate::osm::model::tag::Tag;
+
+/// The OSM object an area was assembled from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AreaSource {
+    Way(i64),
+    Relation(i64),
+}
+
+impl AreaSource {
+    /// Area id derived from the object id the same way as osmium does: way ids are multiplied by
+    /// 2, relation ids are multiplied by 2 plus 1
+    pub fn area_id(&self) -> i64 {
+        match self {
+            AreaSource::Way(id) => {
+                id * 2
+            }
+            AreaSource::Relation(id) => {
+                id * 2 + 1
+            }
+        }
+    }
+}
+
+impl Display for AreaSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AreaSource::Way(id) => {
+                write!(f, "way {}", id)
+            }
+            AreaSource::Relation(id) => {
+                write!(f, "relation {}", id)
+            }
+        }
+    }
+}
+
+/// An area assembled from a closed way or from a multipolygon or boundary relation
+///
+/// A closed way always produces a single polygon without holes. Rings are oriented as required by
+/// GeoJSON, outer rings counter clockwise and inner rings clockwise.
+#[derive(Debug, Clone)]
+pub struct Area {
+    source: AreaSource,
+    tags: Vec<Tag>,
+    polygons: Vec<Polygon>,
+}
+
+impl Area {
+    pub fn new(source: AreaSource, tags: Vec<Tag>, polygons: Vec<Polygon>) -> Area {
+        Area {
+            source,
+            tags,
+            polygons,
+        }
+    }
+
+    /// See [AreaSource::area_id]
+    pub fn id(&self) -> i64 {
+        self.source.area_id()
+    }
+
+    pub fn source(&self) -> &AreaSource {
+        &self.source
+    }
+
+    pub fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    pub fn take_tags(&mut self) -> Vec<Tag> {
+        std::mem::take(&mut self.tags)
+    }
+
+    pub fn polygons(&self) -> &Vec<Polygon> {
+        &self.polygons
+    }
+
+    /// Total area in square degrees
+    pub fn area(&self) -> f64 {
+        self.polygons.iter().map(|polygon| polygon.area()).sum()
+    }
+
+    pub fn contains(&self, coordinate: &Coordinate) -> bool {
+        self.polygons.iter().any(|polygon| polygon.contains(coordinate))
+    }
+
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let mut result: Option<BoundingBox> = None;
+        for bounding_box in self.polygons.iter().filter_map(|polygon| polygon.bounding_box()) {
+            match result.as_mut() {
+                None => {
+                    result = Some(bounding_box);
+                }
+                Some(result) => {
+                    result.merge_bounding_box(&bounding_box);
+                }
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,432 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+use crate::osm::geometry::area::{Area, AreaSource};
+use crate::osm::geometry::polygon::Polygon;
+use crate::osm::geometry::ring::Ring;
+use crate::osm::index::node_location_index::{NodeLocationIndex, parallel_fill};
+use crate::osm::model::coordinate::Coordinate;
+use crate::osm::model::element::Element;
+use crate::osm::model::relation::{Member, Relation};
+use crate::osm::model::tag::Tag;
+use crate::osm::model::way::Way;
+use crate::osm::pbf;
+use crate::osm::transform::locations_on_ways::LOCATIONS_ON_WAYS;
+
+/// The reason an area could not be assembled
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeometryProblem {
+    /// A member way is not in the input
+    MissingWay { way_id: i64 },
+    /// A node of a way has no location
+    MissingNodeLocation { way_id: i64, node_id: i64 },
+    /// The member ways can not be stitched into closed rings
+    UnclosedRing { first_node_id: i64, last_node_id: i64 },
+    /// A ring with less than 4 points or with zero area
+    DegenerateRing { first_node_id: i64 },
+    /// There are no rings that are not inside another ring
+    NoOuterRing,
+}
+
+impl Display for GeometryProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeometryProblem::MissingWay { way_id } => {
+                write!(f, "missing way {}", way_id)
+            }
+            GeometryProblem::MissingNodeLocation { way_id, node_id } => {
+                write!(f, "missing location of node {} in way {}", node_id, way_id)
+            }
+            GeometryProblem::UnclosedRing { first_node_id, last_node_id } => {
+                write!(f, "unclosed ring from node {} to node {}", first_node_id, last_node_id)
+            }
+            GeometryProblem::DegenerateRing { first_node_id } => {
+                write!(f, "degenerate ring starting at node {}", first_node_id)
+            }
+            GeometryProblem::NoOuterRing => {
+                write!(f, "no outer ring")
+            }
+        }
+    }
+}
+
+/// Report of an area that could not be assembled
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidGeometry {
+    source: AreaSource,
+    problem: GeometryProblem,
+}
+
+impl InvalidGeometry {
+    pub fn new(source: AreaSource, problem: GeometryProblem) -> InvalidGeometry {
+        InvalidGeometry {
+            source,
+            problem,
+        }
+    }
+
+    pub fn source(&self) -> &AreaSource {
+        &self.source
+    }
+
+    pub fn problem(&self) -> &GeometryProblem {
+        &self.problem
+    }
+}
+
+impl Display for InvalidGeometry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid geometry of {}: {}", self.source, self.problem)
+    }
+}
+
+impl std::error::Error for InvalidGeometry {}
+
+/// Node ids and locations of a way
+#[derive(Debug, Clone)]
+struct Segment {
+    node_ids: Vec<i64>,
+    coordinates: Vec<Coordinate>,
+}
+
+/// A relation is assembled into an area if it is of type multipolygon or boundary
+pub fn is_area_relation(relation: &Relation) -> bool {
+    relation.visible() && relation.tags().iter()
+        .any(|tag| tag.k() == "type" && (tag.v() == "multipolygon" || tag.v() == "boundary"))
+}
+
+/// Tag keys that make a closed way an area
+const AREA_KEYS: [&str; 18] = [
+    "aeroway", "amenity", "building", "building:part", "craft", "historic", "landuse", "leisure",
+    "man_made", "military", "natural", "office", "place", "public_transport", "shop", "sport",
+    "tourism", "water",
+];
+
+/// Tags with an area key that are nevertheless lines
+const LINEAR_TAGS: [(&str, &str); 9] = [
+    ("aeroway", "runway"), ("aeroway", "taxiway"), ("man_made", "embankment"),
+    ("man_made", "pipeline"), ("natural", "arete"), ("natural", "cliff"), ("natural", "coastline"),
+    ("natural", "ridge"), ("natural", "tree_row"),
+];
+
+/// Tags with a key that is otherwise linear, such as waterway, that make a closed way an area
+const AREA_TAGS: [(&str, &str); 2] = [
+    ("waterway", "dock"), ("waterway", "riverbank"),
+];
+
+fn is_area_tag(tag: &Tag) -> bool {
+    let pair = (tag.k().as_str(), tag.v().as_str());
+    (AREA_KEYS.contains(&pair.0) && !LINEAR_TAGS.contains(&pair)) || AREA_TAGS.contains(&pair)
+}
+
+/// A way is assembled into an area if it is closed, has at least 4 nodes and its tags make it an
+/// area:
+/// * area=yes makes any closed way an area, area=no makes it a line
+/// * otherwise it is an area if it has an area key, such as building, landuse, natural or leisure,
+///   unless the value is linear, such as natural=coastline
+/// * ways with only linear keys, such as highway, barrier or railway, are lines
+pub fn is_area_way(way: &Way) -> bool {
+    if !way.visible() || way.refs().len() < 4 || way.refs().first() != way.refs().last() {
+        return false;
+    }
+    match way.tags().iter().find(|tag| tag.k() == "area").map(|tag| tag.v().as_str()) {
+        Some("yes") => true,
+        Some("no") => false,
+        _ => way.tags().iter().any(is_area_tag),
+    }
+}
+
+/// Assemble areas from closed ways and from multipolygon and boundary relations
+///
+/// The relations must be known before their member ways are seen, and the node locations must be
+/// available when the ways are added. Since *.osm.pbf files are sorted by type, this takes two
+/// passes, see [assemble_areas]:
+/// * first pass - [AreaAssembler::add_relation] for all relations
+/// * second pass - store the node locations in the index, then [AreaAssembler::add_way] for all
+///   ways, and finally [AreaAssembler::assemble_relations]
+///
+/// Way node locations are taken from the way itself if the input has the LocationsOnWays feature
+/// and from the index otherwise. The rings of a relation are stitched from the member ways
+/// regardless of the member roles, inner and outer rings are determined by the geometry.
+pub struct AreaAssembler {
+    index: Arc<dyn NodeLocationIndex>,
+    relations: Vec<Relation>,
+    member_way_ids: HashSet<i64>,
+    member_ways: HashMap<i64, Result<Segment, GeometryProblem>>,
+}
+
+impl AreaAssembler {
+    pub fn new(index: Arc<dyn NodeLocationIndex>) -> AreaAssembler {
+        AreaAssembler {
+            index,
+            relations: Vec::new(),
+            member_way_ids: HashSet::new(),
+            member_ways: HashMap::new(),
+        }
+    }
+
+    /// Register a relation for assembly. Returns false if the relation is not an area relation
+    pub fn add_relation(&mut self, relation: &Relation) -> bool {
+        if !is_area_relation(relation) {
+            return false;
+        }
+        for member in relation.members() {
+            if let Member::Way { member } = member {
+                self.member_way_ids.insert(member.id());
+            }
+        }
+        self.relations.push(relation.clone());
+        true
+    }
+
+    /// Keep the way if it is a member of a registered relation, and assemble it if it is an area
+    /// by itself
+    ///
+    /// Returns None if the way is not an area, see [is_area_way].
+    pub fn add_way(&mut self, way: &Way) -> Option<Result<Area, InvalidGeometry>> {
+        if self.member_way_ids.contains(&way.id()) && way.visible() {
+            self.member_ways.insert(way.id(), self.segment(way));
+        }
+        if is_area_way(way) {
+            Some(self.assemble_way(way))
+        } else {
+            None
+        }
+    }
+
+    /// Assemble a closed way into an area regardless of its tags
+    pub fn assemble_way(&self, way: &Way) -> Result<Area, InvalidGeometry> {
+        let source = AreaSource::Way(way.id());
+        let segment = self.segment(way)
+            .map_err(|problem| InvalidGeometry::new(source, problem))?;
+        let ring = Ring::new(segment.node_ids, segment.coordinates);
+        if !ring.is_closed() {
+            let problem = GeometryProblem::UnclosedRing {
+                first_node_id: *ring.node_ids().first().unwrap_or(&0),
+                last_node_id: *ring.node_ids().last().unwrap_or(&0),
+            };
+            return Err(InvalidGeometry::new(source, problem));
+        }
+        Self::validate_ring(&ring)
+            .map_err(|problem| InvalidGeometry::new(source, problem))?;
+        let mut polygon = Polygon::new(ring, Vec::new());
+        polygon.normalize_orientation();
+        Ok(Area::new(source, way.tags().clone(), vec![polygon]))
+    }
+
+    /// Assemble all registered relations. The registered relations and the kept ways are released
+    pub fn assemble_relations(&mut self) -> Vec<Result<Area, InvalidGeometry>> {
+        let relations = std::mem::take(&mut self.relations);
+        let results = relations.iter()
+            .map(|relation| self.assemble_relation(relation))
+            .collect();
+        self.member_way_ids.clear();
+        self.member_ways.clear();
+        results
+    }
+
+    /// Assemble a single relation from the member ways added so far
+    pub fn assemble_relation(&self, relation: &Relation) -> Result<Area, InvalidGeometry> {
+        let source = AreaSource::Relation(relation.id());
+        let mut segments = Vec::new();
+        for member in relation.members() {
+            if let Member::Way { member } = member {
+                match self.member_ways.get(&member.id()) {
+                    None => {
+                        return Err(InvalidGeometry::new(source, GeometryProblem::MissingWay { way_id: member.id() }));
+                    }
+                    Some(Err(problem)) => {
+                        return Err(InvalidGeometry::new(source, problem.clone()));
+                    }
+                    Some(Ok(segment)) => {
+                        if segment.node_ids.len() > 1 {
+                            segments.push(segment.clone());
+                        }
+                    }
+                }
+            }
+        }
+        let rings = Self::stitch(segments)
+            .map_err(|problem| InvalidGeometry::new(source, problem))?;
+        let polygons = Self::build_polygons(rings)
+            .map_err(|problem| InvalidGeometry::new(source, problem))?;
+        let tags: Vec<Tag> = relation.tags().iter()
+            .filter(|tag| tag.k() != "type")
+            .cloned()
+            .collect();
+        Ok(Area::new(source, tags, polygons))
+    }
+
+    fn segment(&self, way: &Way) -> Result<Segment, GeometryProblem> {
+        let coordinates = if !way.coordinates().is_empty() && way.coordinates().len() == way.refs().len() {
+            way.coordinates().clone()
+        } else {
+            let mut coordinates = Vec::with_capacity(way.refs().len());
+            for node_id in way.refs() {
+                let coordinate = self.index.get(*node_id)
+                    .ok_or(GeometryProblem::MissingNodeLocation { way_id: way.id(), node_id: *node_id })?;
+                coordinates.push(coordinate);
+            }
+            coordinates
+        };
+        Ok(
+            Segment {
+                node_ids: way.refs().clone(),
+                coordinates,
+            }
+        )
+    }
+
+    fn validate_ring(ring: &Ring) -> Result<(), GeometryProblem> {
+        if ring.coordinates().len() < 4 || ring.area() == 0.0 {
+            Err(GeometryProblem::DegenerateRing { first_node_id: *ring.node_ids().first().unwrap_or(&0) })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Join segments that share end nodes into closed rings
+    fn stitch(segments: Vec<Segment>) -> Result<Vec<Ring>, GeometryProblem> {
+        let mut pool = segments;
+        pool.reverse();
+        let mut rings = Vec::new();
+        while let Some(segment) = pool.pop() {
+            let mut node_ids = segment.node_ids;
+            let mut coordinates = segment.coordinates;
+            while node_ids.first() != node_ids.last() {
+                let last = *node_ids.last().unwrap();
+                let position = pool.iter()
+                    .rposition(|s| s.node_ids.first() == Some(&last) || s.node_ids.last() == Some(&last))
+                    .ok_or(GeometryProblem::UnclosedRing { first_node_id: node_ids[0], last_node_id: last })?;
+                let mut next = pool.remove(position);
+                if next.node_ids.first() != Some(&last) {
+                    next.node_ids.reverse();
+                    next.coordinates.reverse();
+                }
+                node_ids.extend_from_slice(&next.node_ids[1..]);
+                coordinates.extend_from_slice(&next.coordinates[1..]);
+            }
+            let ring = Ring::new(node_ids, coordinates);
+            Self::validate_ring(&ring)?;
+            rings.push(ring);
+        }
+        Ok(rings)
+    }
+
+    /// A point of the ring that is not a vertex of the other ring, used to test containment
+    fn sample_point<'a>(ring: &'a Ring, other: &Ring) -> &'a Coordinate {
+        let other_ids: HashSet<&i64> = other.node_ids().iter().collect();
+        ring.node_ids().iter()
+            .position(|id| !other_ids.contains(id))
+            .map(|i| &ring.coordinates()[i])
+            .unwrap_or(&ring.coordinates()[0])
+    }
+
+    /// Classify rings into outer and inner rings by nesting depth. Rings at even depth are outer
+    /// rings, rings at odd depth are holes in the smallest ring that contains them
+    fn build_polygons(mut rings: Vec<Ring>) -> Result<Vec<Polygon>, GeometryProblem> {
+        rings.sort_by(|a, b| b.area().total_cmp(&a.area()));
+        let mut depths = Vec::with_capacity(rings.len());
+        let mut parents = Vec::with_capacity(rings.len());
+        for (i, ring) in rings.iter().enumerate() {
+            let mut depth = 0;
+            let mut parent = None;
+            for (j, candidate) in rings.iter().enumerate().take(i) {
+                if candidate.contains(Self::sample_point(ring, candidate)) {
+                    depth += 1;
+                    parent = Some(j);
+                }
+            }
+            depths.push(depth);
+            parents.push(parent);
+        }
+
+        let mut polygons: Vec<Polygon> = Vec::new();
+        let mut polygon_of_ring = vec![usize::MAX; rings.len()];
+        for (i, ring) in rings.into_iter().enumerate() {
+            if depths[i] % 2 == 0 {
+                polygon_of_ring[i] = polygons.len();
+                polygons.push(Polygon::new(ring, Vec::new()));
+            } else {
+                let parent = parents[i].unwrap();
+                polygons[polygon_of_ring[parent]].add_inner(ring);
+            }
+        }
+        if polygons.is_empty() {
+            return Err(GeometryProblem::NoOuterRing);
+        }
+        for polygon in &mut polygons {
+            polygon.normalize_orientation();
+        }
+        Ok(polygons)
+    }
+}
+
+/// Assemble all areas in a *.osm.pbf file
+///
+/// * reader - *.osm.pbf reader
+/// * index - node location index, filled in parallel during the second pass
+/// * tasks - number of threads used to fill the index
+///
+/// Returns the assembled areas and the reports of the areas that could not be assembled.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use std::sync::Arc;
+/// use osm_io::osm::geometry::area_assembler::assemble_areas;
+/// use osm_io::osm::index::sparse_mem_location_index::SparseMemLocationIndex;
+/// use osm_io::osm::pbf;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let (areas, invalid) = assemble_areas(&reader, Arc::new(SparseMemLocationIndex::new()), 4)?;
+///     for report in invalid {
+///         println!("{}", report);
+///     }
+///     println!("areas: {}", areas.len());
+///     Ok(())
+/// }
+/// ```
+pub fn assemble_areas(reader: &pbf::reader::Reader, index: Arc<dyn NodeLocationIndex>, tasks: usize) -> Result<(Vec<Area>, Vec<InvalidGeometry>), anyhow::Error> {
+    let mut assembler = AreaAssembler::new(index.clone());
+    for element in reader.elements()? {
+        if let Element::Relation { relation } = element {
+            assembler.add_relation(&relation);
+        }
+    }
+
+    if !reader.info().optional(LOCATIONS_ON_WAYS) {
+        parallel_fill(index, reader, tasks)?;
+    }
+
+    let mut results = Vec::new();
+    for element in reader.elements()? {
+        match element {
+            Element::Way { way } => {
+                if let Some(result) = assembler.add_way(&way) {
+                    results.push(result);
+                }
+            }
+            Element::Relation { .. } => {
+                break;
+            }
+            _ => {}
+        }
+    }
+    results.append(&mut assembler.assemble_relations());
+
+    let mut areas = Vec::new();
+    let mut invalid = Vec::new();
+    for result in results {
+        match result {
+            Ok(area) => {
+                areas.push(area);
+            }
+            Err(report) => {
+                invalid.push(report);
+            }
+        }
+    }
+    Ok((areas, invalid))
+}
@@ -0,0 +1,4 @@
+pub mod ring;
+pub mod polygon;
+pub mod area;
+pub mod area_assembler;
@@ -0,0 +1,59 @@
+use crate::osm::geometry::ring::Ring;
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::coordinate::Coordinate;
+
+/// A polygon with one outer ring and any number of inner rings (holes)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    outer: Ring,
+    inners: Vec<Ring>,
+}
+
+impl Polygon {
+    pub fn new(outer: Ring, inners: Vec<Ring>) -> Polygon {
+        Polygon {
+            outer,
+            inners,
+        }
+    }
+
+    pub fn outer(&self) -> &Ring {
+        &self.outer
+    }
+
+    pub fn inners(&self) -> &Vec<Ring> {
+        &self.inners
+    }
+
+    pub(crate) fn add_inner(&mut self, inner: Ring) {
+        self.inners.push(inner);
+    }
+
+    /// Orient the outer ring counter clockwise and the inner rings clockwise, as required by
+    /// GeoJSON
+    pub fn normalize_orientation(&mut self) {
+        if !self.outer.is_counter_clockwise() {
+            self.outer.reverse();
+        }
+        for inner in &mut self.inners {
+            if inner.is_counter_clockwise() {
+                inner.reverse();
+            }
+        }
+    }
+
+    /// Area of the outer ring minus the area of the holes in square degrees
+    pub fn area(&self) -> f64 {
+        self.outer.area() - self.inners.iter().map(|inner| inner.area()).sum::<f64>()
+    }
+
+    /// The point is inside the outer ring and outside all inner rings
+    pub fn contains(&self, coordinate: &Coordinate) -> bool {
+        self.outer.contains(coordinate) && !self.inners.iter().any(|inner| inner.contains(coordinate))
+    }
+
+    /// Bounding box of the outer ring
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        self.outer.bounding_box()
+    }
+}
@@ -0,0 +1,132 @@
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::coordinate::Coordinate;
+
+/// A closed line, the boundary of a polygon or of a hole in a polygon
+///
+/// Node ids are kept when the ring was built from ways, rings read from other sources, like
+/// polygon files, have only coordinates. Computations treat longitude as x and latitude as y.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ring {
+    node_ids: Vec<i64>,
+    coordinates: Vec<Coordinate>,
+}
+
+impl Ring {
+    /// Create a ring from parallel arrays of node ids and coordinates
+    pub fn new(node_ids: Vec<i64>, coordinates: Vec<Coordinate>) -> Ring {
+        Ring {
+            node_ids,
+            coordinates,
+        }
+    }
+
+    /// Create a ring without node ids. The first coordinate is appended if the ring is not closed
+    pub fn from_coordinates(mut coordinates: Vec<Coordinate>) -> Ring {
+        if coordinates.len() > 1 && coordinates.first() != coordinates.last() {
+            coordinates.push(coordinates[0].clone());
+        }
+        Ring {
+            node_ids: Vec::new(),
+            coordinates,
+        }
+    }
+
+    pub fn node_ids(&self) -> &Vec<i64> {
+        &self.node_ids
+    }
+
+    pub fn coordinates(&self) -> &Vec<Coordinate> {
+        &self.coordinates
+    }
+
+    /// The first and the last points are the same
+    pub fn is_closed(&self) -> bool {
+        if self.node_ids.is_empty() {
+            self.coordinates.len() > 1 && self.coordinates.first() == self.coordinates.last()
+        } else {
+            self.node_ids.len() > 1 && self.node_ids.first() == self.node_ids.last()
+        }
+    }
+
+    /// Shoelace area in square degrees, positive for counter clockwise rings
+    pub fn signed_area(&self) -> f64 {
+        let mut sum = 0.0;
+        for pair in self.coordinates.windows(2) {
+            sum += pair[0].lon() * pair[1].lat() - pair[1].lon() * pair[0].lat();
+        }
+        sum / 2.0
+    }
+
+    /// Area in square degrees
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    pub fn is_counter_clockwise(&self) -> bool {
+        self.signed_area() > 0.0
+    }
+
+    /// Reverse the direction of the ring
+    pub fn reverse(&mut self) {
+        self.node_ids.reverse();
+        self.coordinates.reverse();
+    }
+
+    /// Point in polygon test by ray casting. Points exactly on the boundary may be reported either
+    /// way
+    pub fn contains(&self, coordinate: &Coordinate) -> bool {
+        let (x, y) = (coordinate.lon(), coordinate.lat());
+        let mut inside = false;
+        for pair in self.coordinates.windows(2) {
+            let (x1, y1) = (pair[0].lon(), pair[0].lat());
+            let (x2, y2) = (pair[1].lon(), pair[1].lat());
+            if (y1 > y) != (y2 > y) && x < (x2 - x1) * (y - y1) / (y2 - y1) + x1 {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// Bounding box of the ring, None for an empty ring
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let mut coordinates = self.coordinates.iter();
+        let mut bounding_box = BoundingBox::from_point(coordinates.next()?);
+        for coordinate in coordinates {
+            bounding_box.merge_point(coordinate);
+        }
+        Some(bounding_box)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Ring {
+        Ring::from_coordinates(
+            vec![
+                Coordinate::new(0.0, 0.0),
+                Coordinate::new(0.0, 2.0),
+                Coordinate::new(2.0, 2.0),
+                Coordinate::new(2.0, 0.0),
+            ]
+        )
+    }
+
+    #[test]
+    fn test_ring() {
+        let mut ring = square();
+        assert!(ring.is_closed());
+        assert_eq!(ring.coordinates().len(), 5);
+        assert_eq!(ring.area(), 4.0);
+        assert!(ring.is_counter_clockwise());
+        ring.reverse();
+        assert!(!ring.is_counter_clockwise());
+        assert!(ring.contains(&Coordinate::new(1.0, 1.0)));
+        assert!(!ring.contains(&Coordinate::new(3.0, 1.0)));
+        assert!(!ring.contains(&Coordinate::new(1.0, -0.5)));
+        let bounding_box = ring.bounding_box().unwrap();
+        assert_eq!(bounding_box.right(), 2.0);
+        assert_eq!(bounding_box.bottom(), 0.0);
+    }
+}
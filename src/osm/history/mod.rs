@@ -0,0 +1,5 @@
+pub mod time_filter;
+pub mod tag_diff;
+pub mod version_change;
+pub mod object_history;
+pub mod revert;
@@ -0,0 +1,260 @@
+use crate::osm::filter::get_id::TypedId;
+use crate::osm::history::tag_diff::TagDiff;
+use crate::osm::history::version_change::{ChangeKind, NodeMove, VersionChange};
+use crate::osm::model::element::Element;
+use crate::osm::model::tag::Tag;
+
+/// All the versions of an object, in the order of the input
+///
+/// Created by [ObjectHistoryIterator] from a history file.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::history::object_history::ObjectHistoryIterator;
+/// use osm_io::osm::pbf;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/history-niue-230109.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     for history in ObjectHistoryIterator::new(reader.elements()?) {
+///         for change in history.changes() {
+///             if !change.tag_diff().removed().is_empty() {
+///                 println!("{} v{} by {} removed tags", history.typed_id(), change.version(), change.user());
+///             }
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ObjectHistory {
+    typed_id: TypedId,
+    versions: Vec<Element>,
+}
+
+impl ObjectHistory {
+    /// Create from the versions of a single object, ordered by version. None if there are no
+    /// versions, or if they are not all versions of the same object
+    pub fn new(versions: Vec<Element>) -> Option<ObjectHistory> {
+        let typed_id = TypedId::of(versions.first()?)?;
+        if !versions.iter().all(|element| TypedId::of(element) == Some(typed_id)) {
+            return None;
+        }
+        Some(
+            ObjectHistory {
+                typed_id,
+                versions,
+            }
+        )
+    }
+
+    pub fn typed_id(&self) -> TypedId {
+        self.typed_id
+    }
+
+    pub fn versions(&self) -> &Vec<Element> {
+        &self.versions
+    }
+
+    pub fn take_versions(self) -> Vec<Element> {
+        self.versions
+    }
+
+    /// The version with the given version number
+    pub fn version(&self, version: i32) -> Option<&Element> {
+        self.versions.iter().find(|element| Metadata::of(element).version == version)
+    }
+
+    pub fn latest(&self) -> &Element {
+        // an object history has at least one version
+        self.versions.last().unwrap()
+    }
+
+    /// The latest version is deleted
+    pub fn is_deleted(&self) -> bool {
+        !Metadata::of(self.latest()).visible
+    }
+
+    /// The change made by each version relative to the previous one
+    pub fn changes(&self) -> Vec<VersionChange> {
+        let mut changes = Vec::with_capacity(self.versions.len());
+        let mut previous: Option<&Element> = None;
+        for element in &self.versions {
+            changes.push(Self::change(previous, element));
+            previous = Some(element);
+        }
+        changes
+    }
+
+    /// The uids and names of the users that created the versions, in the order of their first
+    /// version
+    pub fn contributors(&self) -> Vec<(i32, String)> {
+        let mut contributors: Vec<(i32, String)> = Vec::new();
+        for element in &self.versions {
+            let metadata = Metadata::of(element);
+            if !contributors.iter().any(|(uid, _)| *uid == metadata.uid) {
+                contributors.push((metadata.uid, metadata.user.clone()));
+            }
+        }
+        contributors
+    }
+
+    fn change(previous: Option<&Element>, element: &Element) -> VersionChange {
+        let metadata = Metadata::of(element);
+        let previous_visible = previous.map(|previous| Metadata::of(previous).visible);
+        let kind = match (previous_visible, metadata.visible) {
+            (None, _) => ChangeKind::Created,
+            (Some(_), false) => ChangeKind::Deleted,
+            (Some(false), true) => ChangeKind::Restored,
+            (Some(true), true) => ChangeKind::Modified,
+        };
+        let empty = Vec::new();
+        let previous_tags = match previous {
+            Some(previous) if previous_visible == Some(true) => Metadata::of(previous).tags,
+            _ => &empty,
+        };
+        let tags = if metadata.visible { metadata.tags } else { &empty };
+
+        let mut node_move = None;
+        let mut refs_changed = false;
+        let mut members_changed = false;
+        if kind == ChangeKind::Modified {
+            match (previous, element) {
+                (Some(Element::Node { node: previous }), Element::Node { node }) if previous.coordinate() != node.coordinate() => {
+                    node_move = Some(
+                        NodeMove {
+                            from: previous.coordinate().clone(),
+                            to: node.coordinate().clone(),
+                        }
+                    );
+                }
+                (Some(Element::Way { way: previous }), Element::Way { way }) => {
+                    refs_changed = previous.refs() != way.refs();
+                }
+                (Some(Element::Relation { relation: previous }), Element::Relation { relation }) => {
+                    members_changed = previous.members() != relation.members();
+                }
+                _ => {}
+            }
+        }
+
+        VersionChange {
+            version: metadata.version,
+            timestamp: metadata.timestamp,
+            changeset: metadata.changeset,
+            uid: metadata.uid,
+            user: metadata.user.clone(),
+            kind,
+            tag_diff: TagDiff::between(previous_tags, tags),
+            node_move,
+            refs_changed,
+            members_changed,
+        }
+    }
+}
+
+/// The attributes common to all object types
+struct Metadata<'a> {
+    version: i32,
+    timestamp: i64,
+    changeset: i64,
+    uid: i32,
+    user: &'a String,
+    visible: bool,
+    tags: &'a Vec<Tag>,
+}
+
+impl<'a> Metadata<'a> {
+    fn of(element: &'a Element) -> Metadata<'a> {
+        match element {
+            Element::Node { node } => {
+                Metadata {
+                    version: node.version(),
+                    timestamp: node.timestamp(),
+                    changeset: node.changeset(),
+                    uid: node.uid(),
+                    user: node.user(),
+                    visible: node.visible(),
+                    tags: node.tags(),
+                }
+            }
+            Element::Way { way } => {
+                Metadata {
+                    version: way.version(),
+                    timestamp: way.timestamp(),
+                    changeset: way.changeset(),
+                    uid: way.uid(),
+                    user: way.user(),
+                    visible: way.visible(),
+                    tags: way.tags(),
+                }
+            }
+            Element::Relation { relation } => {
+                Metadata {
+                    version: relation.version(),
+                    timestamp: relation.timestamp(),
+                    changeset: relation.changeset(),
+                    uid: relation.uid(),
+                    user: relation.user(),
+                    visible: relation.visible(),
+                    tags: relation.tags(),
+                }
+            }
+            Element::Sentinel => {
+                panic!("Sentinel has no metadata")
+            }
+        }
+    }
+}
+
+/// Group consecutive versions of the same object into [ObjectHistory]s
+///
+/// [Element::Sentinel]s are dropped. The input must be sorted by type, id and version as history
+/// *.osm.pbf files are.
+pub struct ObjectHistoryIterator<I: Iterator<Item=Element>> {
+    elements: I,
+    next: Option<(TypedId, Element)>,
+}
+
+impl<I: Iterator<Item=Element>> ObjectHistoryIterator<I> {
+    pub fn new(elements: I) -> ObjectHistoryIterator<I> {
+        ObjectHistoryIterator {
+            elements,
+            next: None,
+        }
+    }
+
+    fn next_element(&mut self) -> Option<(TypedId, Element)> {
+        for element in self.elements.by_ref() {
+            if let Some(typed_id) = TypedId::of(&element) {
+                return Some((typed_id, element));
+            }
+        }
+        None
+    }
+}
+
+impl<I: Iterator<Item=Element>> Iterator for ObjectHistoryIterator<I> {
+    type Item = ObjectHistory;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (typed_id, first) = match self.next.take() {
+            None => self.next_element()?,
+            Some(next) => next,
+        };
+        let mut versions = vec![first];
+        while let Some((next_id, element)) = self.next_element() {
+            if next_id == typed_id {
+                versions.push(element);
+            } else {
+                self.next = Some((next_id, element));
+                break;
+            }
+        }
+        Some(
+            ObjectHistory {
+                typed_id,
+                versions,
+            }
+        )
+    }
+}
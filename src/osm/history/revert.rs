@@ -0,0 +1,206 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use crate::osm::apidb_dump;
+use crate::osm::filter::get_id::TypedId;
+use crate::osm::history::object_history::{ObjectHistory, ObjectHistoryIterator};
+use crate::osm::model::element::Element;
+use crate::osm::model::node::Node;
+use crate::osm::model::relation::Relation;
+use crate::osm::model::way::Way;
+use crate::osm::pbf;
+use crate::osm::xml::change_writer::{ChangeAction, ChangeWriter};
+use crate::osm::xml::compression_type::CompressionType;
+
+/// An object touched by the reverted changeset that was edited again afterwards. Such objects are
+/// not reverted
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevertConflict {
+    pub typed_id: TypedId,
+    /// The first version after the changeset
+    pub version: i32,
+    pub changeset: i64,
+    pub user: String,
+}
+
+impl Display for RevertConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} v{} edited later in changeset {} by {}", self.typed_id, self.version, self.changeset, self.user)
+    }
+}
+
+/// The changes that restore every object touched by a changeset to its version before the
+/// changeset
+///
+/// Objects created by the changeset are deleted, objects deleted by the changeset are undeleted
+/// and modified objects get their previous tags, location, nodes and members back. The restored
+/// versions keep the metadata of the previous version but carry the version number of the latest
+/// version, as expected by the API for modifications and deletions. Objects that were edited after
+/// the changeset are reported as [RevertConflict]s and left as they are.
+///
+/// The input must be history data sorted by type, id and version, see [ObjectHistoryIterator].
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::history::revert::Revert;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::xml::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/history-niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/revert-13204421.osc");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let revert = Revert::from_pbf(&reader, 13204421)?;
+///     for conflict in revert.conflicts() {
+///         println!("{}", conflict);
+///     }
+///     revert.write(output_path, CompressionType::Uncompressed)?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Revert {
+    changeset: i64,
+    modify: Vec<Element>,
+    delete: Vec<Element>,
+    conflicts: Vec<RevertConflict>,
+}
+
+impl Revert {
+    pub fn from_pbf(reader: &pbf::reader::Reader, changeset: i64) -> Result<Revert, anyhow::Error> {
+        Ok(Self::compute(changeset, reader.elements()?))
+    }
+
+    pub fn from_apidb(reader: &apidb_dump::read::reader::Reader, changeset: i64) -> Result<Revert, anyhow::Error> {
+        Ok(Self::compute(changeset, reader.elements()?))
+    }
+
+    pub fn compute(changeset: i64, elements: impl Iterator<Item=Element>) -> Revert {
+        let mut revert = Revert {
+            changeset,
+            modify: Vec::new(),
+            delete: Vec::new(),
+            conflicts: Vec::new(),
+        };
+        for history in ObjectHistoryIterator::new(elements) {
+            revert.add(history);
+        }
+        // delete relations before ways before nodes
+        revert.delete.reverse();
+        revert
+    }
+
+    fn add(&mut self, history: ObjectHistory) {
+        let versions = history.versions();
+        let first = match versions.iter().position(|element| changeset_of(element) == self.changeset) {
+            None => {
+                return;
+            }
+            Some(first) => {
+                first
+            }
+        };
+        if let Some(later) = versions[first..].iter().find(|element| changeset_of(element) != self.changeset) {
+            let (version, changeset, user) = match later {
+                Element::Node { node } => (node.version(), node.changeset(), node.user().clone()),
+                Element::Way { way } => (way.version(), way.changeset(), way.user().clone()),
+                Element::Relation { relation } => (relation.version(), relation.changeset(), relation.user().clone()),
+                Element::Sentinel => (0, 0, String::new()),
+            };
+            self.conflicts.push(
+                RevertConflict {
+                    typed_id: history.typed_id(),
+                    version,
+                    changeset,
+                    user,
+                }
+            );
+            return;
+        }
+
+        let latest = history.latest();
+        let (latest_version, latest_visible) = version_and_visible(latest);
+        match first.checked_sub(1).map(|previous| &versions[previous]) {
+            Some(previous) if version_and_visible(previous).1 => {
+                self.modify.push(with_version(previous, latest_version));
+            }
+            _ => {
+                // created or restored by the changeset
+                if latest_visible {
+                    self.delete.push(latest.clone());
+                }
+            }
+        }
+    }
+
+    pub fn changeset(&self) -> i64 {
+        self.changeset
+    }
+
+    /// Previous versions to restore, including undeleted objects, nodes first
+    pub fn modify(&self) -> &Vec<Element> {
+        &self.modify
+    }
+
+    /// Objects to delete, relations first
+    pub fn delete(&self) -> &Vec<Element> {
+        &self.delete
+    }
+
+    pub fn conflicts(&self) -> &Vec<RevertConflict> {
+        &self.conflicts
+    }
+
+    /// Write the changes as osmChange
+    pub fn write(&self, output_path: PathBuf, compression_type: CompressionType) -> Result<(), anyhow::Error> {
+        let mut writer = ChangeWriter::new(output_path, compression_type)?;
+        writer.write_header()?;
+        for element in &self.modify {
+            writer.write_change(ChangeAction::Modify, element.clone())?;
+        }
+        for element in &self.delete {
+            writer.write_change(ChangeAction::Delete, element.clone())?;
+        }
+        writer.close()
+    }
+}
+
+fn changeset_of(element: &Element) -> i64 {
+    match element {
+        Element::Node { node } => node.changeset(),
+        Element::Way { way } => way.changeset(),
+        Element::Relation { relation } => relation.changeset(),
+        Element::Sentinel => 0,
+    }
+}
+
+fn version_and_visible(element: &Element) -> (i32, bool) {
+    match element {
+        Element::Node { node } => (node.version(), node.visible()),
+        Element::Way { way } => (way.version(), way.visible()),
+        Element::Relation { relation } => (relation.version(), relation.visible()),
+        Element::Sentinel => (0, false),
+    }
+}
+
+fn with_version(element: &Element, version: i32) -> Element {
+    match element {
+        Element::Node { node } => {
+            Element::Node {
+                node: Node::new(node.id(), version, node.coordinate().clone(), node.timestamp(), node.changeset(), node.uid(), node.user().clone(), true, node.tags().clone()),
+            }
+        }
+        Element::Way { way } => {
+            Element::Way {
+                way: Way::new(way.id(), version, way.timestamp(), way.changeset(), way.uid(), way.user().clone(), true, way.refs().clone(), way.tags().clone()),
+            }
+        }
+        Element::Relation { relation } => {
+            Element::Relation {
+                relation: Relation::new(relation.id(), version, relation.timestamp(), relation.changeset(), relation.uid(), relation.user().clone(), true, relation.members().clone(), relation.tags().clone()),
+            }
+        }
+        Element::Sentinel => {
+            Element::Sentinel
+        }
+    }
+}
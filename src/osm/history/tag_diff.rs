@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use crate::osm::model::tag::Tag;
+
+/// A tag whose value changed between two versions
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagChange {
+    pub k: String,
+    pub old_v: String,
+    pub new_v: String,
+}
+
+/// The tags added, removed and modified between two versions, ordered by key
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagDiff {
+    added: Vec<Tag>,
+    removed: Vec<Tag>,
+    modified: Vec<TagChange>,
+}
+
+impl TagDiff {
+    pub fn between(old_tags: &[Tag], new_tags: &[Tag]) -> TagDiff {
+        let old: BTreeMap<&String, &String> = old_tags.iter().map(|tag| (tag.k(), tag.v())).collect();
+        let new: BTreeMap<&String, &String> = new_tags.iter().map(|tag| (tag.k(), tag.v())).collect();
+        let mut diff = TagDiff::default();
+        for (k, new_v) in &new {
+            match old.get(k) {
+                None => {
+                    diff.added.push(Tag::new((*k).clone(), (*new_v).clone()));
+                }
+                Some(old_v) if old_v != new_v => {
+                    diff.modified.push(
+                        TagChange {
+                            k: (*k).clone(),
+                            old_v: (*old_v).clone(),
+                            new_v: (*new_v).clone(),
+                        }
+                    );
+                }
+                Some(_) => {}
+            }
+        }
+        for (k, old_v) in &old {
+            if !new.contains_key(k) {
+                diff.removed.push(Tag::new((*k).clone(), (*old_v).clone()));
+            }
+        }
+        diff
+    }
+
+    pub fn added(&self) -> &Vec<Tag> {
+        &self.added
+    }
+
+    pub fn removed(&self) -> &Vec<Tag> {
+        &self.removed
+    }
+
+    pub fn modified(&self) -> &Vec<TagChange> {
+        &self.modified
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::osm::history::tag_diff::{TagChange, TagDiff};
+    use crate::osm::model::tag::Tag;
+
+    fn tags(pairs: &[(&str, &str)]) -> Vec<Tag> {
+        pairs.iter().map(|(k, v)| Tag::new(k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_tag_diff() {
+        let diff = TagDiff::between(
+            &tags(&[("name", "A"), ("amenity", "cafe"), ("old", "x")]),
+            &tags(&[("amenity", "cafe"), ("name", "B"), ("new", "y")]),
+        );
+        assert_eq!(diff.added(), &tags(&[("new", "y")]));
+        assert_eq!(diff.removed(), &tags(&[("old", "x")]));
+        assert_eq!(diff.modified(), &vec![TagChange { k: "name".to_string(), old_v: "A".to_string(), new_v: "B".to_string() }]);
+        assert!(TagDiff::between(&tags(&[("a", "1")]), &tags(&[("a", "1")])).is_empty());
+    }
+}
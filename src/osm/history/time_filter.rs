@@ -0,0 +1,211 @@
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+
+use crate::osm::converters::timestamp_to_iso8601_seconds;
+use crate::osm::filter::get_id::TypedId;
+use crate::osm::model::element::Element;
+use crate::osm::pbf;
+use crate::osm::pbf::compression_type::CompressionType;
+use crate::osm::pbf::file_info::FileInfo;
+
+/// Select the object versions of a history file that were current at a point in time or during a
+/// time range
+///
+/// A version is current from its timestamp until the timestamp of the next version of the same
+/// object, the last version is current until now.
+/// * snapshot - the version of each object current at the given time. Objects that did not exist
+///   yet, or were deleted at that time, are dropped. The result is a normal, non-history file
+/// * range - all the versions current at any time in [from, to), including deleted versions. The
+///   result is a reduced history file
+///
+/// The input must be sorted by type, id and version as history *.osm.pbf files are. Timestamps
+/// are in milliseconds, see [crate::osm::converters::iso8601_to_timestamp_millis].
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::converters::iso8601_to_timestamp_millis;
+/// use osm_io::osm::history::time_filter::TimeFilter;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::pbf::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/history-niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-2020-01-01.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let time_filter = TimeFilter::snapshot(iso8601_to_timestamp_millis("2020-01-01T00:00:00Z")?);
+///     time_filter.run(&reader, output_path, CompressionType::Zlib)?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeFilter {
+    from: i64,
+    to: i64,
+    snapshot: bool,
+}
+
+impl TimeFilter {
+    /// Select the versions current at timestamp
+    pub fn snapshot(timestamp: i64) -> TimeFilter {
+        TimeFilter {
+            from: timestamp,
+            to: timestamp,
+            snapshot: true,
+        }
+    }
+
+    /// Select the versions current at any time in [from, to)
+    pub fn range(from: i64, to: i64) -> Result<TimeFilter, anyhow::Error> {
+        if from >= to {
+            return Err(
+                anyhow!(
+                    "Empty time range {} - {}",
+                    timestamp_to_iso8601_seconds(from.saturating_mul(1000)).unwrap_or(from.to_string()),
+                    timestamp_to_iso8601_seconds(to.saturating_mul(1000)).unwrap_or(to.to_string()),
+                )
+            );
+        }
+        Ok(
+            TimeFilter {
+                from,
+                to,
+                snapshot: false,
+            }
+        )
+    }
+
+    pub fn from(&self) -> i64 {
+        self.from
+    }
+
+    pub fn to(&self) -> i64 {
+        self.to
+    }
+
+    pub fn is_snapshot(&self) -> bool {
+        self.snapshot
+    }
+
+    /// Select the version that is current from timestamp until next_timestamp, None for the last
+    /// version
+    fn selects(&self, timestamp: i64, next_timestamp: Option<i64>, visible: bool) -> bool {
+        let current_until = next_timestamp.unwrap_or(i64::MAX);
+        if self.snapshot {
+            visible && timestamp <= self.from && current_until > self.from
+        } else {
+            timestamp < self.to && current_until > self.from
+        }
+    }
+
+    /// Filter the elements, [Element::Sentinel]s are dropped
+    pub fn filter<I: Iterator<Item=Element>>(&self, elements: I) -> TimeFilterIterator<I> {
+        TimeFilterIterator {
+            time_filter: *self,
+            elements,
+            previous: None,
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// The file info of the result, without the HistoricalInformation feature for snapshots
+    pub fn file_info(&self, file_info: &FileInfo) -> FileInfo {
+        let mut file_info = file_info.clone();
+        if self.snapshot {
+            let optional_features: Vec<String> = file_info.optional_features().iter()
+                .filter(|feature| *feature != "HistoricalInformation")
+                .cloned()
+                .collect();
+            file_info.with_optional_features(&optional_features);
+            let required_features: Vec<String> = file_info.required_features().iter()
+                .filter(|feature| *feature != "HistoricalInformation")
+                .cloned()
+                .collect();
+            file_info.with_required_features(&required_features);
+        }
+        file_info
+    }
+
+    /// Write the selected versions, returns the number of written elements
+    pub fn run(&self, reader: &pbf::reader::Reader, output_path: PathBuf, compression_type: CompressionType) -> Result<u64, anyhow::Error> {
+        let mut writer = pbf::writer::Writer::from_file_info(output_path, self.file_info(reader.info()), compression_type)?;
+        writer.write_header()?;
+        let mut count = 0;
+        for element in self.filter(reader.elements()?) {
+            writer.write_element(element)?;
+            count += 1;
+        }
+        writer.close()?;
+        Ok(count)
+    }
+}
+
+fn timestamp_and_visible(element: &Element) -> (i64, bool) {
+    match element {
+        Element::Node { node } => {
+            (node.timestamp(), node.visible())
+        }
+        Element::Way { way } => {
+            (way.timestamp(), way.visible())
+        }
+        Element::Relation { relation } => {
+            (relation.timestamp(), relation.visible())
+        }
+        Element::Sentinel => {
+            (0, false)
+        }
+    }
+}
+
+/// Iterator over the versions selected by [TimeFilter::filter]
+pub struct TimeFilterIterator<I: Iterator<Item=Element>> {
+    time_filter: TimeFilter,
+    elements: I,
+    // the previous version, selected once the timestamp of the next version is known
+    previous: Option<(TypedId, Element)>,
+    ready: VecDeque<Element>,
+}
+
+impl<I: Iterator<Item=Element>> TimeFilterIterator<I> {
+    fn decide(&mut self, element: Element, next_timestamp: Option<i64>) {
+        let (timestamp, visible) = timestamp_and_visible(&element);
+        if self.time_filter.selects(timestamp, next_timestamp, visible) {
+            self.ready.push_back(element);
+        }
+    }
+}
+
+impl<I: Iterator<Item=Element>> Iterator for TimeFilterIterator<I> {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.ready.is_empty() {
+            match self.elements.next() {
+                None => {
+                    let (_, previous) = self.previous.take()?;
+                    self.decide(previous, None);
+                }
+                Some(element) => {
+                    let typed_id = match TypedId::of(&element) {
+                        None => {
+                            continue;
+                        }
+                        Some(typed_id) => {
+                            typed_id
+                        }
+                    };
+                    if let Some((previous_id, previous)) = self.previous.take() {
+                        let next_timestamp = if previous_id == typed_id {
+                            Some(timestamp_and_visible(&element).0)
+                        } else {
+                            None
+                        };
+                        self.decide(previous, next_timestamp);
+                    }
+                    self.previous = Some((typed_id, element));
+                }
+            }
+        }
+        self.ready.pop_front()
+    }
+}
@@ -0,0 +1,97 @@
+use crate::osm::history::tag_diff::TagDiff;
+use crate::osm::model::coordinate::Coordinate;
+
+/// Mean earth radius in meters
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// What a version did to the object
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    /// The first version
+    Created,
+    Modified,
+    Deleted,
+    /// A visible version after a deleted one
+    Restored,
+}
+
+/// A change of the location of a node
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeMove {
+    pub from: Coordinate,
+    pub to: Coordinate,
+}
+
+impl NodeMove {
+    /// Great circle distance in meters
+    pub fn distance(&self) -> f64 {
+        let lat1 = self.from.lat().to_radians();
+        let lat2 = self.to.lat().to_radians();
+        let d_lat = lat2 - lat1;
+        let d_lon = (self.to.lon() - self.from.lon()).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().asin()
+    }
+}
+
+/// The change made by one version of an object relative to the previous version, and who made it
+#[derive(Debug, Clone)]
+pub struct VersionChange {
+    pub(crate) version: i32,
+    pub(crate) timestamp: i64,
+    pub(crate) changeset: i64,
+    pub(crate) uid: i32,
+    pub(crate) user: String,
+    pub(crate) kind: ChangeKind,
+    pub(crate) tag_diff: TagDiff,
+    pub(crate) node_move: Option<NodeMove>,
+    pub(crate) refs_changed: bool,
+    pub(crate) members_changed: bool,
+}
+
+impl VersionChange {
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    pub fn changeset(&self) -> i64 {
+        self.changeset
+    }
+
+    pub fn uid(&self) -> i32 {
+        self.uid
+    }
+
+    pub fn user(&self) -> &String {
+        &self.user
+    }
+
+    pub fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    /// Tags changed relative to the previous version, all tags are added by the first version
+    /// and removed by a deletion
+    pub fn tag_diff(&self) -> &TagDiff {
+        &self.tag_diff
+    }
+
+    /// The node moved, only for modifications of nodes
+    pub fn node_move(&self) -> &Option<NodeMove> {
+        &self.node_move
+    }
+
+    /// The node list of the way changed, only for modifications of ways
+    pub fn refs_changed(&self) -> bool {
+        self.refs_changed
+    }
+
+    /// The member list of the relation changed, only for modifications of relations
+    pub fn members_changed(&self) -> bool {
+        self.members_changed
+    }
+}
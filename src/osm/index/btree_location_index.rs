@@ -0,0 +1,47 @@
+use std::sync::Mutex;
+
+use anyhow::anyhow;
+use transient_btree_index::{BtreeConfig, BtreeIndex};
+
+use crate::osm::index::node_location_index::{from_location7, NodeLocationIndex, to_location7};
+use crate::osm::model::coordinate::Coordinate;
+
+/// Disk backed [NodeLocationIndex] on top of a transient B-tree
+///
+/// The B-tree lives in temporary files that are removed when the index is dropped. Memory usage
+/// stays low regardless of the number of nodes, at the cost of slower lookups.
+pub struct BtreeLocationIndex {
+    locations: Mutex<BtreeIndex<i64, (i32, i32)>>,
+}
+
+impl BtreeLocationIndex {
+    pub fn new() -> Result<BtreeLocationIndex, anyhow::Error> {
+        let config = BtreeConfig::default()
+            .max_key_size(8)
+            .max_value_size(8);
+        Ok(
+            BtreeLocationIndex {
+                locations: Mutex::new(BtreeIndex::<i64, (i32, i32)>::with_capacity(config, 0)?),
+            }
+        )
+    }
+}
+
+impl NodeLocationIndex for BtreeLocationIndex {
+    fn set(&self, id: i64, coordinate: &Coordinate) -> Result<(), anyhow::Error> {
+        self.locations.lock().unwrap().insert(id, to_location7(coordinate))
+            .map_err(|e| anyhow!("Failed to store location of node {}, error: {}", id, e))?;
+        Ok(())
+    }
+
+    /// Panics if the underlying files can not be read
+    fn get(&self, id: i64) -> Option<Coordinate> {
+        self.locations.lock().unwrap().get(&id)
+            .unwrap_or_else(|e| panic!("Failed to read location of node {}, error: {}", id, e))
+            .map(from_location7)
+    }
+
+    fn len(&self) -> usize {
+        self.locations.lock().unwrap().len()
+    }
+}
@@ -0,0 +1,179 @@
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use anyhow::{anyhow, Context};
+use memmap2::MmapMut;
+
+use crate::osm::index::node_location_index::{from_location7, NodeLocationIndex, to_location7};
+use crate::osm::model::coordinate::Coordinate;
+
+const SLOT_SIZE: usize = 8;
+const GROWTH_SLOTS: usize = 1 << 20;
+// Encoded value of a missing location. Stored values are XOR-ed with it, so the zero filled
+// space of a freshly grown file reads as missing
+const EMPTY: u64 = 0x7fffffff_7fffffff;
+
+struct Slots {
+    file: File,
+    mmap: MmapMut,
+    len: usize,
+}
+
+impl Slots {
+    fn capacity(&self) -> usize {
+        self.mmap.len() / SLOT_SIZE
+    }
+
+    fn grow(&mut self, min_capacity: usize) -> Result<(), anyhow::Error> {
+        let capacity = min_capacity.max(self.capacity() * 2).div_ceil(GROWTH_SLOTS) * GROWTH_SLOTS;
+        self.mmap.flush()?;
+        self.file.set_len((capacity * SLOT_SIZE) as u64)?;
+        // SAFETY: the file is owned by the index and is not modified by anyone else while mapped
+        self.mmap = unsafe { MmapMut::map_mut(&self.file)? };
+        Ok(())
+    }
+
+    fn read(&self, slot: usize) -> u64 {
+        let offset = slot * SLOT_SIZE;
+        u64::from_le_bytes(self.mmap[offset..offset + SLOT_SIZE].try_into().unwrap()) ^ EMPTY
+    }
+
+    fn write(&mut self, slot: usize, value: u64) {
+        let offset = slot * SLOT_SIZE;
+        self.mmap[offset..offset + SLOT_SIZE].copy_from_slice(&(value ^ EMPTY).to_le_bytes());
+    }
+}
+
+/// Memory mapped [NodeLocationIndex] stored as a flat array indexed by node id
+///
+/// Every node id up to the largest stored id occupies 8 bytes of the backing file, so the file
+/// is as large as 8 times the largest node id. The file is sparse on file systems that support
+/// it. This is the most compact and fastest option for planet scale data, where almost every
+/// node id is in use. Negative node ids are not supported.
+pub struct DenseMmapLocationIndex {
+    path: PathBuf,
+    remove_on_drop: bool,
+    slots: RwLock<Slots>,
+}
+
+impl DenseMmapLocationIndex {
+    /// Create a new index backed by the file at path. An existing file is truncated
+    pub fn new(path: &Path) -> Result<DenseMmapLocationIndex, anyhow::Error> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        file.set_len((GROWTH_SLOTS * SLOT_SIZE) as u64)?;
+        // SAFETY: the file was just created and is not modified by anyone else while mapped
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+        Ok(
+            DenseMmapLocationIndex {
+                path: path.to_path_buf(),
+                remove_on_drop: false,
+                slots: RwLock::new(Slots { file, mmap, len: 0 }),
+            }
+        )
+    }
+
+    /// Create a new index backed by a file in the temporary directory, removed when the index is
+    /// dropped
+    pub fn temporary() -> Result<DenseMmapLocationIndex, anyhow::Error> {
+        let path = std::env::temp_dir().join(format!("osm-io-locations-{}.idx", uuid::Uuid::new_v4()));
+        let mut index = Self::new(&path)?;
+        index.remove_on_drop = true;
+        Ok(index)
+    }
+
+    /// Backing file path
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+impl NodeLocationIndex for DenseMmapLocationIndex {
+    fn set(&self, id: i64, coordinate: &Coordinate) -> Result<(), anyhow::Error> {
+        if id < 0 {
+            return Err(anyhow!("Negative node id {} is not supported by the dense index", id));
+        }
+        let slot = id as usize;
+        let (lat7, lon7) = to_location7(coordinate);
+        let value = ((lat7 as u32 as u64) << 32) | lon7 as u32 as u64;
+        let mut slots = self.slots.write().unwrap();
+        if slot >= slots.capacity() {
+            slots.grow(slot + 1)
+                .with_context(|| anyhow!("Failed to grow the index, path: {}", self.path.display()))?;
+        }
+        if slots.read(slot) == EMPTY {
+            slots.len += 1;
+        }
+        slots.write(slot, value);
+        Ok(())
+    }
+
+    fn get(&self, id: i64) -> Option<Coordinate> {
+        if id < 0 {
+            return None;
+        }
+        let slot = id as usize;
+        let slots = self.slots.read().unwrap();
+        if slot >= slots.capacity() {
+            return None;
+        }
+        match slots.read(slot) {
+            EMPTY => {
+                None
+            }
+            value => {
+                Some(from_location7(((value >> 32) as u32 as i32, value as u32 as i32)))
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.slots.read().unwrap().len
+    }
+}
+
+impl Drop for DenseMmapLocationIndex {
+    fn drop(&mut self) {
+        if self.remove_on_drop {
+            std::fs::remove_file(&self.path).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dense_mmap_location_index() {
+        let index = DenseMmapLocationIndex::temporary().unwrap();
+        let path = index.path().clone();
+        assert!(index.is_empty());
+        assert_eq!(index.get(5), None);
+
+        index.set(0, &Coordinate::new(0.0, 0.0)).unwrap();
+        index.set(5, &Coordinate::new(-19.055434, -169.9218762)).unwrap();
+        index.set(5 * GROWTH_SLOTS as i64, &Coordinate::new(90.0, 180.0)).unwrap();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.get(0), Some(Coordinate::new(0.0, 0.0)));
+        let coordinate = index.get(5).unwrap();
+        assert_eq!(coordinate.lat7(), -190554340);
+        assert_eq!(coordinate.lon7(), -1699218762);
+        assert_eq!(index.get(5 * GROWTH_SLOTS as i64).unwrap().lon7(), 1800000000);
+        assert_eq!(index.get(6), None);
+        assert_eq!(index.get(-1), None);
+        assert!(index.set(-1, &Coordinate::new(0.0, 0.0)).is_err());
+
+        index.set(5, &Coordinate::new(1.0, 2.0)).unwrap();
+        assert_eq!(index.len(), 3);
+
+        drop(index);
+        assert!(!path.exists());
+    }
+}
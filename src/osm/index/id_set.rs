@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+const PAGE_BITS: u32 = 16;
+const PAGE_IDS: i64 = 1 << PAGE_BITS;
+const PAGE_WORDS: usize = (PAGE_IDS / 64) as usize;
+
+/// Set of OSM object ids stored as a paged bitmap
+///
+/// Each page holds one bit for each of 65536 consecutive ids and is allocated when the first id
+/// in its range is inserted. This takes much less memory than a hash set when ids are dense, as
+/// they are in most extracts. Negative ids are supported.
+#[derive(Debug, Clone, Default)]
+pub struct IdSet {
+    pages: HashMap<i64, Box<[u64; PAGE_WORDS]>>,
+    len: usize,
+}
+
+impl IdSet {
+    pub fn new() -> IdSet {
+        IdSet::default()
+    }
+
+    fn locate(id: i64) -> (i64, usize, u64) {
+        let page = id >> PAGE_BITS;
+        let offset = (id & (PAGE_IDS - 1)) as usize;
+        (page, offset / 64, 1 << (offset % 64))
+    }
+
+    /// Add the id. Returns true if the id was not in the set
+    pub fn insert(&mut self, id: i64) -> bool {
+        let (page, word, mask) = Self::locate(id);
+        let words = self.pages.entry(page).or_insert_with(|| Box::new([0; PAGE_WORDS]));
+        if words[word] & mask == 0 {
+            words[word] |= mask;
+            self.len += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn contains(&self, id: i64) -> bool {
+        let (page, word, mask) = Self::locate(id);
+        self.pages.get(&page)
+            .map(|words| words[word] & mask != 0)
+            .unwrap_or(false)
+    }
+
+    /// Number of ids in the set
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// All ids in ascending order
+    pub fn ids(&self) -> Vec<i64> {
+        let mut pages: Vec<&i64> = self.pages.keys().collect();
+        pages.sort();
+        let mut result = Vec::with_capacity(self.len);
+        for page in pages {
+            let words = &self.pages[page];
+            for (word, bits) in words.iter().enumerate() {
+                let mut bits = *bits;
+                while bits != 0 {
+                    let bit = bits.trailing_zeros() as i64;
+                    result.push((page << PAGE_BITS) + word as i64 * 64 + bit);
+                    bits &= bits - 1;
+                }
+            }
+        }
+        result
+    }
+}
+
+impl FromIterator<i64> for IdSet {
+    fn from_iter<T: IntoIterator<Item=i64>>(iter: T) -> Self {
+        let mut set = IdSet::new();
+        for id in iter {
+            set.insert(id);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_set() {
+        let mut set = IdSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(1));
+        assert!(!set.insert(1));
+        assert!(set.insert(-1));
+        assert!(set.insert(65536));
+        assert!(set.insert(12_000_000_000));
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(1));
+        assert!(set.contains(-1));
+        assert!(!set.contains(2));
+        assert!(!set.contains(-65537));
+        assert_eq!(set.ids(), vec![-1, 1, 65536, 12_000_000_000]);
+    }
+}
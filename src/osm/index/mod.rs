@@ -0,0 +1,5 @@
+pub mod node_location_index;
+pub mod sparse_mem_location_index;
+pub mod dense_mmap_location_index;
+pub mod btree_location_index;
+pub mod id_set;
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+
+use crate::osm::model::coordinate::Coordinate;
+use crate::osm::model::element::Element;
+use crate::osm::model::way::Way;
+use crate::osm::pbf;
+
+/// Node locations by node id
+///
+/// Implementations are safe to share between threads, so a single index can be filled with
+/// [pbf::reader::Reader::parallel_for_each], see [parallel_fill].
+/// Locations are stored with the 1e-7 degree precision used by *.osm.pbf files.
+pub trait NodeLocationIndex: Send + Sync {
+    /// Store the location of a node, replacing a previously stored location
+    fn set(&self, id: i64, coordinate: &Coordinate) -> Result<(), anyhow::Error>;
+
+    /// Location of a node, None if the node is not in the index
+    fn get(&self, id: i64) -> Option<Coordinate>;
+
+    /// Number of stored locations
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Store the locations of all visible nodes in the elements
+///
+/// Ways and relations are skipped, so any element iterator can be used as is.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::index::node_location_index::{fill, NodeLocationIndex};
+/// use osm_io::osm::index::sparse_mem_location_index::SparseMemLocationIndex;
+/// use osm_io::osm::pbf;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let index = SparseMemLocationIndex::new();
+///     fill(&index, reader.elements()?)?;
+///     println!("{:?}", index.get(1));
+///     Ok(())
+/// }
+/// ```
+pub fn fill(index: &dyn NodeLocationIndex, elements: impl IntoIterator<Item=Element>) -> Result<(), anyhow::Error> {
+    for element in elements {
+        if let Element::Node { node } = element {
+            if node.visible() {
+                index.set(node.id(), node.coordinate())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Store the locations of all visible nodes in a *.osm.pbf file using multiple threads
+///
+/// * index - the index to fill
+/// * reader - *.osm.pbf reader
+/// * tasks - number of threads
+pub fn parallel_fill(index: Arc<dyn NodeLocationIndex>, reader: &pbf::reader::Reader, tasks: usize) -> Result<(), anyhow::Error> {
+    reader.parallel_for_each(tasks, move |element| {
+        if let Element::Node { node } = element {
+            if node.visible() {
+                index.set(node.id(), node.coordinate())?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Coordinates of the way nodes in the order of way refs
+///
+/// Fails naming the first node missing in the index.
+pub fn way_coordinates(index: &dyn NodeLocationIndex, way: &Way) -> Result<Vec<Coordinate>, anyhow::Error> {
+    way.refs().iter()
+        .map(|r| {
+            index.get(*r)
+                .ok_or(anyhow!("Missing location of node {} in way {}", r, way.id()))
+        })
+        .collect()
+}
+
+pub(crate) fn to_location7(coordinate: &Coordinate) -> (i32, i32) {
+    (coordinate.lat7() as i32, coordinate.lon7() as i32)
+}
+
+pub(crate) fn from_location7(location7: (i32, i32)) -> Coordinate {
+    Coordinate::new(location7.0 as f64 / 1E7, location7.1 as f64 / 1E7)
+}
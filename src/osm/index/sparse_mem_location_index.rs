@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::osm::index::node_location_index::{from_location7, NodeLocationIndex, to_location7};
+use crate::osm::model::coordinate::Coordinate;
+
+/// In memory [NodeLocationIndex] backed by a hash map
+///
+/// Memory usage is proportional to the number of stored nodes, which makes it the best choice
+/// for small and medium sized extracts.
+#[derive(Default)]
+pub struct SparseMemLocationIndex {
+    locations: RwLock<HashMap<i64, (i32, i32)>>,
+}
+
+impl SparseMemLocationIndex {
+    pub fn new() -> SparseMemLocationIndex {
+        SparseMemLocationIndex::default()
+    }
+}
+
+impl NodeLocationIndex for SparseMemLocationIndex {
+    fn set(&self, id: i64, coordinate: &Coordinate) -> Result<(), anyhow::Error> {
+        self.locations.write().unwrap().insert(id, to_location7(coordinate));
+        Ok(())
+    }
+
+    fn get(&self, id: i64) -> Option<Coordinate> {
+        self.locations.read().unwrap().get(&id).map(|location7| from_location7(*location7))
+    }
+
+    fn len(&self) -> usize {
+        self.locations.read().unwrap().len()
+    }
+}
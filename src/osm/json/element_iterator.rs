@@ -0,0 +1,183 @@
+use anyhow::anyhow;
+use json::JsonValue;
+
+use crate::osm::converters::iso8601_to_timestamp_millis;
+use crate::osm::model::coordinate::Coordinate;
+use crate::osm::model::element::Element;
+use crate::osm::model::node::Node;
+use crate::osm::model::relation::{Member, MemberData, Relation};
+use crate::osm::model::tag::Tag;
+use crate::osm::model::way::Way;
+
+fn required_i64(value: &JsonValue, name: &str) -> Result<i64, anyhow::Error> {
+    value[name].as_i64()
+        .ok_or(anyhow!("Missing or invalid field {}: {}", name, value[name]))
+}
+
+fn optional_i64(value: &JsonValue, name: &str, default: i64) -> Result<i64, anyhow::Error> {
+    if value[name].is_null() {
+        return Ok(default);
+    }
+    required_i64(value, name)
+}
+
+fn timestamp(value: &JsonValue) -> Result<i64, anyhow::Error> {
+    match value["timestamp"].as_str() {
+        None => {
+            Ok(-1)
+        }
+        Some(timestamp) => {
+            iso8601_to_timestamp_millis(timestamp)
+        }
+    }
+}
+
+fn tags(value: &JsonValue) -> Result<Vec<Tag>, anyhow::Error> {
+    let mut tags = Vec::new();
+    for (k, v) in value["tags"].entries() {
+        let v = v.as_str()
+            .ok_or(anyhow!("Invalid value of tag {}: {}", k, v))?;
+        tags.push(Tag::new(k.to_string(), v.to_string()));
+    }
+    Ok(tags)
+}
+
+fn refs(value: &JsonValue) -> Result<Vec<i64>, anyhow::Error> {
+    value["nodes"].members()
+        .map(|r| r.as_i64().ok_or(anyhow!("Invalid node reference: {}", r)))
+        .collect()
+}
+
+fn members(value: &JsonValue) -> Result<Vec<Member>, anyhow::Error> {
+    let mut members = Vec::new();
+    for m in value["members"].members() {
+        let member = MemberData::new(
+            required_i64(m, "ref")?,
+            m["role"].as_str().unwrap_or_default().to_string(),
+        );
+        match m["type"].as_str() {
+            Some("node") => {
+                members.push(Member::Node { member });
+            }
+            Some("way") => {
+                members.push(Member::Way { member });
+            }
+            Some("relation") => {
+                members.push(Member::Relation { member });
+            }
+            t => {
+                return Err(anyhow!("Invalid relation member type: {:?}", t));
+            }
+        }
+    }
+    Ok(members)
+}
+
+/// Convert a single object of the OSM JSON elements array into an [Element]
+///
+/// Missing metadata fields get the same defaults as in the OSM XML reader.
+pub fn parse_element(value: &JsonValue) -> Result<Element, anyhow::Error> {
+    let id = required_i64(value, "id")?;
+    let version = optional_i64(value, "version", 0)? as i32;
+    let timestamp = timestamp(value)?;
+    let changeset = optional_i64(value, "changeset", -1)?;
+    let uid = optional_i64(value, "uid", -1)? as i32;
+    let user = value["user"].as_str().unwrap_or_default().to_string();
+    let visible = value["visible"].as_bool().unwrap_or(true);
+    let tags = tags(value)?;
+    match value["type"].as_str() {
+        Some("node") => {
+            let coordinate = Coordinate::new(
+                value["lat"].as_f64().unwrap_or(0.0),
+                value["lon"].as_f64().unwrap_or(0.0),
+            );
+            Ok(
+                Element::Node {
+                    node: Node::new(id, version, coordinate, timestamp, changeset, uid, user, visible, tags)
+                }
+            )
+        }
+        Some("way") => {
+            Ok(
+                Element::Way {
+                    way: Way::new(id, version, timestamp, changeset, uid, user, visible, refs(value)?, tags)
+                }
+            )
+        }
+        Some("relation") => {
+            Ok(
+                Element::Relation {
+                    relation: Relation::new(id, version, timestamp, changeset, uid, user, visible, members(value)?, tags)
+                }
+            )
+        }
+        t => {
+            Err(anyhow!("Invalid element type: {:?}", t))
+        }
+    }
+}
+
+/// Iterate over elements in *.json file
+///
+/// Objects of other types, like the Overpass "area" or "count" results, are skipped.
+pub struct ElementIterator {
+    values: std::vec::IntoIter<JsonValue>,
+}
+
+impl ElementIterator {
+    pub(crate) fn new(values: Vec<JsonValue>) -> ElementIterator {
+        ElementIterator {
+            values: values.into_iter(),
+        }
+    }
+}
+
+impl Iterator for ElementIterator {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for value in self.values.by_ref() {
+            if !matches!(value["type"].as_str(), Some("node") | Some("way") | Some("relation")) {
+                continue;
+            }
+            return Some(
+                parse_element(&value)
+                    .unwrap_or_else(|e| panic!("Failed to parse OSM JSON element {}: {:?}", value.dump(), e))
+            );
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_element() {
+        let value = json::parse(r#"{"type":"way","id":200,"timestamp":"2009-03-25T00:54:00Z","version":1,"changeset":907211,"user":"alice","uid":18203,"nodes":[100,101,102,100],"tags":{"landuse":"grass"}}"#)
+            .unwrap();
+        match parse_element(&value).unwrap() {
+            Element::Way { way } => {
+                assert_eq!(way.id(), 200);
+                assert_eq!(way.version(), 1);
+                assert_eq!(way.timestamp(), 1237942440000);
+                assert_eq!(way.uid(), 18203);
+                assert_eq!(way.refs(), &vec![100, 101, 102, 100]);
+                assert_eq!(way.tags()[0].v(), "grass");
+                assert!(way.visible());
+            }
+            _ => panic!("expected a way"),
+        }
+        let value = json::parse(r#"{"type":"node","id":1,"lat":1.5,"lon":2.5}"#).unwrap();
+        match parse_element(&value).unwrap() {
+            Element::Node { node } => {
+                assert_eq!(node.version(), 0);
+                assert_eq!(node.changeset(), -1);
+                assert_eq!(node.coordinate().lon7(), 25000000);
+            }
+            _ => panic!("expected a node"),
+        }
+        assert!(parse_element(&json::parse(r#"{"type":"area","id":1}"#).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use json::JsonValue;
+
+use crate::osm::converters::iso8601_to_timestamp_millis;
+use crate::osm::json::element_iterator::ElementIterator;
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::pbf::file_info::FileInfo;
+use crate::osm::xml;
+use crate::osm::xml::compression_type::CompressionType;
+
+/// *.json file reader for OSM JSON as returned by the API 0.6 and by Overpass
+///
+/// The json crate has no streaming parser, so the whole document is parsed in memory. This is
+/// intended for API and Overpass responses rather than for large extracts.
+#[derive(Debug, Clone)]
+pub struct Reader {
+    path: PathBuf,
+    compression_type: CompressionType,
+    info: FileInfo,
+}
+
+impl Reader {
+    /// Create a new Reader
+    ///
+    /// * path - a path to a valid *.json, *.json.gz or *.json.bz2 file
+    ///
+    /// The compression is deduced from the file extension.
+    ///
+    /// Example:
+    /// ```
+    /// use std::path::PathBuf;
+    /// use osm_io::osm::json::reader::Reader;
+    /// let input_path = PathBuf::from("./overpass-result.json");
+    /// let reader = Reader::new(&input_path);
+    /// ```
+    pub fn new(path: &Path) -> Result<Reader, anyhow::Error> {
+        let mut reader = Reader {
+            path: path.to_path_buf(),
+            compression_type: CompressionType::from_path(path),
+            info: FileInfo::default(),
+        };
+        let document = reader.parse()?;
+        reader.info = Self::read_info(&document)
+            .with_context(|| anyhow!("Failed to parse file header, path: {}", path.display()))?;
+        Ok(reader)
+    }
+
+    fn parse(&self) -> Result<JsonValue, anyhow::Error> {
+        let mut input = xml::reader::Reader::open(&self.path, &self.compression_type)?;
+        let mut document = String::new();
+        input.read_to_string(&mut document)
+            .with_context(|| anyhow!("path: {}", self.path.display()))?;
+        json::parse(&document)
+            .with_context(|| anyhow!("Invalid JSON, path: {}", self.path.display()))
+    }
+
+    /// Read the generator, the bounds and the Overpass base timestamp
+    fn read_info(document: &JsonValue) -> Result<FileInfo, anyhow::Error> {
+        if !document.is_object() {
+            return Err(anyhow!("Not an OSM JSON document"));
+        }
+        let mut info = FileInfo::default();
+        if let Some(generator) = document["generator"].as_str() {
+            info.with_writingprogram_str(generator);
+        }
+        let bounds = &document["bounds"];
+        if bounds.is_object() {
+            info.with_bounding_box(
+                &Some(
+                    BoundingBox::new(
+                        bounds["minlon"].as_f64().unwrap_or(-180.0),
+                        bounds["minlat"].as_f64().unwrap_or(-90.0),
+                        bounds["maxlon"].as_f64().unwrap_or(180.0),
+                        bounds["maxlat"].as_f64().unwrap_or(90.0),
+                    )
+                )
+            );
+        }
+        if let Some(timestamp) = document["osm3s"]["timestamp_osm_base"].as_str() {
+            info.with_osmosis_replication_timestamp(&Some(iso8601_to_timestamp_millis(timestamp)? / 1000));
+        }
+        Ok(info)
+    }
+
+    /// Iterator used to iterate over elements.
+    /// Example:
+    /// ```
+    /// use std::path::PathBuf;
+    /// use osm_io::osm::json;
+    /// fn example() -> Result<(), anyhow::Error> {
+    ///     let input_path = PathBuf::from("./tests/fixtures/history-sample.json");
+    ///     let reader = json::reader::Reader::new(&input_path)?;
+    ///     for element in reader.elements()? {
+    ///         println!("{:?}", element);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn elements(&self) -> Result<ElementIterator, anyhow::Error> {
+        let mut document = self.parse()?;
+        match document["elements"].take() {
+            JsonValue::Array(values) => {
+                Ok(ElementIterator::new(values))
+            }
+            JsonValue::Null => {
+                Ok(ElementIterator::new(Vec::new()))
+            }
+            _ => {
+                Err(anyhow!("Invalid elements array, path: {}", self.path.display()))
+            }
+        }
+    }
+
+    /// Header data. The bounding box is taken from the bounds object, the writing program from
+    /// the generator field and the osmosis replication timestamp from the Overpass
+    /// osm3s.timestamp_osm_base field
+    pub fn info(&self) -> &FileInfo {
+        &self.info
+    }
+
+    pub fn compression_type(&self) -> &CompressionType {
+        &self.compression_type
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
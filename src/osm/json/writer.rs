@@ -0,0 +1,264 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context};
+use json::JsonValue;
+
+use crate::osm::converters::timestamp_to_iso8601_seconds;
+use crate::osm::model::element::Element;
+use crate::osm::model::relation::Member;
+use crate::osm::model::tag::Tag;
+use crate::osm::pbf::file_info::FileInfo;
+use crate::osm::xml::compression_type::CompressionType;
+use crate::osm::xml::writer::Output;
+
+/// *.json file writer
+///
+/// Write an OSM JSON document in the format of the API 0.6, one element per line, optionally
+/// compressed with gzip or bzip2.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::json;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::xml::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109.json");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let mut writer = json::writer::Writer::from_file_info(
+///         output_path,
+///         reader.info().clone(),
+///         CompressionType::Uncompressed,
+///     )?;
+///
+///     writer.write_header()?;
+///     for element in reader.elements()? {
+///         writer.write_element(element)?;
+///     }
+///     writer.close()?;
+///     Ok(())
+/// }
+/// ```
+pub struct Writer {
+    path: PathBuf,
+    file_info: FileInfo,
+    metadata: bool,
+    first: bool,
+    output: Option<Output>,
+}
+
+impl Writer {
+    /// Create a new [Writer] from [FileInfo]
+    ///
+    /// The bounding box and the writing program are taken from the file_info. User and changeset
+    /// metadata are written by default, see [Writer::with_metadata]
+    pub fn from_file_info(
+        path: PathBuf,
+        file_info: FileInfo,
+        compression_type: CompressionType,
+    ) -> Result<Writer, anyhow::Error> {
+        let file = File::create(path.clone())
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        Ok(
+            Writer {
+                path,
+                file_info,
+                metadata: true,
+                first: true,
+                output: Some(Output::new(file, &compression_type)),
+            }
+        )
+    }
+
+    /// Write or omit timestamp, version, changeset, user and uid fields
+    pub fn with_metadata(&mut self, metadata: bool) {
+        self.metadata = metadata;
+    }
+
+    fn output(&mut self) -> Result<&mut Output, anyhow::Error> {
+        self.output.as_mut()
+            .ok_or(anyhow!("Writer is closed, path: {}", self.path.display()))
+    }
+
+    /// Write the version, generator and bounds fields and open the elements array
+    ///
+    /// Must be called before writing elements.
+    pub fn write_header(&mut self) -> Result<(), anyhow::Error> {
+        let generator = self.file_info.writingprogram().clone()
+            .unwrap_or("osm-io".to_string());
+        let mut header = String::with_capacity(256);
+        header.push_str("{\n");
+        header.push_str(&format!("  \"version\": \"0.6\",\n  \"generator\": {},\n", JsonValue::from(generator).dump()));
+        if let Some(bounding_box) = self.file_info.bounding_box() {
+            header.push_str(
+                &format!(
+                    "  \"bounds\": {{\"minlat\": {:.7}, \"minlon\": {:.7}, \"maxlat\": {:.7}, \"maxlon\": {:.7}}},\n",
+                    bounding_box.bottom(),
+                    bounding_box.left(),
+                    bounding_box.top(),
+                    bounding_box.right(),
+                )
+            );
+        }
+        header.push_str("  \"elements\": [");
+        self.output()?.write_all(header.as_bytes())?;
+        Ok(())
+    }
+
+    /// Write element
+    ///
+    /// [Element::Sentinel] is ignored
+    pub fn write_element(&mut self, element: Element) -> Result<(), anyhow::Error> {
+        if let Element::Sentinel = element {
+            return Ok(());
+        }
+        let value = format_element(&element, self.metadata)?;
+        let separator = if self.first { "\n    " } else { ",\n    " };
+        self.first = false;
+        let output = self.output()?;
+        output.write_all(separator.as_bytes())?;
+        output.write_all(value.dump().as_bytes())?;
+        Ok(())
+    }
+
+    /// Write elements
+    pub fn write_elements(&mut self, elements: Vec<Element>) -> Result<(), anyhow::Error> {
+        for element in elements {
+            self.write_element(element)?;
+        }
+        Ok(())
+    }
+
+    /// Close the elements array and the document and flush the compression stream
+    ///
+    /// Must be called in the end. The writer can not be used after it was closed
+    pub fn close(&mut self) -> Result<(), anyhow::Error> {
+        self.output()?.write_all(b"\n  ]\n}\n")?;
+        self.output.take().unwrap().finish()
+    }
+
+    /// Output path
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+fn format_tags(tags: &[Tag], value: &mut JsonValue) {
+    if tags.is_empty() {
+        return;
+    }
+    let mut object = JsonValue::new_object();
+    for tag in tags {
+        object[tag.k().as_str()] = tag.v().as_str().into();
+    }
+    value["tags"] = object;
+}
+
+#[allow(clippy::too_many_arguments)]
+fn format_common(element_type: &str, id: i64, version: i32, timestamp: i64, changeset: i64, uid: i32, user: &str, visible: bool, metadata: bool) -> Result<JsonValue, anyhow::Error> {
+    let mut value = JsonValue::new_object();
+    value["type"] = element_type.into();
+    value["id"] = id.into();
+    if metadata {
+        if timestamp >= 0 {
+            value["timestamp"] = timestamp_to_iso8601_seconds(timestamp * 1000)?.into();
+        }
+        if version > 0 {
+            value["version"] = version.into();
+        }
+        if changeset >= 0 {
+            value["changeset"] = changeset.into();
+        }
+        if !user.is_empty() {
+            value["user"] = user.into();
+        }
+        if uid >= 0 {
+            value["uid"] = uid.into();
+        }
+    }
+    if !visible {
+        value["visible"] = false.into();
+    }
+    Ok(value)
+}
+
+/// Convert an [Element] into an object of the OSM JSON elements array
+///
+/// * metadata - write or omit timestamp, version, changeset, user and uid fields, missing values
+///   are always omitted
+///
+/// [Element::Sentinel] is converted to null.
+pub fn format_element(element: &Element, metadata: bool) -> Result<JsonValue, anyhow::Error> {
+    match element {
+        Element::Node { node } => {
+            let mut value = format_common("node", node.id(), node.version(), node.timestamp(), node.changeset(), node.uid(), node.user(), node.visible(), metadata)?;
+            if node.visible() {
+                value["lat"] = (node.coordinate().lat7() as f64 / 1E7).into();
+                value["lon"] = (node.coordinate().lon7() as f64 / 1E7).into();
+            }
+            format_tags(node.tags(), &mut value);
+            Ok(value)
+        }
+        Element::Way { way } => {
+            let mut value = format_common("way", way.id(), way.version(), way.timestamp(), way.changeset(), way.uid(), way.user(), way.visible(), metadata)?;
+            if way.visible() {
+                value["nodes"] = way.refs().clone().into();
+            }
+            format_tags(way.tags(), &mut value);
+            Ok(value)
+        }
+        Element::Relation { relation } => {
+            let mut value = format_common("relation", relation.id(), relation.version(), relation.timestamp(), relation.changeset(), relation.uid(), relation.user(), relation.visible(), metadata)?;
+            if relation.visible() {
+                let mut members = JsonValue::new_array();
+                for member in relation.members() {
+                    let (member_type, member_data) = match member {
+                        Member::Node { member } => {
+                            ("node", member)
+                        }
+                        Member::Way { member } => {
+                            ("way", member)
+                        }
+                        Member::Relation { member } => {
+                            ("relation", member)
+                        }
+                    };
+                    let mut m = JsonValue::new_object();
+                    m["type"] = member_type.into();
+                    m["ref"] = member_data.id().into();
+                    m["role"] = member_data.role().as_str().into();
+                    members.push(m)?;
+                }
+                value["members"] = members;
+            }
+            format_tags(relation.tags(), &mut value);
+            Ok(value)
+        }
+        Element::Sentinel => {
+            Ok(JsonValue::Null)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::osm::model::relation::{MemberData, Relation};
+
+    use super::*;
+
+    #[test]
+    fn test_format_element() {
+        let relation = Relation::new(
+            300, 3, 1328148122000, 10500000, 18204, "bob & co".to_string(), true,
+            vec![Member::Way { member: MemberData::new(200, "outer".to_string()) }],
+            vec![Tag::new("type".to_string(), "multipolygon".to_string())],
+        );
+        let value = format_element(&Element::Relation { relation }, true).unwrap();
+        assert_eq!(
+            value.dump(),
+            r#"{"type":"relation","id":300,"timestamp":"2012-02-02T02:02:02Z","version":3,"changeset":10500000,"user":"bob & co","uid":18204,"members":[{"type":"way","ref":200,"role":"outer"}],"tags":{"type":"multipolygon"}}"#
+        );
+    }
+}
@@ -2,3 +2,17 @@ pub mod pbf;
 pub mod model;
 pub mod apidb_dump;
 pub mod converters;
+pub mod xml;
+pub mod o5m;
+pub mod opl;
+pub mod json;
+pub mod index;
+pub mod transform;
+pub mod geometry;
+pub mod geojson;
+pub mod extract;
+pub mod filter;
+pub mod check_refs;
+pub mod history;
+pub mod renumber;
+pub mod stats;
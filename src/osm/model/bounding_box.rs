@@ -5,7 +5,7 @@ use anyhow::anyhow;
 
 use crate::osm::model::coordinate::Coordinate;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BoundingBox {
     left: f64,
     bottom: f64,
@@ -78,6 +78,14 @@ impl BoundingBox {
     pub fn bottom(&self) -> f64 {
         self.bottom
     }
+
+    /// The coordinate is inside the bounding box or on its boundary
+    pub fn contains(&self, coordinate: &Coordinate) -> bool {
+        coordinate.lon() >= self.left
+            && coordinate.lon() <= self.right
+            && coordinate.lat() >= self.bottom
+            && coordinate.lat() <= self.top
+    }
 }
 
 impl Display for BoundingBox {
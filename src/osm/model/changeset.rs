@@ -1,3 +1,142 @@
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::tag::Tag;
+
+/// A comment in the discussion of a changeset
+///
+/// The date is in milliseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangesetComment {
+    date: i64,
+    uid: i32,
+    user: String,
+    text: String,
+}
+
+impl ChangesetComment {
+    pub fn new(date: i64, uid: i32, user: String, text: String) -> ChangesetComment {
+        ChangesetComment {
+            date,
+            uid,
+            user,
+            text,
+        }
+    }
+
+    pub fn date(&self) -> i64 {
+        self.date
+    }
+
+    pub fn uid(&self) -> i32 {
+        self.uid
+    }
+
+    pub fn user(&self) -> &String {
+        &self.user
+    }
+
+    pub fn text(&self) -> &String {
+        &self.text
+    }
+}
+
+/// An OSM changeset
+///
+/// Timestamps are in milliseconds. The bounding box is None for a changeset without changes. The
+/// discussion comments are empty unless set with [Changeset::with_comments], the comments count
+/// is kept separately because not every format carries the discussion itself.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Changeset {
+    id: i64,
+    created_at: i64,
+    closed_at: Option<i64>,
+    open: bool,
+    uid: i32,
+    user: String,
+    bounding_box: Option<BoundingBox>,
+    num_changes: i32,
+    comments_count: i32,
+    tags: Vec<Tag>,
+    comments: Vec<ChangesetComment>,
+}
+
+impl Changeset {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(id: i64, created_at: i64, closed_at: Option<i64>, open: bool, uid: i32, user: String, bounding_box: Option<BoundingBox>, num_changes: i32, comments_count: i32, tags: Vec<Tag>) -> Changeset {
+        Changeset {
+            id,
+            created_at,
+            closed_at,
+            open,
+            uid,
+            user,
+            bounding_box,
+            num_changes,
+            comments_count,
+            tags,
+            comments: Vec::new(),
+        }
+    }
+
+    /// Set the discussion comments
+    pub fn with_comments(&mut self, comments: Vec<ChangesetComment>) {
+        self.comments = comments;
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// The creation time, -1 if it is not known
+    pub fn created_at(&self) -> i64 {
+        self.created_at
+    }
+
+    /// The closing time, None if it is not known, as for open changesets in the planet dumps
+    pub fn closed_at(&self) -> Option<i64> {
+        self.closed_at
+    }
+
+    pub fn open(&self) -> bool {
+        self.open
+    }
+
+    pub fn uid(&self) -> i32 {
+        self.uid
+    }
+
+    pub fn user(&self) -> &String {
+        &self.user
+    }
+
+    pub fn take_user(&mut self) -> String {
+        std::mem::take(&mut self.user)
+    }
+
+    pub fn bounding_box(&self) -> &Option<BoundingBox> {
+        &self.bounding_box
+    }
+
+    pub fn num_changes(&self) -> i32 {
+        self.num_changes
+    }
+
+    pub fn comments_count(&self) -> i32 {
+        self.comments_count
+    }
+
+    pub fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    pub fn take_tags(&mut self) -> Vec<Tag> {
+        std::mem::take(&mut self.tags)
+    }
+
+    pub fn comments(&self) -> &Vec<ChangesetComment> {
+        &self.comments
+    }
 
-}
\ No newline at end of file
+    pub fn take_comments(&mut self) -> Vec<ChangesetComment> {
+        std::mem::take(&mut self.comments)
+    }
+}
@@ -1,4 +1,74 @@
-#[derive(Debug)]
+/// An OSM user and the edits attributed to the user in the data
+///
+/// Every object version counts as one edit, a created, a modified or a deleted object.
+/// Timestamps are in milliseconds, 0 if no edit has a timestamp.
+#[derive(Debug, Clone, PartialEq)]
 pub struct User {
+    uid: i32,
+    name: String,
+    edits: u64,
+    created: u64,
+    modified: u64,
+    deleted: u64,
+    changesets: u64,
+    first_timestamp: i64,
+    last_timestamp: i64,
+}
 
-}
\ No newline at end of file
+impl User {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(uid: i32, name: String, edits: u64, created: u64, modified: u64, deleted: u64, changesets: u64, first_timestamp: i64, last_timestamp: i64) -> User {
+        User {
+            uid,
+            name,
+            edits,
+            created,
+            modified,
+            deleted,
+            changesets,
+            first_timestamp,
+            last_timestamp,
+        }
+    }
+
+    pub fn uid(&self) -> i32 {
+        self.uid
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Number of object versions
+    pub fn edits(&self) -> u64 {
+        self.edits
+    }
+
+    /// Number of first versions
+    pub fn created(&self) -> u64 {
+        self.created
+    }
+
+    /// Number of later visible versions
+    pub fn modified(&self) -> u64 {
+        self.modified
+    }
+
+    /// Number of invisible versions
+    pub fn deleted(&self) -> u64 {
+        self.deleted
+    }
+
+    /// Number of distinct changesets
+    pub fn changesets(&self) -> u64 {
+        self.changesets
+    }
+
+    pub fn first_timestamp(&self) -> i64 {
+        self.first_timestamp
+    }
+
+    pub fn last_timestamp(&self) -> i64 {
+        self.last_timestamp
+    }
+}
@@ -1,3 +1,4 @@
+use crate::osm::model::coordinate::Coordinate;
 use crate::osm::model::tag::Tag;
 
 #[derive(Debug, Clone)]
@@ -11,6 +12,7 @@ pub struct Way {
     visible: bool,
     refs: Vec<i64>,
     tags: Vec<Tag>,
+    coordinates: Vec<Coordinate>,
 }
 
 impl Way {
@@ -26,6 +28,7 @@ impl Way {
             visible,
             refs,
             tags,
+            coordinates: Vec::new(),
         }
     }
 
@@ -72,4 +75,19 @@ impl Way {
     pub fn take_tags(&mut self) -> Vec<Tag> {
         std::mem::take(&mut self.tags)
     }
-}
\ No newline at end of file
+
+    /// Node locations in the order of refs, empty unless the way was read from a file with the
+    /// LocationsOnWays feature or the locations were added with [Way::with_coordinates]
+    pub fn coordinates(&self) -> &Vec<Coordinate> {
+        &self.coordinates
+    }
+
+    /// Set the node locations, must be either empty or have the same length as refs
+    pub fn with_coordinates(&mut self, coordinates: Vec<Coordinate>) {
+        self.coordinates = coordinates;
+    }
+
+    pub fn take_coordinates(&mut self) -> Vec<Coordinate> {
+        std::mem::take(&mut self.coordinates)
+    }
+}
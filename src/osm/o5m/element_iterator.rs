@@ -0,0 +1,331 @@
+use std::io::Read;
+
+use anyhow::{anyhow, Context};
+
+use crate::osm::model::coordinate::Coordinate;
+use crate::osm::model::element::Element;
+use crate::osm::model::node::Node;
+use crate::osm::model::relation::{Member, MemberData, Relation};
+use crate::osm::model::tag::Tag;
+use crate::osm::model::way::Way;
+use crate::osm::o5m::string_table::{MAX_STORED_LENGTH, StringTable};
+use crate::osm::o5m::varint::{read_signed, read_unsigned};
+
+pub(crate) const NODE: u8 = 0x10;
+pub(crate) const WAY: u8 = 0x11;
+pub(crate) const RELATION: u8 = 0x12;
+pub(crate) const BOUNDING_BOX: u8 = 0xdb;
+pub(crate) const FILE_TIMESTAMP: u8 = 0xdc;
+pub(crate) const HEADER: u8 = 0xe0;
+pub(crate) const END_OF_FILE: u8 = 0xfe;
+pub(crate) const RESET: u8 = 0xff;
+
+/// Values used for delta coding. All of them are set to 0 by a reset dataset
+#[derive(Default)]
+pub(crate) struct DeltaState {
+    pub(crate) id: i64,
+    pub(crate) timestamp: i64,
+    pub(crate) changeset: i64,
+    pub(crate) lon: i64,
+    pub(crate) lat: i64,
+    pub(crate) refs: [i64; 3],
+}
+
+struct Author {
+    version: i32,
+    timestamp: i64,
+    changeset: i64,
+    uid: i32,
+    user: String,
+}
+
+/// Read o5m datasets from a stream
+pub(crate) struct DatasetReader {
+    input: Box<dyn Read + Send>,
+    string_table: StringTable,
+    state: DeltaState,
+    buf: Vec<u8>,
+}
+
+impl DatasetReader {
+    pub(crate) fn new(input: Box<dyn Read + Send>) -> DatasetReader {
+        DatasetReader {
+            input,
+            string_table: StringTable::new(),
+            state: DeltaState::default(),
+            buf: Vec::with_capacity(4096),
+        }
+    }
+
+    fn read_byte(&mut self) -> Result<Option<u8>, anyhow::Error> {
+        let mut byte = [0_u8; 1];
+        match self.input.read(&mut byte)? {
+            0 => {
+                Ok(None)
+            }
+            _ => {
+                Ok(Some(byte[0]))
+            }
+        }
+    }
+
+    fn read_length(&mut self) -> Result<usize, anyhow::Error> {
+        let mut result = 0_u64;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_byte()?.ok_or(anyhow!("Unexpected end of file"))?;
+            result |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+            if shift > 63 {
+                return Err(anyhow!("Invalid dataset length"));
+            }
+        }
+        Ok(result as usize)
+    }
+
+    /// Read the next dataset. Reset datasets are handled internally. Returns the dataset type, the
+    /// payload is available in self.buf
+    pub(crate) fn next_dataset(&mut self) -> Result<Option<u8>, anyhow::Error> {
+        loop {
+            let dataset_type = match self.read_byte()? {
+                None => {
+                    return Ok(None);
+                }
+                Some(END_OF_FILE) => {
+                    return Ok(None);
+                }
+                Some(RESET) => {
+                    self.reset();
+                    continue;
+                }
+                Some(t) => {
+                    t
+                }
+            };
+            self.buf.clear();
+            if dataset_type < 0xf0 {
+                let length = self.read_length()?;
+                self.buf.resize(length, 0);
+                self.input.read_exact(&mut self.buf)
+                    .with_context(|| anyhow!("Failed to read dataset of type {:#x}", dataset_type))?;
+            }
+            return Ok(Some(dataset_type));
+        }
+    }
+
+    pub(crate) fn buf(&self) -> &Vec<u8> {
+        &self.buf
+    }
+
+    fn reset(&mut self) {
+        self.state = DeltaState::default();
+        self.string_table.clear();
+    }
+
+    /// Read a single string or a string pair, either inline or as a reference into the string table.
+    /// Returns the raw bytes including the terminating zeros
+    fn read_string(string_table: &mut StringTable, buf: &[u8], pos: &mut usize, pair: bool) -> Result<Vec<u8>, anyhow::Error> {
+        let reference = read_unsigned(buf, pos)? as usize;
+        if reference != 0 {
+            return string_table.get(reference)
+                .cloned()
+                .ok_or(anyhow!("Invalid string reference {}", reference));
+        }
+        let start = *pos;
+        let parts = if pair { 2 } else { 1 };
+        for _ in 0..parts {
+            while *buf.get(*pos).ok_or(anyhow!("Unterminated string"))? != 0 {
+                *pos += 1;
+            }
+            *pos += 1;
+        }
+        let entry = buf[start..*pos].to_vec();
+        if entry.len() - parts <= MAX_STORED_LENGTH {
+            string_table.add(&entry);
+        }
+        Ok(entry)
+    }
+
+    fn split_pair(entry: &[u8]) -> Result<(&[u8], &[u8]), anyhow::Error> {
+        let separator = entry.iter().position(|b| *b == 0).ok_or(anyhow!("Invalid string pair"))?;
+        let second = &entry[separator + 1..];
+        Ok((&entry[..separator], &second[..second.len().saturating_sub(1)]))
+    }
+
+    fn read_tags(&mut self, buf: &[u8], pos: &mut usize) -> Result<Vec<Tag>, anyhow::Error> {
+        let mut tags = Vec::new();
+        while *pos < buf.len() {
+            let entry = Self::read_string(&mut self.string_table, buf, pos, true)?;
+            let (k, v) = Self::split_pair(&entry)?;
+            tags.push(Tag::new(String::from_utf8(k.to_vec())?, String::from_utf8(v.to_vec())?));
+        }
+        Ok(tags)
+    }
+
+    fn read_author(&mut self, buf: &[u8], pos: &mut usize) -> Result<Author, anyhow::Error> {
+        let mut author = Author {
+            version: 0,
+            timestamp: -1,
+            changeset: -1,
+            uid: -1,
+            user: String::default(),
+        };
+        author.version = read_unsigned(buf, pos)? as i32;
+        if author.version != 0 {
+            self.state.timestamp += read_signed(buf, pos)?;
+            if self.state.timestamp != 0 {
+                author.timestamp = self.state.timestamp * 1000;
+                self.state.changeset += read_signed(buf, pos)?;
+                author.changeset = self.state.changeset;
+                let entry = Self::read_string(&mut self.string_table, buf, pos, true)?;
+                let (uid, user) = Self::split_pair(&entry)?;
+                let mut uid_pos = 0;
+                author.uid = if uid.is_empty() { -1 } else { read_unsigned(uid, &mut uid_pos)? as i32 };
+                author.user = String::from_utf8(user.to_vec())?;
+            }
+        }
+        Ok(author)
+    }
+
+    pub(crate) fn read_node(&mut self) -> Result<Element, anyhow::Error> {
+        let buf = std::mem::take(&mut self.buf);
+        let mut pos = 0;
+        self.state.id += read_signed(&buf, &mut pos)?;
+        let id = self.state.id;
+        let author = self.read_author(&buf, &mut pos)?;
+        let visible = pos < buf.len();
+        let mut coordinate = Coordinate::new(0.0, 0.0);
+        let mut tags = Vec::new();
+        if visible {
+            self.state.lon += read_signed(&buf, &mut pos)?;
+            self.state.lat += read_signed(&buf, &mut pos)?;
+            coordinate = Coordinate::new(self.state.lat as f64 / 1E7, self.state.lon as f64 / 1E7);
+            tags = self.read_tags(&buf, &mut pos)?;
+        }
+        self.buf = buf;
+        Ok(
+            Element::Node {
+                node: Node::new(id, author.version, coordinate, author.timestamp, author.changeset, author.uid, author.user, visible, tags)
+            }
+        )
+    }
+
+    pub(crate) fn read_way(&mut self) -> Result<Element, anyhow::Error> {
+        let buf = std::mem::take(&mut self.buf);
+        let mut pos = 0;
+        self.state.id += read_signed(&buf, &mut pos)?;
+        let id = self.state.id;
+        let author = self.read_author(&buf, &mut pos)?;
+        let visible = pos < buf.len();
+        let mut refs = Vec::new();
+        let mut tags = Vec::new();
+        if visible {
+            let refs_length = read_unsigned(&buf, &mut pos)? as usize;
+            let refs_end = pos + refs_length;
+            while pos < refs_end {
+                self.state.refs[0] += read_signed(&buf, &mut pos)?;
+                refs.push(self.state.refs[0]);
+            }
+            tags = self.read_tags(&buf, &mut pos)?;
+        }
+        self.buf = buf;
+        Ok(
+            Element::Way {
+                way: Way::new(id, author.version, author.timestamp, author.changeset, author.uid, author.user, visible, refs, tags)
+            }
+        )
+    }
+
+    pub(crate) fn read_relation(&mut self) -> Result<Element, anyhow::Error> {
+        let buf = std::mem::take(&mut self.buf);
+        let mut pos = 0;
+        self.state.id += read_signed(&buf, &mut pos)?;
+        let id = self.state.id;
+        let author = self.read_author(&buf, &mut pos)?;
+        let visible = pos < buf.len();
+        let mut members = Vec::new();
+        let mut tags = Vec::new();
+        if visible {
+            let refs_length = read_unsigned(&buf, &mut pos)? as usize;
+            let refs_end = pos + refs_length;
+            while pos < refs_end {
+                let delta = read_signed(&buf, &mut pos)?;
+                let entry = Self::read_string(&mut self.string_table, &buf, &mut pos, false)?;
+                if entry.len() < 2 {
+                    return Err(anyhow!("Invalid relation member type and role"));
+                }
+                let role = String::from_utf8(entry[1..entry.len() - 1].to_vec())?;
+                match entry[0] {
+                    b'0' => {
+                        self.state.refs[0] += delta;
+                        members.push(Member::Node { member: MemberData::new(self.state.refs[0], role) });
+                    }
+                    b'1' => {
+                        self.state.refs[1] += delta;
+                        members.push(Member::Way { member: MemberData::new(self.state.refs[1], role) });
+                    }
+                    b'2' => {
+                        self.state.refs[2] += delta;
+                        members.push(Member::Relation { member: MemberData::new(self.state.refs[2], role) });
+                    }
+                    t => {
+                        return Err(anyhow!("Invalid relation member type: {}", t));
+                    }
+                }
+            }
+            tags = self.read_tags(&buf, &mut pos)?;
+        }
+        self.buf = buf;
+        Ok(
+            Element::Relation {
+                relation: Relation::new(id, author.version, author.timestamp, author.changeset, author.uid, author.user, visible, members, tags)
+            }
+        )
+    }
+
+    fn next_element(&mut self) -> Result<Option<Element>, anyhow::Error> {
+        while let Some(dataset_type) = self.next_dataset()? {
+            match dataset_type {
+                NODE => {
+                    return self.read_node().map(Some);
+                }
+                WAY => {
+                    return self.read_way().map(Some);
+                }
+                RELATION => {
+                    return self.read_relation().map(Some);
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Iterate over elements in *.o5m or *.o5c file
+///
+/// Elements deleted in an *.o5c file are produced with visible set to false.
+pub struct ElementIterator {
+    dataset_reader: DatasetReader,
+}
+
+impl ElementIterator {
+    pub(crate) fn new(input: Box<dyn Read + Send>) -> ElementIterator {
+        ElementIterator {
+            dataset_reader: DatasetReader::new(input),
+        }
+    }
+}
+
+impl Iterator for ElementIterator {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.dataset_reader.next_element()
+            .with_context(|| anyhow!("Failed to parse o5m dataset"))
+            .unwrap_or_else(|e| panic!("{:?}", e))
+    }
+}
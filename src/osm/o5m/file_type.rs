@@ -0,0 +1,19 @@
+/// Distinguish between full data (*.o5m) and change (*.o5c) files
+#[derive(Clone, Debug, PartialEq)]
+pub enum FileType {
+    O5m,
+    O5c,
+}
+
+impl FileType {
+    pub(crate) fn header(&self) -> &'static [u8] {
+        match self {
+            FileType::O5m => {
+                b"o5m2"
+            }
+            FileType::O5c => {
+                b"o5c2"
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod reader;
+pub mod element_iterator;
+pub mod writer;
+pub mod file_type;
+pub(crate) mod varint;
+pub(crate) mod string_table;
@@ -0,0 +1,117 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::o5m::element_iterator::{BOUNDING_BOX, DatasetReader, ElementIterator, FILE_TIMESTAMP, HEADER, NODE, RELATION, WAY};
+use crate::osm::o5m::file_type::FileType;
+use crate::osm::o5m::varint::read_signed;
+use crate::osm::pbf::file_info::FileInfo;
+
+/// *.o5m and *.o5c file reader
+///
+/// Prepare the o5m file for reading. The actual reading is performed by associated iterators.
+#[derive(Debug, Clone)]
+pub struct Reader {
+    path: PathBuf,
+    file_type: FileType,
+    info: FileInfo,
+}
+
+impl Reader {
+    /// Create a new Reader
+    ///
+    /// * path - a path to a valid *.o5m or *.o5c file
+    ///
+    /// Example:
+    /// ```
+    /// use std::path::PathBuf;
+    /// use osm_io::osm::o5m::reader::Reader;
+    /// let input_path = PathBuf::from("./planet.o5m");
+    /// let reader = Reader::new(&input_path);
+    /// ```
+    pub fn new(path: &Path) -> Result<Reader, anyhow::Error> {
+        let (file_type, info) = Self::read_header(path)
+            .with_context(|| anyhow!("Failed to parse file header, path: {}", path.display()))?;
+        Ok(
+            Reader {
+                path: path.to_path_buf(),
+                file_type,
+                info,
+            }
+        )
+    }
+
+    fn open(path: &Path) -> Result<DatasetReader, anyhow::Error> {
+        let file = File::open(path)
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        Ok(DatasetReader::new(Box::new(BufReader::new(file))))
+    }
+
+    fn read_header(path: &Path) -> Result<(FileType, FileInfo), anyhow::Error> {
+        let mut dataset_reader = Self::open(path)?;
+        let file_type = match dataset_reader.next_dataset()? {
+            Some(HEADER) if dataset_reader.buf().as_slice() == FileType::O5m.header() => {
+                FileType::O5m
+            }
+            Some(HEADER) if dataset_reader.buf().as_slice() == FileType::O5c.header() => {
+                FileType::O5c
+            }
+            _ => {
+                return Err(anyhow!("Not an o5m or o5c file"));
+            }
+        };
+
+        let mut info = FileInfo::default();
+        while let Some(dataset_type) = dataset_reader.next_dataset()? {
+            match dataset_type {
+                BOUNDING_BOX => {
+                    let buf = dataset_reader.buf();
+                    let mut pos = 0;
+                    let left = read_signed(buf, &mut pos)? as f64 / 1E7;
+                    let bottom = read_signed(buf, &mut pos)? as f64 / 1E7;
+                    let right = read_signed(buf, &mut pos)? as f64 / 1E7;
+                    let top = read_signed(buf, &mut pos)? as f64 / 1E7;
+                    info.with_bounding_box(&Some(BoundingBox::new(left, bottom, right, top)));
+                }
+                FILE_TIMESTAMP => {
+                    let mut pos = 0;
+                    info.with_osmosis_replication_timestamp(&Some(read_signed(dataset_reader.buf(), &mut pos)?));
+                }
+                NODE | WAY | RELATION => {
+                    break;
+                }
+                _ => {}
+            }
+        }
+        Ok((file_type, info))
+    }
+
+    /// Iterator used to iterate over elements.
+    ///
+    /// Objects deleted in an *.o5c file are produced with visible set to false.
+    pub fn elements(&self) -> Result<ElementIterator, anyhow::Error> {
+        let file = File::open(&self.path)
+            .with_context(|| anyhow!("path: {}", self.path.display()))?;
+        Ok(
+            ElementIterator::new(Box::new(BufReader::new(file)))
+        )
+    }
+
+    /// Header data. The bounding box and the file timestamp are taken from the corresponding
+    /// datasets
+    pub fn info(&self) -> &FileInfo {
+        &self.info
+    }
+
+    /// Full data or change file
+    pub fn file_type(&self) -> &FileType {
+        &self.file_type
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
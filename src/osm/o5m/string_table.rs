@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+/// Number of entries in the o5m string reference table
+const TABLE_SIZE: usize = 15000;
+/// Strings or string pairs longer than this are never stored in the reference table
+pub(crate) const MAX_STORED_LENGTH: usize = 250;
+
+/// The o5m string reference table, used by the reader to resolve references
+pub(crate) struct StringTable {
+    entries: Vec<Vec<u8>>,
+    next: usize,
+}
+
+impl StringTable {
+    pub(crate) fn new() -> StringTable {
+        StringTable {
+            entries: vec![Vec::new(); TABLE_SIZE],
+            next: 0,
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        for entry in self.entries.iter_mut() {
+            entry.clear();
+        }
+        self.next = 0;
+    }
+
+    /// Store the raw bytes of a string or a string pair including the terminating zeros
+    pub(crate) fn add(&mut self, entry: &[u8]) {
+        self.entries[self.next].clear();
+        self.entries[self.next].extend_from_slice(entry);
+        self.next = (self.next + 1) % TABLE_SIZE;
+    }
+
+    /// Get the entry stored `reference` positions back, 1 is the most recent
+    pub(crate) fn get(&self, reference: usize) -> Option<&Vec<u8>> {
+        if reference == 0 || reference > TABLE_SIZE {
+            None
+        } else {
+            self.entries.get((self.next + TABLE_SIZE - reference) % TABLE_SIZE)
+        }
+    }
+}
+
+/// The writer side of the o5m string reference table
+pub(crate) struct StringTableBuilder {
+    positions: HashMap<Vec<u8>, u64>,
+    count: u64,
+}
+
+impl StringTableBuilder {
+    pub(crate) fn new() -> StringTableBuilder {
+        StringTableBuilder {
+            positions: HashMap::new(),
+            count: 0,
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.positions.clear();
+        self.count = 0;
+    }
+
+    /// Find the reference to an entry if it is still in the table. Otherwise store it, if it is short
+    /// enough, and return None so that the caller writes it inline
+    pub(crate) fn reference(&mut self, entry: &[u8], length: usize) -> Option<u64> {
+        if let Some(position) = self.positions.get(entry) {
+            let reference = self.count - position;
+            if reference <= TABLE_SIZE as u64 {
+                return Some(reference);
+            }
+        }
+        if length <= MAX_STORED_LENGTH {
+            self.positions.insert(entry.to_vec(), self.count);
+            self.count += 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_table() {
+        let mut table = StringTable::new();
+        let mut builder = StringTableBuilder::new();
+        for i in 0..(TABLE_SIZE + 10) {
+            let entry = format!("{}\0", i).into_bytes();
+            assert_eq!(builder.reference(&entry, entry.len() - 1), None);
+            table.add(&entry);
+        }
+        assert_eq!(table.get(1).unwrap(), &format!("{}\0", TABLE_SIZE + 9).into_bytes());
+        assert_eq!(builder.reference(&format!("{}\0", TABLE_SIZE + 9).into_bytes(), 5), Some(1));
+        assert_eq!(table.get(TABLE_SIZE).unwrap(), &b"10\0".to_vec());
+        assert_eq!(builder.reference(b"10\0", 2), Some(TABLE_SIZE as u64));
+        // overwritten
+        assert_eq!(builder.reference(b"9\0", 1), None);
+        assert!(table.get(0).is_none());
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::anyhow;
+
+/// Read an unsigned variable length integer, 7 bits per byte, least significant group first
+pub(crate) fn read_unsigned(buf: &[u8], pos: &mut usize) -> Result<u64, anyhow::Error> {
+    let mut result = 0_u64;
+    let mut shift = 0;
+    loop {
+        let byte = *buf.get(*pos).ok_or(anyhow!("Unexpected end of dataset"))?;
+        *pos += 1;
+        if shift > 63 {
+            return Err(anyhow!("Variable length integer is too long"));
+        }
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok(result)
+}
+
+/// Read a signed variable length integer, the least significant bit is the sign
+pub(crate) fn read_signed(buf: &[u8], pos: &mut usize) -> Result<i64, anyhow::Error> {
+    let u = read_unsigned(buf, pos)?;
+    Ok(((u >> 1) as i64) ^ -((u & 1) as i64))
+}
+
+pub(crate) fn write_unsigned(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+pub(crate) fn write_signed(buf: &mut Vec<u8>, value: i64) {
+    write_unsigned(buf, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varint() {
+        // examples from the o5m format specification
+        let mut buf = Vec::new();
+        write_unsigned(&mut buf, 323);
+        assert_eq!(buf, vec![0xc3, 0x02]);
+        buf.clear();
+        write_signed(&mut buf, -65);
+        assert_eq!(buf, vec![0x81, 0x01]);
+        buf.clear();
+        write_signed(&mut buf, 64);
+        assert_eq!(buf, vec![0x80, 0x01]);
+
+        buf.clear();
+        let values = [0_i64, 1, -1, 63, -64, 1 << 40, -(1 << 40), i64::MAX, i64::MIN];
+        for value in values {
+            write_signed(&mut buf, value);
+        }
+        let mut pos = 0;
+        for value in values {
+            assert_eq!(read_signed(&buf, &mut pos).unwrap(), value);
+        }
+        assert_eq!(pos, buf.len());
+        assert!(read_unsigned(&buf, &mut pos).is_err());
+    }
+}
@@ -0,0 +1,285 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context};
+
+use crate::osm::model::element::Element;
+use crate::osm::model::relation::Member;
+use crate::osm::model::tag::Tag;
+use crate::osm::o5m::element_iterator::{BOUNDING_BOX, DeltaState, END_OF_FILE, FILE_TIMESTAMP, HEADER, NODE, RELATION, RESET, WAY};
+use crate::osm::o5m::file_type::FileType;
+use crate::osm::o5m::string_table::StringTableBuilder;
+use crate::osm::o5m::varint::{write_signed, write_unsigned};
+use crate::osm::pbf::file_info::FileInfo;
+
+/// *.o5m and *.o5c file writer
+///
+/// A reset dataset is written whenever the element type changes, so elements should be ordered
+/// by type as in *.osm.pbf files. When writing an *.o5c file, elements with visible set to false
+/// are written as deletions, *.o5m files can not contain deletions. A uid of -1, no author, is
+/// written as an empty uid.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::o5m;
+/// use osm_io::osm::o5m::file_type::FileType;
+/// use osm_io::osm::pbf;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109.o5m");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let mut writer = o5m::writer::Writer::from_file_info(
+///         output_path,
+///         reader.info().clone(),
+///         FileType::O5m,
+///     )?;
+///
+///     writer.write_header()?;
+///     for element in reader.elements()? {
+///         writer.write_element(element)?;
+///     }
+///     writer.close()?;
+///     Ok(())
+/// }
+/// ```
+pub struct Writer {
+    path: PathBuf,
+    file_info: FileInfo,
+    file_type: FileType,
+    output: BufWriter<File>,
+    string_table_builder: StringTableBuilder,
+    state: DeltaState,
+    current_type: Option<u8>,
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    /// Create a new [Writer] from [FileInfo]
+    ///
+    /// The bounding box and the osmosis replication timestamp are taken from the file_info
+    pub fn from_file_info(
+        path: PathBuf,
+        file_info: FileInfo,
+        file_type: FileType,
+    ) -> Result<Writer, anyhow::Error> {
+        let file = File::create(path.clone())
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        Ok(
+            Writer {
+                path,
+                file_info,
+                file_type,
+                output: BufWriter::new(file),
+                string_table_builder: StringTableBuilder::new(),
+                state: DeltaState::default(),
+                current_type: None,
+                buf: Vec::with_capacity(4096),
+            }
+        )
+    }
+
+    fn write_dataset(&mut self, dataset_type: u8) -> Result<(), anyhow::Error> {
+        let mut length = Vec::with_capacity(4);
+        write_unsigned(&mut length, self.buf.len() as u64);
+        self.output.write_all(&[dataset_type])?;
+        self.output.write_all(&length)?;
+        self.output.write_all(&self.buf)?;
+        self.buf.clear();
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), anyhow::Error> {
+        self.output.write_all(&[RESET])?;
+        self.state = DeltaState::default();
+        self.string_table_builder.clear();
+        Ok(())
+    }
+
+    /// Write the header, bounding box and file timestamp datasets
+    ///
+    /// Must be called before writing elements.
+    pub fn write_header(&mut self) -> Result<(), anyhow::Error> {
+        self.reset()?;
+        self.buf.extend_from_slice(self.file_type.header());
+        self.write_dataset(HEADER)?;
+        if let Some(timestamp) = *self.file_info.osmosis_replication_timestamp() {
+            write_signed(&mut self.buf, timestamp);
+            self.write_dataset(FILE_TIMESTAMP)?;
+        }
+        if let Some(bounding_box) = self.file_info.bounding_box().clone() {
+            write_signed(&mut self.buf, (bounding_box.left() * 1E7).round() as i64);
+            write_signed(&mut self.buf, (bounding_box.bottom() * 1E7).round() as i64);
+            write_signed(&mut self.buf, (bounding_box.right() * 1E7).round() as i64);
+            write_signed(&mut self.buf, (bounding_box.top() * 1E7).round() as i64);
+            self.write_dataset(BOUNDING_BOX)?;
+        }
+        Ok(())
+    }
+
+    /// Write a string or a string pair given as raw bytes including the terminating zeros
+    fn write_string(&mut self, entry: &[u8], parts: usize) {
+        match self.string_table_builder.reference(entry, entry.len() - parts) {
+            None => {
+                self.buf.push(0);
+                self.buf.extend_from_slice(entry);
+            }
+            Some(reference) => {
+                write_unsigned(&mut self.buf, reference);
+            }
+        }
+    }
+
+    fn write_pair(&mut self, first: &[u8], second: &[u8]) {
+        let mut entry = Vec::with_capacity(first.len() + second.len() + 2);
+        entry.extend_from_slice(first);
+        entry.push(0);
+        entry.extend_from_slice(second);
+        entry.push(0);
+        self.write_string(&entry, 2);
+    }
+
+    fn write_tags(&mut self, tags: &[Tag]) {
+        for tag in tags {
+            self.write_pair(tag.k().as_bytes(), tag.v().as_bytes());
+        }
+    }
+
+    fn write_id_and_author(&mut self, id: i64, version: i32, timestamp: i64, changeset: i64, uid: i32, user: &str) {
+        write_signed(&mut self.buf, id - self.state.id);
+        self.state.id = id;
+        if version <= 0 {
+            self.buf.push(0);
+            return;
+        }
+        write_unsigned(&mut self.buf, version as u64);
+        let timestamp = timestamp.max(0) / 1000;
+        write_signed(&mut self.buf, timestamp - self.state.timestamp);
+        self.state.timestamp = timestamp;
+        if timestamp != 0 {
+            write_signed(&mut self.buf, changeset - self.state.changeset);
+            self.state.changeset = changeset;
+            let mut uid_bytes = Vec::with_capacity(5);
+            if uid > 0 {
+                write_unsigned(&mut uid_bytes, uid as u64);
+            }
+            self.write_pair(&uid_bytes, user.as_bytes());
+        }
+    }
+
+    /// Write element
+    ///
+    /// [Element::Sentinel] is ignored. Fails for elements with visible set to false unless writing
+    /// an *.o5c file
+    pub fn write_element(&mut self, element: Element) -> Result<(), anyhow::Error> {
+        let visible = match &element {
+            Element::Node { node } => node.visible(),
+            Element::Way { way } => way.visible(),
+            Element::Relation { relation } => relation.visible(),
+            Element::Sentinel => true,
+        };
+        if !visible && self.file_type != FileType::O5c {
+            return Err(anyhow!("Deletions can only be written to *.o5c files, path: {}", self.path.display()));
+        }
+        let dataset_type = match &element {
+            Element::Node { .. } => {
+                NODE
+            }
+            Element::Way { .. } => {
+                WAY
+            }
+            Element::Relation { .. } => {
+                RELATION
+            }
+            Element::Sentinel => {
+                return Ok(());
+            }
+        };
+        if self.current_type.is_some() && self.current_type != Some(dataset_type) {
+            self.reset()?;
+        }
+        self.current_type = Some(dataset_type);
+
+        match &element {
+            Element::Node { node } => {
+                self.write_id_and_author(node.id(), node.version(), node.timestamp(), node.changeset(), node.uid(), node.user());
+                if node.visible() {
+                    let lon = node.coordinate().lon7();
+                    let lat = node.coordinate().lat7();
+                    write_signed(&mut self.buf, lon - self.state.lon);
+                    write_signed(&mut self.buf, lat - self.state.lat);
+                    self.state.lon = lon;
+                    self.state.lat = lat;
+                    self.write_tags(node.tags());
+                }
+            }
+            Element::Way { way } => {
+                self.write_id_and_author(way.id(), way.version(), way.timestamp(), way.changeset(), way.uid(), way.user());
+                if way.visible() {
+                    let mut refs = Vec::with_capacity(way.refs().len() * 4);
+                    for r in way.refs() {
+                        write_signed(&mut refs, r - self.state.refs[0]);
+                        self.state.refs[0] = *r;
+                    }
+                    write_unsigned(&mut self.buf, refs.len() as u64);
+                    self.buf.extend_from_slice(&refs);
+                    self.write_tags(way.tags());
+                }
+            }
+            Element::Relation { relation } => {
+                self.write_id_and_author(relation.id(), relation.version(), relation.timestamp(), relation.changeset(), relation.uid(), relation.user());
+                if relation.visible() {
+                    let tags_and_author = std::mem::take(&mut self.buf);
+                    for member in relation.members() {
+                        let (index, member_type, member_data) = match member {
+                            Member::Node { member } => {
+                                (0, b'0', member)
+                            }
+                            Member::Way { member } => {
+                                (1, b'1', member)
+                            }
+                            Member::Relation { member } => {
+                                (2, b'2', member)
+                            }
+                        };
+                        write_signed(&mut self.buf, member_data.id() - self.state.refs[index]);
+                        self.state.refs[index] = member_data.id();
+                        let mut entry = Vec::with_capacity(member_data.role().len() + 2);
+                        entry.push(member_type);
+                        entry.extend_from_slice(member_data.role().as_bytes());
+                        entry.push(0);
+                        self.write_string(&entry, 1);
+                    }
+                    let members = std::mem::replace(&mut self.buf, tags_and_author);
+                    write_unsigned(&mut self.buf, members.len() as u64);
+                    self.buf.extend_from_slice(&members);
+                    self.write_tags(relation.tags());
+                }
+            }
+            Element::Sentinel => {}
+        }
+        self.write_dataset(dataset_type)
+    }
+
+    /// Write elements
+    pub fn write_elements(&mut self, elements: Vec<Element>) -> Result<(), anyhow::Error> {
+        for element in elements {
+            self.write_element(element)?;
+        }
+        Ok(())
+    }
+
+    /// Write the end of file marker and flush the internal buffers
+    ///
+    /// Must be called in the end
+    pub fn close(&mut self) -> Result<(), anyhow::Error> {
+        self.output.write_all(&[END_OF_FILE])?;
+        self.output.flush()?;
+        Ok(())
+    }
+
+    /// Output path
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
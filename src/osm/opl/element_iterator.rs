@@ -0,0 +1,275 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+use crate::osm::converters::iso8601_to_timestamp_millis;
+use crate::osm::model::coordinate::Coordinate;
+use crate::osm::model::element::Element;
+use crate::osm::model::node::Node;
+use crate::osm::model::relation::{Member, MemberData, Relation};
+use crate::osm::model::tag::Tag;
+use crate::osm::model::way::Way;
+
+/// Decode the %xxxx% escape sequences of an OPL string, where xxxx is the hexadecimal Unicode
+/// code point
+pub fn unescape(s: &str) -> Result<String, anyhow::Error> {
+    if !s.contains('%') {
+        return Ok(s.to_string());
+    }
+    let mut unescaped = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('%') {
+        unescaped.push_str(&rest[..start]);
+        let escaped = &rest[start + 1..];
+        let end = escaped.find('%')
+            .ok_or(anyhow!("Unterminated escape sequence in {}", s))?;
+        let code = u32::from_str_radix(&escaped[..end], 16)
+            .map_err(|_| anyhow!("Invalid escape sequence in {}", s))?;
+        unescaped.push(char::from_u32(code).ok_or(anyhow!("Invalid code point {:x} in {}", code, s))?);
+        rest = &escaped[end + 1..];
+    }
+    unescaped.push_str(rest);
+    Ok(unescaped)
+}
+
+fn parse<T: FromStr>(name: char, value: &str) -> Result<T, anyhow::Error> {
+    value.parse::<T>()
+        .map_err(|_| anyhow!("Invalid value of field {}: {}", name, value))
+}
+
+fn parse_tags(value: &str) -> Result<Vec<Tag>, anyhow::Error> {
+    let mut tags = Vec::new();
+    if value.is_empty() {
+        return Ok(tags);
+    }
+    for pair in value.split(',') {
+        let (k, v) = pair.split_once('=')
+            .ok_or(anyhow!("Invalid tag: {}", pair))?;
+        tags.push(Tag::new(unescape(k)?, unescape(v)?));
+    }
+    Ok(tags)
+}
+
+fn parse_refs(value: &str) -> Result<Vec<i64>, anyhow::Error> {
+    let mut refs = Vec::new();
+    if value.is_empty() {
+        return Ok(refs);
+    }
+    for r in value.split(',') {
+        let id = r.strip_prefix('n')
+            .ok_or(anyhow!("Invalid node reference: {}", r))?;
+        refs.push(parse('N', id)?);
+    }
+    Ok(refs)
+}
+
+fn parse_members(value: &str) -> Result<Vec<Member>, anyhow::Error> {
+    let mut members = Vec::new();
+    if value.is_empty() {
+        return Ok(members);
+    }
+    for m in value.split(',') {
+        let (reference, role) = m.split_once('@')
+            .ok_or(anyhow!("Invalid relation member: {}", m))?;
+        let mut chars = reference.chars();
+        let member_type = chars.next()
+            .ok_or(anyhow!("Invalid relation member: {}", m))?;
+        let member = MemberData::new(parse('M', chars.as_str())?, unescape(role)?);
+        match member_type {
+            'n' => {
+                members.push(Member::Node { member });
+            }
+            'w' => {
+                members.push(Member::Way { member });
+            }
+            'r' => {
+                members.push(Member::Relation { member });
+            }
+            t => {
+                return Err(anyhow!("Invalid relation member type: {}", t));
+            }
+        }
+    }
+    Ok(members)
+}
+
+/// Parse a single OPL line into an [Element]
+///
+/// Returns None for an empty line. Missing metadata fields get the same defaults as in the OSM XML
+/// reader.
+pub fn parse_element(line: &str) -> Result<Option<Element>, anyhow::Error> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    let mut fields = line.split(' ').filter(|f| !f.is_empty());
+    let first = fields.next().unwrap();
+    let mut chars = first.chars();
+    let element_type = chars.next().unwrap();
+    let id: i64 = parse(element_type, chars.as_str())?;
+
+    let mut version = 0;
+    let mut visible = true;
+    let mut changeset = -1;
+    let mut timestamp = -1;
+    let mut uid = -1;
+    let mut user = String::default();
+    let mut tags = Vec::new();
+    let mut lon = None;
+    let mut lat = None;
+    let mut refs = Vec::new();
+    let mut members = Vec::new();
+
+    for field in fields {
+        let mut chars = field.chars();
+        let name = chars.next().unwrap();
+        let value = chars.as_str();
+        match name {
+            'v' => {
+                version = parse(name, value)?;
+            }
+            'd' => {
+                visible = match value {
+                    "V" => true,
+                    "D" => false,
+                    _ => return Err(anyhow!("Invalid value of field d: {}", value)),
+                };
+            }
+            'c' => {
+                changeset = parse(name, value)?;
+            }
+            't' => {
+                if !value.is_empty() {
+                    timestamp = iso8601_to_timestamp_millis(value)?;
+                }
+            }
+            'i' => {
+                uid = parse(name, value)?;
+            }
+            'u' => {
+                user = unescape(value)?;
+            }
+            'T' => {
+                tags = parse_tags(value)?;
+            }
+            'x' => {
+                if !value.is_empty() {
+                    lon = Some(parse::<f64>(name, value)?);
+                }
+            }
+            'y' => {
+                if !value.is_empty() {
+                    lat = Some(parse::<f64>(name, value)?);
+                }
+            }
+            'N' => {
+                refs = parse_refs(value)?;
+            }
+            'M' => {
+                members = parse_members(value)?;
+            }
+            _ => {
+                return Err(anyhow!("Unknown field: {}", field));
+            }
+        }
+    }
+
+    let element = match element_type {
+        'n' => {
+            let coordinate = Coordinate::new(lat.unwrap_or(0.0), lon.unwrap_or(0.0));
+            Element::Node {
+                node: Node::new(id, version, coordinate, timestamp, changeset, uid, user, visible, tags)
+            }
+        }
+        'w' => {
+            Element::Way {
+                way: Way::new(id, version, timestamp, changeset, uid, user, visible, refs, tags)
+            }
+        }
+        'r' => {
+            Element::Relation {
+                relation: Relation::new(id, version, timestamp, changeset, uid, user, visible, members, tags)
+            }
+        }
+        t => {
+            return Err(anyhow!("Invalid element type: {}", t));
+        }
+    };
+    Ok(Some(element))
+}
+
+/// Iterate over elements in *.opl file
+///
+/// The file is read line by line, so only the current line is kept in memory.
+pub struct ElementIterator {
+    input: Box<dyn BufRead + Send>,
+    line: String,
+    line_number: usize,
+}
+
+impl ElementIterator {
+    pub(crate) fn new(input: Box<dyn BufRead + Send>) -> ElementIterator {
+        ElementIterator {
+            input,
+            line: String::with_capacity(1024),
+            line_number: 0,
+        }
+    }
+
+    fn next_element(&mut self) -> Result<Option<Element>, anyhow::Error> {
+        loop {
+            self.line.clear();
+            if self.input.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            self.line_number += 1;
+            if let Some(element) = parse_element(&self.line)? {
+                return Ok(Some(element));
+            }
+        }
+    }
+}
+
+impl Iterator for ElementIterator {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_element()
+            .with_context(|| anyhow!("Failed to parse OPL line {}", self.line_number))
+            .unwrap_or_else(|e| panic!("{:?}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape("plain").unwrap(), "plain");
+        assert_eq!(unescape("bob%20%%26%%20%co").unwrap(), "bob & co");
+        assert_eq!(unescape("%1f600%").unwrap(), "\u{1f600}");
+        assert!(unescape("broken%20").is_err());
+    }
+
+    #[test]
+    fn test_parse_element() {
+        let element = parse_element("r300 v3 dV c10500000 t2012-02-02T02:02:02Z i18204 ubob%20%co Ttype=multipolygon Mw200@outer,n101@")
+            .unwrap()
+            .unwrap();
+        match element {
+            Element::Relation { relation } => {
+                assert_eq!(relation.id(), 300);
+                assert_eq!(relation.version(), 3);
+                assert_eq!(relation.user(), "bob co");
+                assert_eq!(relation.tags()[0].v(), "multipolygon");
+                assert_eq!(relation.members().len(), 2);
+                assert!(matches!(&relation.members()[1], Member::Node { member } if member.id() == 101 && member.role().is_empty()));
+            }
+            _ => panic!("expected a relation"),
+        }
+        assert!(parse_element("").unwrap().is_none());
+        assert!(parse_element("n1 q5").is_err());
+    }
+}
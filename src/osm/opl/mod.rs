@@ -0,0 +1,3 @@
+pub mod reader;
+pub mod element_iterator;
+pub mod writer;
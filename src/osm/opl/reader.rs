@@ -0,0 +1,80 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+
+use crate::osm::opl::element_iterator::ElementIterator;
+use crate::osm::pbf::file_info::FileInfo;
+use crate::osm::xml;
+use crate::osm::xml::compression_type::CompressionType;
+
+/// *.opl, *.opl.gz and *.opl.bz2 file reader
+///
+/// Prepare the OPL file for reading. The actual reading is performed by associated iterators.
+#[derive(Debug, Clone)]
+pub struct Reader {
+    path: PathBuf,
+    compression_type: CompressionType,
+    info: FileInfo,
+}
+
+impl Reader {
+    /// Create a new Reader
+    ///
+    /// * path - a path to a valid *.opl, *.opl.gz or *.opl.bz2 file
+    ///
+    /// The compression is deduced from the file extension.
+    ///
+    /// Example:
+    /// ```
+    /// use std::path::PathBuf;
+    /// use osm_io::osm::opl::reader::Reader;
+    /// let input_path = PathBuf::from("./map.opl");
+    /// let reader = Reader::new(&input_path);
+    /// ```
+    pub fn new(path: &Path) -> Result<Reader, anyhow::Error> {
+        let compression_type = CompressionType::from_path(path);
+        // fail early on a missing file, same as the other readers
+        xml::reader::Reader::open(path, &compression_type)
+            .with_context(|| anyhow!("Failed to open OPL file, path: {}", path.display()))?;
+        Ok(
+            Reader {
+                path: path.to_path_buf(),
+                compression_type,
+                info: FileInfo::default(),
+            }
+        )
+    }
+
+    /// Iterator used to iterate over elements.
+    /// Example:
+    /// ```
+    /// use std::path::PathBuf;
+    /// use osm_io::osm::opl;
+    /// fn example() -> Result<(), anyhow::Error> {
+    ///     let input_path = PathBuf::from("./tests/fixtures/history-sample.opl");
+    ///     let reader = opl::reader::Reader::new(&input_path)?;
+    ///     for element in reader.elements()? {
+    ///         println!("{:?}", element);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn elements(&self) -> Result<ElementIterator, anyhow::Error> {
+        Ok(
+            ElementIterator::new(xml::reader::Reader::open(&self.path, &self.compression_type)?)
+        )
+    }
+
+    /// Header data. OPL files have no header, so this is always the default [FileInfo]
+    pub fn info(&self) -> &FileInfo {
+        &self.info
+    }
+
+    pub fn compression_type(&self) -> &CompressionType {
+        &self.compression_type
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
@@ -0,0 +1,266 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context};
+
+use crate::osm::converters::timestamp_to_iso8601_seconds;
+use crate::osm::model::element::Element;
+use crate::osm::model::relation::Member;
+use crate::osm::model::tag::Tag;
+use crate::osm::pbf::file_info::FileInfo;
+use crate::osm::xml::compression_type::CompressionType;
+use crate::osm::xml::writer::Output;
+
+/// *.opl file writer
+///
+/// Write one line per element in the osmium OPL format, optionally compressed with gzip or bzip2.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::opl;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::xml::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109.opl");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let mut writer = opl::writer::Writer::from_file_info(
+///         output_path,
+///         reader.info().clone(),
+///         CompressionType::Uncompressed,
+///     )?;
+///
+///     writer.write_header()?;
+///     for element in reader.elements()? {
+///         writer.write_element(element)?;
+///     }
+///     writer.close()?;
+///     Ok(())
+/// }
+/// ```
+pub struct Writer {
+    path: PathBuf,
+    #[allow(dead_code)]
+    file_info: FileInfo,
+    metadata: bool,
+    output: Option<Output>,
+}
+
+impl Writer {
+    /// Create a new [Writer] from [FileInfo]
+    ///
+    /// OPL has no header, the file_info is accepted for symmetry with the other writers. User and
+    /// changeset metadata are written by default, see [Writer::with_metadata]
+    pub fn from_file_info(
+        path: PathBuf,
+        file_info: FileInfo,
+        compression_type: CompressionType,
+    ) -> Result<Writer, anyhow::Error> {
+        let file = File::create(path.clone())
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        Ok(
+            Writer {
+                path,
+                file_info,
+                metadata: true,
+                output: Some(Output::new(file, &compression_type)),
+            }
+        )
+    }
+
+    /// Write or omit version, changeset, timestamp, uid and user fields
+    pub fn with_metadata(&mut self, metadata: bool) {
+        self.metadata = metadata;
+    }
+
+    fn output(&mut self) -> Result<&mut Output, anyhow::Error> {
+        self.output.as_mut()
+            .ok_or(anyhow!("Writer is closed, path: {}", self.path.display()))
+    }
+
+    /// OPL files have no header, provided for symmetry with the other writers
+    pub fn write_header(&mut self) -> Result<(), anyhow::Error> {
+        self.output()?;
+        Ok(())
+    }
+
+    /// Write element
+    ///
+    /// [Element::Sentinel] is ignored
+    pub fn write_element(&mut self, element: Element) -> Result<(), anyhow::Error> {
+        if let Element::Sentinel = element {
+            return Ok(());
+        }
+        let mut line = format_element(&element, self.metadata)?;
+        line.push('\n');
+        self.output()?.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Write elements
+    pub fn write_elements(&mut self, elements: Vec<Element>) -> Result<(), anyhow::Error> {
+        for element in elements {
+            self.write_element(element)?;
+        }
+        Ok(())
+    }
+
+    /// Flush the compression stream
+    ///
+    /// Must be called in the end. The writer can not be used after it was closed
+    pub fn close(&mut self) -> Result<(), anyhow::Error> {
+        self.output()?;
+        self.output.take().unwrap().finish()
+    }
+
+    /// Output path
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+/// Escape a string for use in an OPL field
+///
+/// Space, comma, equal sign, at sign, percent sign, control and whitespace characters are replaced
+/// with %xxxx%, where xxxx is the hexadecimal Unicode code point.
+pub fn escape(s: &str) -> Cow<'_, str> {
+    fn needs_escape(c: char) -> bool {
+        matches!(c, ' ' | ',' | '=' | '@' | '%') || c.is_control() || c.is_whitespace()
+    }
+
+    if !s.contains(needs_escape) {
+        return Cow::Borrowed(s);
+    }
+    let mut escaped = String::with_capacity(s.len() + 16);
+    for c in s.chars() {
+        if needs_escape(c) {
+            escaped.push_str(&format!("%{:x}%", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Format a coordinate given in 1e-7 degrees without trailing zeros
+fn format_coordinate(value7: i64) -> String {
+    let sign = if value7 < 0 { "-" } else { "" };
+    let value7 = value7.unsigned_abs();
+    let fraction = format!("{:07}", value7 % 10_000_000);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{}{}", sign, value7 / 10_000_000)
+    } else {
+        format!("{}{}.{}", sign, value7 / 10_000_000, fraction)
+    }
+}
+
+fn format_tags(tags: &[Tag], buf: &mut String) {
+    buf.push_str(" T");
+    for (i, tag) in tags.iter().enumerate() {
+        if i > 0 {
+            buf.push(',');
+        }
+        buf.push_str(&escape(tag.k()));
+        buf.push('=');
+        buf.push_str(&escape(tag.v()));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn format_common(prefix: char, id: i64, version: i32, timestamp: i64, changeset: i64, uid: i32, user: &str, visible: bool, metadata: bool, buf: &mut String) -> Result<(), anyhow::Error> {
+    buf.push_str(&format!("{}{}", prefix, id));
+    if metadata {
+        buf.push_str(&format!(" v{}", version));
+    }
+    buf.push_str(if visible { " dV" } else { " dD" });
+    if metadata {
+        buf.push_str(&format!(" c{}", changeset.max(0)));
+        buf.push_str(" t");
+        if timestamp >= 0 {
+            buf.push_str(&timestamp_to_iso8601_seconds(timestamp * 1000)?);
+        }
+        buf.push_str(&format!(" i{}", uid.max(0)));
+        buf.push_str(" u");
+        buf.push_str(&escape(user));
+    }
+    Ok(())
+}
+
+/// Format an [Element] as a single OPL line without the terminating line break
+///
+/// * metadata - write or omit version, changeset, timestamp, uid and user fields
+///
+/// [Element::Sentinel] is formatted as an empty string.
+pub fn format_element(element: &Element, metadata: bool) -> Result<String, anyhow::Error> {
+    let mut buf = String::with_capacity(256);
+    match element {
+        Element::Node { node } => {
+            format_common('n', node.id(), node.version(), node.timestamp(), node.changeset(), node.uid(), node.user(), node.visible(), metadata, &mut buf)?;
+            format_tags(node.tags(), &mut buf);
+            if node.visible() {
+                buf.push_str(" x");
+                buf.push_str(&format_coordinate(node.coordinate().lon7()));
+                buf.push_str(" y");
+                buf.push_str(&format_coordinate(node.coordinate().lat7()));
+            } else {
+                buf.push_str(" x y");
+            }
+        }
+        Element::Way { way } => {
+            format_common('w', way.id(), way.version(), way.timestamp(), way.changeset(), way.uid(), way.user(), way.visible(), metadata, &mut buf)?;
+            format_tags(way.tags(), &mut buf);
+            buf.push_str(" N");
+            let refs: Vec<String> = way.refs().iter().map(|r| format!("n{}", r)).collect();
+            buf.push_str(&refs.join(","));
+        }
+        Element::Relation { relation } => {
+            format_common('r', relation.id(), relation.version(), relation.timestamp(), relation.changeset(), relation.uid(), relation.user(), relation.visible(), metadata, &mut buf)?;
+            format_tags(relation.tags(), &mut buf);
+            buf.push_str(" M");
+            for (i, member) in relation.members().iter().enumerate() {
+                if i > 0 {
+                    buf.push(',');
+                }
+                let (member_type, member_data) = match member {
+                    Member::Node { member } => {
+                        ('n', member)
+                    }
+                    Member::Way { member } => {
+                        ('w', member)
+                    }
+                    Member::Relation { member } => {
+                        ('r', member)
+                    }
+                };
+                buf.push_str(&format!("{}{}@{}", member_type, member_data.id(), escape(member_data.role())));
+            }
+        }
+        Element::Sentinel => {}
+    }
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("plain"), "plain");
+        assert!(matches!(escape("plain"), Cow::Borrowed(_)));
+        assert_eq!(escape("bob & co"), "bob%20%&%20%co");
+        assert_eq!(escape("a=b,c@d%"), "a%3d%b%2c%c%40%d%25%");
+        assert_eq!(escape("Café\n"), "Café%a%");
+    }
+
+    #[test]
+    fn test_format_coordinate() {
+        assert_eq!(format_coordinate(-1699218762), "-169.9218762");
+        assert_eq!(format_coordinate(-190554400), "-19.05544");
+        assert_eq!(format_coordinate(10_000_000), "1");
+        assert_eq!(format_coordinate(-5), "-0.0000005");
+    }
+}
@@ -0,0 +1,309 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use std::vec::IntoIter;
+
+use anyhow::{anyhow, Context};
+use json::JsonValue;
+
+use crate::osm::model::element::Element;
+use crate::osm::pbf::blob_desc::BlobDesc;
+use crate::osm::pbf::file_block::FileBlock;
+use crate::osm::pbf::reader::Reader;
+
+/// Id ranges of the elements in a single data block of a *.osm.pbf file
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockEntry {
+    index: usize,
+    start: u64,
+    length: u64,
+    nodes: Option<(i64, i64)>,
+    ways: Option<(i64, i64)>,
+    relations: Option<(i64, i64)>,
+}
+
+impl BlockEntry {
+    /// Block number in the file, the header block is 0
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Offset of the blob in the file
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Length of the blob in bytes
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// Smallest and largest node id in the block, None if there are no nodes
+    pub fn nodes(&self) -> Option<(i64, i64)> {
+        self.nodes
+    }
+
+    /// Smallest and largest way id in the block, None if there are no ways
+    pub fn ways(&self) -> Option<(i64, i64)> {
+        self.ways
+    }
+
+    /// Smallest and largest relation id in the block, None if there are no relations
+    pub fn relations(&self) -> Option<(i64, i64)> {
+        self.relations
+    }
+
+    fn merge(range: &mut Option<(i64, i64)>, id: i64) {
+        match range {
+            None => {
+                *range = Some((id, id));
+            }
+            Some((min, max)) => {
+                *min = id.min(*min);
+                *max = id.max(*max);
+            }
+        }
+    }
+
+    fn range_to_json(range: &Option<(i64, i64)>) -> JsonValue {
+        match range {
+            None => {
+                JsonValue::Null
+            }
+            Some((min, max)) => {
+                json::array![*min, *max]
+            }
+        }
+    }
+
+    fn range_from_json(value: &JsonValue) -> Result<Option<(i64, i64)>, anyhow::Error> {
+        if value.is_null() {
+            Ok(None)
+        } else {
+            let min = value[0].as_i64().ok_or(anyhow!("Invalid id range: {}", value.dump()))?;
+            let max = value[1].as_i64().ok_or(anyhow!("Invalid id range: {}", value.dump()))?;
+            Ok(Some((min, max)))
+        }
+    }
+
+    fn to_json(&self) -> JsonValue {
+        json::object! {
+            "index": self.index,
+            "start": self.start,
+            "length": self.length,
+            "nodes": Self::range_to_json(&self.nodes),
+            "ways": Self::range_to_json(&self.ways),
+            "relations": Self::range_to_json(&self.relations),
+        }
+    }
+
+    fn from_json(value: &JsonValue) -> Result<BlockEntry, anyhow::Error> {
+        Ok(
+            BlockEntry {
+                index: value["index"].as_usize().ok_or(anyhow!("Missing block index"))?,
+                start: value["start"].as_u64().ok_or(anyhow!("Missing block start"))?,
+                length: value["length"].as_u64().ok_or(anyhow!("Missing block length"))?,
+                nodes: Self::range_from_json(&value["nodes"])?,
+                ways: Self::range_from_json(&value["ways"])?,
+                relations: Self::range_from_json(&value["relations"])?,
+            }
+        )
+    }
+}
+
+/// Index of the id ranges of the data blocks of a *.osm.pbf file
+///
+/// The index allows decoding only the blocks that may contain the objects of interest. It is
+/// built with a pass over the file and can be saved next to it, see [BlockIndex::index_path]. A
+/// saved index is only loaded if the size and the modification time of the *.osm.pbf file did not
+/// change since it was built, a stale index is ignored.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::pbf::block_index::BlockIndex;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let block_index = match BlockIndex::find(&reader)? {
+///         None => {
+///             let block_index = BlockIndex::build(&reader)?;
+///             block_index.save(&BlockIndex::index_path(&input_path))?;
+///             block_index
+///         }
+///         Some(block_index) => {
+///             block_index
+///         }
+///     };
+///     let ways = block_index.elements(|entry| entry.ways().is_some())
+///         .filter(|element| element.is_way())
+///         .count();
+///     println!("ways: {}", ways);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlockIndex {
+    path: PathBuf,
+    file_size: u64,
+    modified: u64,
+    entries: Vec<BlockEntry>,
+}
+
+impl BlockIndex {
+    /// Build the index by decoding all the blocks of the file
+    pub fn build(reader: &Reader) -> Result<BlockIndex, anyhow::Error> {
+        let path = reader.path().clone();
+        let mut entries = Vec::new();
+        for blob_desc in reader.blobs()? {
+            if blob_desc.t() != "OSMData" {
+                continue;
+            }
+            let file_block = FileBlock::from_blob_desc(&blob_desc)?;
+            let mut entry = BlockEntry {
+                index: blob_desc.index(),
+                start: blob_desc.start(),
+                length: blob_desc.length(),
+                nodes: None,
+                ways: None,
+                relations: None,
+            };
+            for element in file_block.elements() {
+                match element {
+                    Element::Node { node } => {
+                        BlockEntry::merge(&mut entry.nodes, node.id());
+                    }
+                    Element::Way { way } => {
+                        BlockEntry::merge(&mut entry.ways, way.id());
+                    }
+                    Element::Relation { relation } => {
+                        BlockEntry::merge(&mut entry.relations, relation.id());
+                    }
+                    Element::Sentinel => {}
+                }
+            }
+            entries.push(entry);
+        }
+        let (file_size, modified) = Self::file_state(&path)?;
+        Ok(
+            BlockIndex {
+                path,
+                file_size,
+                modified,
+                entries,
+            }
+        )
+    }
+
+    /// The default location of the index of a *.osm.pbf file, the file path with .idx appended
+    pub fn index_path(path: &Path) -> PathBuf {
+        let mut index_path = path.as_os_str().to_os_string();
+        index_path.push(".idx");
+        PathBuf::from(index_path)
+    }
+
+    /// Load the index from the default location if it exists and is not stale, see
+    /// [BlockIndex::index_path] and [BlockIndex::load]
+    pub fn find(reader: &Reader) -> Result<Option<BlockIndex>, anyhow::Error> {
+        let index_path = Self::index_path(reader.path());
+        if index_path.exists() {
+            Self::load(reader, &index_path)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Load the index of the file of the reader. Returns None if the index is stale, that is the
+    /// size or the modification time of the file changed since the index was built
+    pub fn load(reader: &Reader, index_path: &Path) -> Result<Option<BlockIndex>, anyhow::Error> {
+        let text = fs::read_to_string(index_path)
+            .with_context(|| anyhow!("path: {}", index_path.display()))?;
+        let value = json::parse(&text)
+            .with_context(|| anyhow!("path: {}", index_path.display()))?;
+        let path = reader.path().clone();
+        let (file_size, modified) = Self::file_state(&path)?;
+        if value["file_size"].as_u64() != Some(file_size) || value["modified"].as_u64() != Some(modified) {
+            return Ok(None);
+        }
+        let entries = value["blocks"].members()
+            .map(BlockEntry::from_json)
+            .collect::<Result<Vec<BlockEntry>, anyhow::Error>>()
+            .with_context(|| anyhow!("path: {}", index_path.display()))?;
+        Ok(
+            Some(
+                BlockIndex {
+                    path,
+                    file_size,
+                    modified,
+                    entries,
+                }
+            )
+        )
+    }
+
+    /// Save the index as JSON
+    pub fn save(&self, index_path: &Path) -> Result<(), anyhow::Error> {
+        let mut blocks = JsonValue::new_array();
+        for entry in &self.entries {
+            blocks.push(entry.to_json())?;
+        }
+        let value = json::object! {
+            "file_size": self.file_size,
+            "modified": self.modified,
+            "blocks": blocks,
+        };
+        fs::write(index_path, value.dump())
+            .with_context(|| anyhow!("path: {}", index_path.display()))
+    }
+
+    /// The size and the modification time in nanoseconds since the epoch
+    fn file_state(path: &Path) -> Result<(u64, u64), anyhow::Error> {
+        let metadata = fs::metadata(path)
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        let modified = metadata.modified()?
+            .duration_since(UNIX_EPOCH)?
+            .as_nanos() as u64;
+        Ok((metadata.len(), modified))
+    }
+
+    /// Data blocks in file order
+    pub fn entries(&self) -> &Vec<BlockEntry> {
+        &self.entries
+    }
+
+    /// Iterate over the elements of the selected blocks in file order
+    pub fn elements(&self, select: impl Fn(&BlockEntry) -> bool) -> BlockElementIterator {
+        let blob_descs: Vec<BlobDesc> = self.entries.iter()
+            .filter(|entry| select(entry))
+            .map(|entry| BlobDesc::new(self.path.clone(), entry.index, entry.start, entry.length, "OSMData".to_string()))
+            .collect();
+        BlockElementIterator {
+            blob_descs: blob_descs.into_iter(),
+            elements: Vec::new().into_iter(),
+        }
+    }
+}
+
+/// Iterate over the elements of the blocks selected with [BlockIndex::elements]
+///
+/// Panics if a block can not be decoded.
+pub struct BlockElementIterator {
+    blob_descs: IntoIter<BlobDesc>,
+    elements: IntoIter<Element>,
+}
+
+impl Iterator for BlockElementIterator {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(element) = self.elements.next() {
+                return Some(element);
+            }
+            let blob_desc = self.blob_descs.next()?;
+            let mut file_block = FileBlock::from_blob_desc(&blob_desc)
+                .unwrap_or_else(|e| panic!("Failed to decode block {} of {}: {}", blob_desc.index(), blob_desc.path().display(), e));
+            self.elements = file_block.take_elements().into_iter();
+        }
+    }
+}
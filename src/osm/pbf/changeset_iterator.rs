@@ -0,0 +1,41 @@
+use std::vec::IntoIter;
+
+use crate::osm::model::changeset::Changeset;
+use crate::osm::pbf::file_block::FileBlock;
+use crate::osm::pbf::file_block_iterator::FileBlockIterator;
+
+/// Iterate over changesets in *.osm.pbf file
+///
+/// Changesets are stored in changeset groups, separate from the element groups. Blocks without
+/// changesets are skipped.
+pub struct ChangesetIterator {
+    file_block_iterator: FileBlockIterator,
+    changeset_iterator: IntoIter<Changeset>,
+}
+
+impl ChangesetIterator {
+    pub(crate) fn new(file_block_iterator: FileBlockIterator) -> ChangesetIterator {
+        ChangesetIterator {
+            file_block_iterator,
+            changeset_iterator: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl Iterator for ChangesetIterator {
+    type Item = Changeset;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(changeset) = self.changeset_iterator.next() {
+                return Some(changeset);
+            }
+            match self.file_block_iterator.next()? {
+                FileBlock::Header { .. } => {}
+                FileBlock::Data { metadata: _, mut data } => {
+                    self.changeset_iterator = data.take_changesets().into_iter();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::osm::model::changeset::Changeset;
+use crate::osm::pbf::string_table_builder::StringTableBuilder;
+use crate::osmpbf;
+use crate::osmpbf::PrimitiveGroup;
+
+const NANODEG: f64 = 1_000_000_000f64;
+
+pub(crate) struct ChangesetsGroupBuilder {
+    changesets: Option<Vec<osmpbf::ChangeSet>>,
+    date_granularity: i32,
+}
+
+impl ChangesetsGroupBuilder {
+    pub(crate) fn new(date_granularity: i32, changeset: &Changeset, string_table_builder: &mut StringTableBuilder) -> ChangesetsGroupBuilder {
+        let mut changesets = Some(Vec::<osmpbf::ChangeSet>::with_capacity(8000));
+        changesets.as_mut().unwrap().push(Self::convert(changeset, date_granularity, string_table_builder));
+
+        ChangesetsGroupBuilder {
+            changesets,
+            date_granularity,
+        }
+    }
+
+    pub(crate) fn add(&mut self, changeset: &Changeset, string_table_builder: &mut StringTableBuilder) {
+        self.changesets.as_mut().unwrap().push(Self::convert(changeset, self.date_granularity, string_table_builder));
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    fn convert(changeset: &Changeset, date_granularity: i32, string_table_builder: &mut StringTableBuilder) -> osmpbf::ChangeSet {
+        let mut c = osmpbf::ChangeSet::default();
+
+        c.id = changeset.id();
+        for tag in changeset.tags() {
+            let key_index = string_table_builder.add(tag.k());
+            let val_index = string_table_builder.add(tag.v());
+            c.keys.push(key_index as u32);
+            c.vals.push(val_index as u32)
+        }
+
+        c.info = Some(osmpbf::Info::default());
+        c.info.as_mut().unwrap().uid = Some(changeset.uid());
+        c.info.as_mut().unwrap().user_sid = Some(string_table_builder.add(changeset.user()) as u32);
+
+        if changeset.created_at() >= 0 {
+            let created_at = changeset.created_at() / date_granularity as i64;
+            c.created_at = Some(created_at);
+            c.closetime_delta = changeset.closed_at().map(|closed_at| closed_at / date_granularity as i64 - created_at);
+        }
+        c.open = Some(changeset.open());
+        c.bbox = changeset.bounding_box().as_ref().map(|bounding_box| osmpbf::HeaderBBox {
+            left: (bounding_box.left() * NANODEG).round() as i64,
+            right: (bounding_box.right() * NANODEG).round() as i64,
+            top: (bounding_box.top() * NANODEG).round() as i64,
+            bottom: (bounding_box.bottom() * NANODEG).round() as i64,
+        });
+        c.num_changes = Some(changeset.num_changes());
+        c.comments_count = Some(changeset.comments_count());
+        c
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub(crate) fn build(&mut self) -> PrimitiveGroup {
+        let mut primitive_group = PrimitiveGroup::default();
+        primitive_group.changesets = self.changesets.replace(Vec::<osmpbf::ChangeSet>::new()).unwrap();
+        primitive_group
+    }
+}
@@ -43,7 +43,10 @@ impl ElementAccumulator {
                         self.add(element);
                     }
                     Element::Relation { .. } => {
-                        panic!("expected Element::Node or Element::Way but got Element::Relation");
+                        self.state = State::Relations;
+                        result = Some(std::mem::replace(&mut self.elements, Vec::with_capacity(self.block_size)));
+                        self.index += 1;
+                        self.add(element);
                     }
                     Element::Sentinel => {}
                 }
@@ -104,4 +107,32 @@ impl ElementAccumulator {
     pub(crate) fn len(&self) -> usize {
         self.elements.len()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::osm::model::coordinate::Coordinate;
+    use crate::osm::model::element::Element;
+    use crate::osm::model::node::Node;
+    use crate::osm::model::relation::Relation;
+    use crate::osm::pbf::element_accumulator::ElementAccumulator;
+
+    #[test]
+    fn test_relation_after_nodes() {
+        let mut accumulator = ElementAccumulator::new();
+        let node = Element::Node {
+            node: Node::new(1, 1, Coordinate::new(0.0, 0.0), 0, 0, 0, String::new(), true, Vec::new()),
+        };
+        let relation = Element::Relation {
+            relation: Relation::new(1, 1, 0, 0, 0, String::new(), true, Vec::new(), Vec::new()),
+        };
+        assert!(accumulator.add(node).is_none());
+        let nodes = accumulator.add(relation).unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert!(matches!(nodes[0], Element::Node { .. }));
+        let relations = accumulator.elements();
+        assert_eq!(relations.len(), 1);
+        assert!(matches!(relations[0], Element::Relation { .. }));
+        assert_eq!(accumulator.index(), 2);
+    }
+}
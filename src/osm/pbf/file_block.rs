@@ -9,6 +9,7 @@ use prost::Message;
 
 use crate::osmpbf;
 use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::changeset::Changeset;
 use crate::osm::model::element::Element;
 use crate::osm::pbf::blob_desc::BlobDesc;
 use crate::osm::pbf::compression_type::CompressionType;
@@ -276,6 +277,10 @@ impl FileBlock {
         self.as_osm_data().unwrap().elements()
     }
 
+    pub(crate) fn changesets(&self) -> &Vec<Changeset> {
+        self.as_osm_data().unwrap().changesets()
+    }
+
     pub(crate) fn take_elements(&mut self) -> Vec<Element> {
         match self {
             FileBlock::Header { .. } => {
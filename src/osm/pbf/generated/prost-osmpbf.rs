@@ -185,12 +185,40 @@ pub struct DenseInfo {
     #[prost(bool, repeated, tag = "6")]
     pub visible: ::prost::alloc::vec::Vec<bool>,
 }
-/// This is kept for backwards compatibility but not used anywhere.
+/// Changeset metadata. Only the id is required, files with changesets that have just an id remain
+/// readable. The other fields are ignored by readers that do not know them.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ChangeSet {
     #[prost(int64, required, tag = "1")]
     pub id: i64,
+    /// Parallel arrays.
+    ///
+    /// String IDs.
+    #[prost(uint32, repeated, tag = "2")]
+    pub keys: ::prost::alloc::vec::Vec<u32>,
+    /// String IDs.
+    #[prost(uint32, repeated, tag = "3")]
+    pub vals: ::prost::alloc::vec::Vec<u32>,
+    /// The uid and user_sid of the author
+    #[prost(message, optional, tag = "4")]
+    pub info: ::core::option::Option<Info>,
+    /// In date_granularity units
+    #[prost(int64, optional, tag = "8")]
+    pub created_at: ::core::option::Option<i64>,
+    /// Closed at - created at, in date_granularity units
+    #[prost(int64, optional, tag = "9")]
+    pub closetime_delta: ::core::option::Option<i64>,
+    #[prost(bool, optional, tag = "10")]
+    pub open: ::core::option::Option<bool>,
+    #[prost(message, optional, tag = "11")]
+    pub bbox: ::core::option::Option<HeaderBBox>,
+    /// osm-io extension, not part of the upstream format. The field numbers are kept well clear of
+    /// the upstream ones, so that future upstream fields do not collide with them.
+    #[prost(int32, optional, tag = "1001")]
+    pub num_changes: ::core::option::Option<i32>,
+    #[prost(int32, optional, tag = "1002")]
+    pub comments_count: ::core::option::Option<i32>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
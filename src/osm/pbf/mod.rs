@@ -2,17 +2,20 @@ pub mod reader;
 pub mod writer;
 pub mod parallel_writer;
 pub mod element_iterator;
+pub mod changeset_iterator;
 pub mod file_block_iterator;
 pub mod file_block;
 pub mod file_info;
 pub mod compression_type;
 pub mod thread_local_accumulator;
 pub mod bounding_box_calculator;
+pub mod block_index;
 
 pub(crate) mod dense_group_builder;
 pub(crate) mod string_table_builder;
 pub(crate) mod ways_group_builder;
 pub(crate) mod relations_group_builder;
+pub(crate) mod changesets_group_builder;
 pub(crate) mod parallel_element_iteration_command;
 pub(crate) mod element_accumulator;
 pub(crate) mod file_block_metadata;
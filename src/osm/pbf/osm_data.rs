@@ -6,16 +6,22 @@ use prost::Message;
 
 use crate::{osm, osmpbf};
 use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::changeset::Changeset;
 use crate::osm::model::element::Element;
+use crate::osm::model::tag::Tag;
+use crate::osm::pbf::changesets_group_builder::ChangesetsGroupBuilder;
 use crate::osm::pbf::dense_group_builder::DenseGroupBuilder;
 use crate::osm::pbf::relations_group_builder::RelationsGroupBuilder;
 use crate::osm::pbf::string_table_builder::StringTableBuilder;
 use crate::osm::pbf::ways_group_builder::WaysGroupBuilder;
 use crate::osmpbf::{PrimitiveBlock, PrimitiveGroup};
 
+const NANODEG: f64 = 1_000_000_000f64;
+
 #[derive(Debug, Default)]
 pub struct OsmData {
     elements: Vec<Element>,
+    changesets: Vec<Changeset>,
     bounding_box: Option<BoundingBox>,
 }
 
@@ -34,20 +40,25 @@ impl OsmData {
         let lat_offset = primitive_block.lat_offset();
         let lon_offset = primitive_block.lon_offset();
         let mut elements = Vec::<Element>::with_capacity(8000);
+        let mut changesets = Vec::<Changeset>::new();
         for g in &primitive_block.primitivegroup {
             Self::read_dense(&g.dense, &string_table, granularity, date_granularity, lat_offset, lon_offset, &mut elements);
             Self::read_nodes(&g.nodes, &string_table, granularity, date_granularity, lat_offset, lon_offset, &mut elements);
-            Self::read_ways(&g.ways, &string_table, granularity, date_granularity, &mut elements);
+            Self::read_ways(&g.ways, &string_table, granularity, date_granularity, lat_offset, lon_offset, &mut elements);
             Self::read_relations(&g.relations, &string_table, granularity, date_granularity, &mut elements);
-            Self::read_changesets(&g.changesets, &string_table, granularity, date_granularity, lat_offset, lon_offset, &mut elements);
+            Self::read_changesets(&g.changesets, &string_table, date_granularity, &mut changesets);
         }
         Ok(
-            OsmData { elements, bounding_box: None }
+            OsmData { elements, changesets, bounding_box: None }
         )
     }
 
     pub fn from_elements(elements: Vec<Element>, bounding_box: Option<BoundingBox>) -> OsmData {
-        OsmData { elements, bounding_box }
+        OsmData { elements, changesets: Vec::new(), bounding_box }
+    }
+
+    pub fn from_changesets(changesets: Vec<Changeset>) -> OsmData {
+        OsmData { elements: Vec::new(), changesets, bounding_box: None }
     }
 
     pub fn compute_bounding_box(&self) -> Option<BoundingBox> {
@@ -221,7 +232,7 @@ impl OsmData {
     }
 
 
-    fn read_ways(way_group: &Vec<osmpbf::Way>, string_table: &[String], _granularity: i64, date_granularity: i32, elements: &mut Vec<Element>) {
+    fn read_ways(way_group: &Vec<osmpbf::Way>, string_table: &[String], granularity: i64, date_granularity: i32, lat_offset: i64, lon_offset: i64, elements: &mut Vec<Element>) {
         for way in way_group {
             let id = way.id;
             let (timestamp, changeset, uid, user, visible, version) =
@@ -241,7 +252,24 @@ impl OsmData {
                 tags.push(osm::model::tag::Tag::new(k, v));
             }
 
-            let way = osm::model::way::Way::new(
+            // LocationsOnWays feature, the lat and lon arrays are either empty or parallel to refs
+            let mut coordinates = Vec::<osm::model::coordinate::Coordinate>::new();
+            if !way.lat.is_empty() && way.lat.len() == refs.len() && way.lon.len() == refs.len() {
+                let mut last_lat = 0_i64;
+                let mut last_lon = 0_i64;
+                for i in 0..refs.len() {
+                    last_lat += way.lat[i];
+                    last_lon += way.lon[i];
+                    coordinates.push(
+                        osm::model::coordinate::Coordinate::new(
+                            (lat_offset + (granularity * last_lat)) as f64 / 1000000000f64,
+                            (lon_offset + (granularity * last_lon)) as f64 / 1000000000f64,
+                        )
+                    );
+                }
+            }
+
+            let mut way = osm::model::way::Way::new(
                 id,
                 version,
                 timestamp,
@@ -252,6 +280,7 @@ impl OsmData {
                 refs,
                 tags,
             );
+            way.with_coordinates(coordinates);
             elements.push(Element::Way { way });
         }
     }
@@ -307,9 +336,45 @@ impl OsmData {
         }
     }
 
-    fn read_changesets(changeset_group: &[osmpbf::ChangeSet], _string_table: &[String], _granularity: i64, _date_granularity: i32, _lat_offset: i64, _lon_offset: i64, _elements: &mut [Element]) {
-        if let Some(_changeset) = changeset_group.iter().next() {
-            panic!("According to documentation changesets are not used");
+    fn read_changesets(changeset_group: &[osmpbf::ChangeSet], string_table: &[String], date_granularity: i32, changesets: &mut Vec<Changeset>) {
+        for changeset in changeset_group {
+            let tags: Vec<Tag> = changeset.keys.iter().zip(changeset.vals.iter())
+                .map(|(k, v)| Tag::new(string_table[*k as usize].clone(), string_table[*v as usize].clone()))
+                .collect();
+            let (uid, user) = match &changeset.info {
+                None => {
+                    (-1, String::default())
+                }
+                Some(info) => {
+                    (info.uid.unwrap_or(-1), string_table[info.user_sid.unwrap_or(0) as usize].clone())
+                }
+            };
+            // the close time is relative to the creation time, so it is lost with it
+            let created_at = changeset.created_at.map_or(-1, |created_at| created_at * date_granularity as i64);
+            let closed_at = changeset.created_at.zip(changeset.closetime_delta)
+                .map(|(created_at, closetime_delta)| (created_at + closetime_delta) * date_granularity as i64);
+            let bounding_box = changeset.bbox.as_ref().map(|bbox| {
+                BoundingBox::new(
+                    bbox.left as f64 / NANODEG,
+                    bbox.bottom as f64 / NANODEG,
+                    bbox.right as f64 / NANODEG,
+                    bbox.top as f64 / NANODEG,
+                )
+            });
+            changesets.push(
+                Changeset::new(
+                    changeset.id,
+                    created_at,
+                    closed_at,
+                    changeset.open.unwrap_or(false),
+                    uid,
+                    user,
+                    bounding_box,
+                    changeset.num_changes.unwrap_or(0),
+                    changeset.comments_count.unwrap_or(0),
+                    tags,
+                )
+            );
         }
     }
 
@@ -324,6 +389,7 @@ impl OsmData {
         let mut dense_group_builder = None;
         let mut ways_group_builder = None;
         let mut relations_group_builder = None;
+        let mut changesets_group_builder = None;
         for element in &self.elements {
             match element {
                 Element::Node { node } => {
@@ -338,7 +404,7 @@ impl OsmData {
                 Element::Way { way } => {
                     if ways_group_builder.is_none() {
                         ways_group_builder = Some(
-                            WaysGroupBuilder::new(date_granularity, way, &mut string_table_builder)
+                            WaysGroupBuilder::new(granularity, date_granularity, lat_offset, lon_offset, way, &mut string_table_builder)
                         );
                     } else {
                         ways_group_builder.as_mut().unwrap().add(way, &mut string_table_builder)
@@ -356,6 +422,15 @@ impl OsmData {
                 Element::Sentinel => {}
             }
         }
+        for changeset in &self.changesets {
+            if changesets_group_builder.is_none() {
+                changesets_group_builder = Some(
+                    ChangesetsGroupBuilder::new(date_granularity, changeset, &mut string_table_builder)
+                );
+            } else {
+                changesets_group_builder.as_mut().unwrap().add(changeset, &mut string_table_builder)
+            }
+        }
         let mut primitivegroup = PrimitiveGroup::default();
         if dense_group_builder.is_some() {
             primitivegroup = dense_group_builder.unwrap().build();
@@ -363,6 +438,8 @@ impl OsmData {
             primitivegroup = ways_group_builder.unwrap().build();
         } else if relations_group_builder.is_some() {
             primitivegroup = relations_group_builder.unwrap().build();
+        } else if changesets_group_builder.is_some() {
+            primitivegroup = changesets_group_builder.unwrap().build();
         }
 
         let stringtable = string_table_builder.build();
@@ -388,4 +465,12 @@ impl OsmData {
     pub fn take_elements(&mut self) -> Vec<Element> {
         std::mem::take(&mut self.elements)
     }
+
+    pub fn changesets(&self) -> &Vec<Changeset> {
+        &self.changesets
+    }
+
+    pub fn take_changesets(&mut self) -> Vec<Changeset> {
+        std::mem::take(&mut self.changesets)
+    }
 }
@@ -9,6 +9,7 @@ use command_executor::thread_pool_builder::ThreadPoolBuilder;
 
 use crate::osm::model::element::Element;
 use crate::osm::pbf::blob_iterator::BlobIterator;
+use crate::osm::pbf::changeset_iterator::ChangesetIterator;
 use crate::osm::pbf::element_iterator::ElementIterator;
 use crate::osm::pbf::file_block_iterator::FileBlockIterator;
 use crate::osm::pbf::file_info::FileInfo;
@@ -41,6 +42,7 @@ impl Reader {
             "DenseNodes".to_string(),
             "HistoricalInformation".to_string(),
             "Sort.Type_then_ID".to_string(),
+            "LocationsOnWays".to_string(),
         ];
 
         let mut reader = Reader {
@@ -133,6 +135,26 @@ impl Reader {
         }
     }
 
+    /// Iterator over the changesets in changeset groups
+    ///
+    /// Changesets are read independently of the elements, iterating both reads the file twice.
+    /// Example:
+    /// ```
+    /// use std::path::PathBuf;
+    /// use osm_io::osm::pbf;
+    /// fn example() -> Result<(), anyhow::Error> {
+    ///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    ///     let reader = pbf::reader::Reader::new(&input_path)?;
+    ///     for changeset in reader.changesets()? {
+    ///         println!("{} by {}", changeset.id(), changeset.user());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn changesets(&self) -> Result<ChangesetIterator, anyhow::Error> {
+        Ok(ChangesetIterator::new(self.blocks()?))
+    }
+
     /// Parallel iteration over elements in a *.osm.pbf file
     ///
     /// Note that because of the parallel access the order of elements enforced by *.osm.pbf format
@@ -234,6 +256,11 @@ impl Reader {
         &self.info
     }
 
+    /// Input path
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
     pub fn count_objects(&self) -> Result<(i64, i64, i64), anyhow::Error> {
         let nodes = Arc::new(AtomicUsize::new(0));
         let ways = Arc::new(AtomicUsize::new(0));
@@ -5,26 +5,40 @@ use crate::osmpbf::PrimitiveGroup;
 
 pub(crate) struct WaysGroupBuilder {
     ways: Option<Vec<osmpbf::Way>>,
+    granularity: i32,
     date_granularity: i32,
+    lat_offset: i64,
+    lon_offset: i64,
 }
 
 impl WaysGroupBuilder {
-    pub(crate) fn new(date_granularity: i32, way: &Way, string_table_builder: &mut StringTableBuilder) -> WaysGroupBuilder {
-        let mut ways = Some(Vec::<osmpbf::Way>::with_capacity(8000));
-        ways.as_mut().unwrap().push(Self::convert(way, date_granularity, string_table_builder));
-
-        WaysGroupBuilder {
-            ways,
+    pub(crate) fn new(
+        granularity: i32,
+        date_granularity: i32,
+        lat_offset: i64,
+        lon_offset: i64,
+        way: &Way,
+        string_table_builder: &mut StringTableBuilder,
+    ) -> WaysGroupBuilder {
+        let mut builder = WaysGroupBuilder {
+            ways: Some(Vec::<osmpbf::Way>::with_capacity(8000)),
+            granularity,
             date_granularity,
-        }
+            lat_offset,
+            lon_offset,
+        };
+        builder.add(way, string_table_builder);
+        builder
     }
 
     pub(crate) fn add(&mut self, way: &Way, string_table_builder: &mut StringTableBuilder) {
-        self.ways.as_mut().unwrap().push(Self::convert(way, self.date_granularity, string_table_builder));
+        let w = self.convert(way, string_table_builder);
+        self.ways.as_mut().unwrap().push(w);
     }
 
     #[allow(clippy::field_reassign_with_default)]
-    fn convert(way: &Way, date_granularity: i32, string_table_builder: &mut StringTableBuilder) -> osmpbf::Way {
+    fn convert(&self, way: &Way, string_table_builder: &mut StringTableBuilder) -> osmpbf::Way {
+        let date_granularity = self.date_granularity;
         let mut w = osmpbf::Way::default();
         w.id = way.id();
         let mut last_ref = 0;
@@ -33,6 +47,20 @@ impl WaysGroupBuilder {
             last_ref = *r;
         }
 
+        // LocationsOnWays feature, written only when there is a location for every ref
+        if !way.coordinates().is_empty() && way.coordinates().len() == way.refs().len() {
+            let mut last_lat = 0_i64;
+            let mut last_lon = 0_i64;
+            for coordinate in way.coordinates() {
+                let lat = (coordinate.lat() * 1E9f64 / self.granularity as f64 - self.lat_offset as f64).round() as i64;
+                let lon = (coordinate.lon() * 1E9f64 / self.granularity as f64 - self.lon_offset as f64).round() as i64;
+                w.lat.push(lat - last_lat);
+                w.lon.push(lon - last_lon);
+                last_lat = lat;
+                last_lon = lon;
+            }
+        }
+
         for tag in way.tags() {
             let key_index = string_table_builder.add(tag.k());
             let val_index = string_table_builder.add(tag.v());
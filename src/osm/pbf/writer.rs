@@ -5,6 +5,7 @@ use std::path::PathBuf;
 use anyhow::{anyhow, Context};
 
 use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::changeset::Changeset;
 use crate::osm::model::element::Element;
 use crate::osm::pbf::compression_type::CompressionType;
 use crate::osm::pbf::element_accumulator::ElementAccumulator;
@@ -63,6 +64,7 @@ pub struct Writer {
     compression_type: CompressionType,
     file: File,
     element_accumulator: ElementAccumulator,
+    changesets: Vec<Changeset>,
 }
 
 impl Writer {
@@ -81,6 +83,7 @@ impl Writer {
                 compression_type,
                 file,
                 element_accumulator: ElementAccumulator::new(),
+                changesets: Vec::new(),
             }
         )
     }
@@ -188,14 +191,41 @@ impl Writer {
         Ok(())
     }
 
+    /// Write changeset
+    ///
+    /// Changesets are written in changeset groups, in blocks of 8000 or less changesets separate
+    /// from the element blocks
+    pub fn write_changeset(&mut self, changeset: Changeset) -> Result<(), anyhow::Error> {
+        self.changesets.push(changeset);
+        if self.changesets.len() == 8000 {
+            self.flush_changesets()?;
+        }
+        Ok(())
+    }
+
+    fn flush_changesets(&mut self) -> Result<(), anyhow::Error> {
+        let data = FileBlock::Data {
+            metadata: FileBlockMetadata::new(
+                "OSMData".to_string(),
+                self.element_accumulator.index(),
+            ),
+            data: OsmData::from_changesets(std::mem::take(&mut self.changesets)),
+        };
+        self.write_file_block(data)
+    }
+
     /// Flush the internal buffers.
     ///
-    /// Must be called in the end to write any elements accumulated in internal buffers
+    /// Must be called in the end to write any elements and changesets accumulated in internal
+    /// buffers
     pub fn close(&mut self) -> Result<(), anyhow::Error> {
         let elements = self.element_accumulator.elements();
         if !elements.is_empty() {
             self.write_elements(elements)?;
         }
+        if !self.changesets.is_empty() {
+            self.flush_changesets()?;
+        }
         Ok(())
     }
 
@@ -0,0 +1,185 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use transient_btree_index::{BtreeConfig, BtreeIndex};
+
+use crate::osm::filter::get_id::ObjectType;
+
+/// Mapping of the ids of a single object type, backed by a disk based index
+pub struct TypeMapping {
+    start: i64,
+    next: i64,
+    index: BtreeIndex<i64, i64>,
+}
+
+impl TypeMapping {
+    fn new(start: i64) -> Result<TypeMapping, anyhow::Error> {
+        let config = BtreeConfig::default()
+            .fixed_key_size(8)
+            .fixed_value_size(8);
+        Ok(
+            TypeMapping {
+                start,
+                next: start,
+                index: BtreeIndex::<i64, i64>::with_capacity(config, 0)?,
+            }
+        )
+    }
+
+    /// The first new id
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    /// The next new id to be assigned
+    pub fn next(&self) -> i64 {
+        self.next
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// The new id of old_id, None if it was not assigned yet
+    pub fn get(&self, old_id: i64) -> Result<Option<i64>, anyhow::Error> {
+        Ok(self.index.get(&old_id)?)
+    }
+
+    /// The new id of old_id, assigns the next new id if it was not assigned yet
+    pub fn map(&mut self, old_id: i64) -> Result<i64, anyhow::Error> {
+        match self.index.get(&old_id)? {
+            Some(new_id) => {
+                Ok(new_id)
+            }
+            None => {
+                let new_id = self.next;
+                self.index.insert(old_id, new_id)?;
+                self.next += 1;
+                Ok(new_id)
+            }
+        }
+    }
+
+    /// Write (old id, new id) pairs ordered by old id, as little endian i64
+    fn save(&self, path: &Path) -> Result<(), anyhow::Error> {
+        let file = File::create(path)
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        for entry in self.index.range(..)? {
+            let (old_id, new_id) = entry?;
+            writer.write_all(&old_id.to_le_bytes())?;
+            writer.write_all(&new_id.to_le_bytes())?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn load(&mut self, path: &Path) -> Result<(), anyhow::Error> {
+        let file = File::open(path)
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+        let mut buf = [0_u8; 16];
+        loop {
+            match reader.read_exact(&mut buf) {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+                    break;
+                }
+                Err(e) => {
+                    return Err(anyhow!(e).context(format!("path: {}", path.display())));
+                }
+            }
+            let old_id = i64::from_le_bytes(buf[0..8].try_into()?);
+            let new_id = i64::from_le_bytes(buf[8..16].try_into()?);
+            self.index.insert(old_id, new_id)?;
+            self.next = self.next.max(new_id + 1);
+        }
+        Ok(())
+    }
+}
+
+/// Mapping of old to new ids for nodes, ways and relations
+///
+/// New ids are assigned in sequence from a start id for each type, 1 by default. The mapping can
+/// be saved to a directory, as nodes.map, ways.map and relations.map, and loaded later to renumber
+/// updates of the same data consistently. Ids not in a loaded mapping are numbered after the
+/// largest new id of the mapping.
+pub struct IdMapping {
+    nodes: TypeMapping,
+    ways: TypeMapping,
+    relations: TypeMapping,
+}
+
+impl IdMapping {
+    pub fn new(node_start: i64, way_start: i64, relation_start: i64) -> Result<IdMapping, anyhow::Error> {
+        Ok(
+            IdMapping {
+                nodes: TypeMapping::new(node_start)?,
+                ways: TypeMapping::new(way_start)?,
+                relations: TypeMapping::new(relation_start)?,
+            }
+        )
+    }
+
+    /// Load a mapping saved with [IdMapping::save]. Missing files are treated as empty mappings
+    pub fn load(path: &Path, node_start: i64, way_start: i64, relation_start: i64) -> Result<IdMapping, anyhow::Error> {
+        let mut mapping = Self::new(node_start, way_start, relation_start)?;
+        for object_type in [ObjectType::Node, ObjectType::Way, ObjectType::Relation] {
+            let file_path = path.join(Self::file_name(object_type));
+            if file_path.exists() {
+                mapping.mapping_mut(object_type).load(&file_path)?;
+            }
+        }
+        Ok(mapping)
+    }
+
+    /// Save the mapping to a directory, created if missing
+    pub fn save(&self, path: &Path) -> Result<(), anyhow::Error> {
+        std::fs::create_dir_all(path)
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        for object_type in [ObjectType::Node, ObjectType::Way, ObjectType::Relation] {
+            self.mapping(object_type).save(&path.join(Self::file_name(object_type)))?;
+        }
+        Ok(())
+    }
+
+    fn file_name(object_type: ObjectType) -> &'static str {
+        match object_type {
+            ObjectType::Node => "nodes.map",
+            ObjectType::Way => "ways.map",
+            ObjectType::Relation => "relations.map",
+        }
+    }
+
+    pub fn mapping(&self, object_type: ObjectType) -> &TypeMapping {
+        match object_type {
+            ObjectType::Node => &self.nodes,
+            ObjectType::Way => &self.ways,
+            ObjectType::Relation => &self.relations,
+        }
+    }
+
+    pub fn mapping_mut(&mut self, object_type: ObjectType) -> &mut TypeMapping {
+        match object_type {
+            ObjectType::Node => &mut self.nodes,
+            ObjectType::Way => &mut self.ways,
+            ObjectType::Relation => &mut self.relations,
+        }
+    }
+
+    /// True if no id of any type is mapped yet
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.ways.is_empty() && self.relations.is_empty()
+    }
+
+    /// The new id of an object, assigned if it was not assigned yet
+    pub fn map(&mut self, object_type: ObjectType, old_id: i64) -> Result<i64, anyhow::Error> {
+        self.mapping_mut(object_type).map(old_id)
+    }
+}
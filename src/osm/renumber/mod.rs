@@ -0,0 +1,2 @@
+pub mod id_mapping;
+pub mod renumberer;
@@ -0,0 +1,153 @@
+use std::path::PathBuf;
+
+use crate::osm::filter::get_id::{ObjectType, TypedId};
+use crate::osm::model::element::Element;
+use crate::osm::model::node::Node;
+use crate::osm::model::relation::{Member, MemberData, Relation};
+use crate::osm::model::way::Way;
+use crate::osm::pbf;
+use crate::osm::pbf::compression_type::CompressionType;
+use crate::osm::renumber::id_mapping::IdMapping;
+
+/// Rewrite the ids of nodes, ways and relations to dense sequences
+///
+/// The references of ways to nodes and of relations to their members are rewritten to match. New
+/// ids are assigned in input order, so sorted input results in sorted output when starting from an
+/// empty mapping. With a loaded mapping the objects it already contains keep their earlier ids,
+/// so the output of [Renumber::run] is not marked as sorted. Referenced objects
+/// missing in the input get new ids after all the objects of the input. All the versions of an
+/// object in a history file get the same new id.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::pbf::compression_type::CompressionType;
+/// use osm_io::osm::renumber::id_mapping::IdMapping;
+/// use osm_io::osm::renumber::renumberer::Renumber;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109-renumbered.osm.pbf");
+///     let mapping_path = PathBuf::from("./target/results/niue-230109-mapping");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let mut renumber = Renumber::new(IdMapping::load(&mapping_path, 1, 1, 1)?);
+///     renumber.run(&reader, output_path, CompressionType::Zlib)?;
+///     renumber.mapping().save(&mapping_path)?;
+///     Ok(())
+/// }
+/// ```
+pub struct Renumber {
+    mapping: IdMapping,
+}
+
+impl Renumber {
+    pub fn new(mapping: IdMapping) -> Renumber {
+        Renumber {
+            mapping,
+        }
+    }
+
+    pub fn mapping(&self) -> &IdMapping {
+        &self.mapping
+    }
+
+    pub fn into_mapping(self) -> IdMapping {
+        self.mapping
+    }
+
+    /// Assign a new id to the element, without rewriting it
+    pub fn assign(&mut self, element: &Element) -> Result<(), anyhow::Error> {
+        if let Some(typed_id) = TypedId::of(element) {
+            self.mapping.map(typed_id.object_type(), typed_id.id())?;
+        }
+        Ok(())
+    }
+
+    /// Rewrite the id and the references of the element
+    pub fn renumber(&mut self, element: Element) -> Result<Element, anyhow::Error> {
+        match element {
+            Element::Node { mut node } => {
+                let id = self.mapping.map(ObjectType::Node, node.id())?;
+                let tags = node.take_tags();
+                let user = node.take_user();
+                Ok(
+                    Element::Node {
+                        node: Node::new(id, node.version(), node.coordinate().clone(), node.timestamp(), node.changeset(), node.uid(), user, node.visible(), tags),
+                    }
+                )
+            }
+            Element::Way { mut way } => {
+                let id = self.mapping.map(ObjectType::Way, way.id())?;
+                let refs = way.refs().iter()
+                    .map(|node_id| self.mapping.map(ObjectType::Node, *node_id))
+                    .collect::<Result<Vec<i64>, anyhow::Error>>()?;
+                let tags = way.take_tags();
+                let user = way.take_user();
+                let coordinates = way.take_coordinates();
+                let mut renumbered = Way::new(id, way.version(), way.timestamp(), way.changeset(), way.uid(), user, way.visible(), refs, tags);
+                renumbered.with_coordinates(coordinates);
+                Ok(
+                    Element::Way {
+                        way: renumbered,
+                    }
+                )
+            }
+            Element::Relation { mut relation } => {
+                let id = self.mapping.map(ObjectType::Relation, relation.id())?;
+                let mut members = Vec::with_capacity(relation.members().len());
+                for member in relation.members() {
+                    members.push(
+                        match member {
+                            Member::Node { member } => {
+                                Member::Node { member: MemberData::new(self.mapping.map(ObjectType::Node, member.id())?, member.role().clone()) }
+                            }
+                            Member::Way { member } => {
+                                Member::Way { member: MemberData::new(self.mapping.map(ObjectType::Way, member.id())?, member.role().clone()) }
+                            }
+                            Member::Relation { member } => {
+                                Member::Relation { member: MemberData::new(self.mapping.map(ObjectType::Relation, member.id())?, member.role().clone()) }
+                            }
+                        }
+                    );
+                }
+                let tags = relation.take_tags();
+                let user = relation.take_user();
+                Ok(
+                    Element::Relation {
+                        relation: Relation::new(id, relation.version(), relation.timestamp(), relation.changeset(), relation.uid(), user, relation.visible(), members, tags),
+                    }
+                )
+            }
+            Element::Sentinel => {
+                Ok(Element::Sentinel)
+            }
+        }
+    }
+
+    /// Renumber the input in two passes, the first assigns the new ids in input order, the second
+    /// writes. Returns the number of written elements
+    pub fn run(&mut self, reader: &pbf::reader::Reader, output_path: PathBuf, compression_type: CompressionType) -> Result<u64, anyhow::Error> {
+        let mut info = reader.info().clone();
+        if !self.mapping.is_empty() {
+            let features: Vec<String> = info.optional_features().iter()
+                .filter(|feature| feature.as_str() != "Sort.Type_then_ID")
+                .cloned()
+                .collect();
+            info.with_optional_features(&features);
+        }
+        for element in reader.elements()? {
+            self.assign(&element)?;
+        }
+        let mut writer = pbf::writer::Writer::from_file_info(output_path, info, compression_type)?;
+        writer.write_header()?;
+        let mut count = 0;
+        for element in reader.elements()? {
+            if let Element::Sentinel = element {
+                continue;
+            }
+            writer.write_element(self.renumber(element)?)?;
+            count += 1;
+        }
+        writer.close()?;
+        Ok(count)
+    }
+}
@@ -0,0 +1,163 @@
+use crate::osm::filter::get_id::{ObjectType, TypedId};
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::changeset::Changeset;
+use crate::osm::model::element::Element;
+use crate::osm::stats::file_stats::{MetadataPresence, TypeStats};
+
+/// Statistics of a single data block, or of consecutive blocks merged in file order
+#[derive(Debug, Clone)]
+pub(crate) struct BlockStats {
+    pub(crate) index: usize,
+    pub(crate) nodes: TypeStats,
+    pub(crate) ways: TypeStats,
+    pub(crate) relations: TypeStats,
+    pub(crate) changesets: TypeStats,
+    pub(crate) bounding_box: Option<BoundingBox>,
+    pub(crate) first_timestamp: Option<i64>,
+    pub(crate) last_timestamp: Option<i64>,
+    pub(crate) first: Option<(TypedId, i32)>,
+    pub(crate) last: Option<(TypedId, i32)>,
+    pub(crate) objects_ordered: bool,
+    pub(crate) multiple_versions: bool,
+    pub(crate) all_objects: MetadataPresence,
+    pub(crate) some_objects: MetadataPresence,
+}
+
+impl BlockStats {
+    pub(crate) fn new(index: usize) -> BlockStats {
+        BlockStats {
+            index,
+            nodes: TypeStats::default(),
+            ways: TypeStats::default(),
+            relations: TypeStats::default(),
+            changesets: TypeStats::default(),
+            bounding_box: None,
+            first_timestamp: None,
+            last_timestamp: None,
+            first: None,
+            last: None,
+            objects_ordered: true,
+            multiple_versions: false,
+            all_objects: MetadataPresence::all(true),
+            some_objects: MetadataPresence::all(false),
+        }
+    }
+
+    pub(crate) fn add(&mut self, element: &Element) {
+        let (version, timestamp, changeset, uid, user) = match element {
+            Element::Node { node } => {
+                self.nodes.add(node.id());
+                if node.visible() {
+                    match &mut self.bounding_box {
+                        None => {
+                            self.bounding_box = Some(BoundingBox::from_point(node.coordinate()));
+                        }
+                        Some(bounding_box) => {
+                            bounding_box.merge_point(node.coordinate());
+                        }
+                    }
+                }
+                (node.version(), node.timestamp(), node.changeset(), node.uid(), node.user())
+            }
+            Element::Way { way } => {
+                self.ways.add(way.id());
+                (way.version(), way.timestamp(), way.changeset(), way.uid(), way.user())
+            }
+            Element::Relation { relation } => {
+                self.relations.add(relation.id());
+                (relation.version(), relation.timestamp(), relation.changeset(), relation.uid(), relation.user())
+            }
+            Element::Sentinel => {
+                return;
+            }
+        };
+
+        if timestamp > 0 {
+            self.first_timestamp = Some(self.first_timestamp.map_or(timestamp, |first| first.min(timestamp)));
+            self.last_timestamp = Some(self.last_timestamp.map_or(timestamp, |last| last.max(timestamp)));
+        }
+
+        let metadata = MetadataPresence {
+            version: version > 0,
+            timestamp: timestamp > 0,
+            changeset: changeset > 0,
+            user: !user.is_empty(),
+            uid: uid > 0,
+        };
+        self.all_objects.and(&metadata);
+        self.some_objects.or(&metadata);
+
+        // an element is always one of the object types here
+        let current = (TypedId::of(element).unwrap(), version);
+        match self.last {
+            None => {
+                self.first = Some(current);
+            }
+            Some(last) => {
+                self.follow(last, current);
+            }
+        }
+        self.last = Some(current);
+    }
+
+    /// Check the order of an object that follows another
+    fn follow(&mut self, previous: (TypedId, i32), current: (TypedId, i32)) {
+        if current <= previous {
+            self.objects_ordered = false;
+        }
+        if current.0 == previous.0 {
+            self.multiple_versions = true;
+        }
+    }
+
+    pub(crate) fn add_changeset(&mut self, changeset: &Changeset) {
+        self.changesets.add(changeset.id());
+    }
+
+    /// Merge the statistics of the block that follows this one in the file
+    pub(crate) fn merge(&mut self, next: &BlockStats) {
+        self.nodes.merge(&next.nodes);
+        self.ways.merge(&next.ways);
+        self.relations.merge(&next.relations);
+        self.changesets.merge(&next.changesets);
+        if let Some(next_bounding_box) = &next.bounding_box {
+            match &mut self.bounding_box {
+                None => {
+                    self.bounding_box = Some(next_bounding_box.clone());
+                }
+                Some(bounding_box) => {
+                    bounding_box.merge_bounding_box(next_bounding_box);
+                }
+            }
+        }
+        self.first_timestamp = match (self.first_timestamp, next.first_timestamp) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.last_timestamp = match (self.last_timestamp, next.last_timestamp) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        self.objects_ordered &= next.objects_ordered;
+        self.multiple_versions |= next.multiple_versions;
+        if let (Some(last), Some(first)) = (self.last, next.first) {
+            self.follow(last, first);
+        }
+        if self.first.is_none() {
+            self.first = next.first;
+        }
+        if next.last.is_some() {
+            self.last = next.last;
+        }
+        self.all_objects.and(&next.all_objects);
+        self.some_objects.or(&next.some_objects);
+    }
+
+    pub(crate) fn type_stats(&self, object_type: ObjectType) -> &TypeStats {
+        match object_type {
+            ObjectType::Node => &self.nodes,
+            ObjectType::Way => &self.ways,
+            ObjectType::Relation => &self.relations,
+        }
+    }
+}
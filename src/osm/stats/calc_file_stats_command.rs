@@ -0,0 +1,41 @@
+use std::sync::{Arc, Mutex};
+
+use command_executor::command::Command;
+
+use crate::osm::pbf::blob_desc::BlobDesc;
+use crate::osm::pbf::file_block::FileBlock;
+use crate::osm::stats::block_stats::BlockStats;
+
+pub(crate) struct CalcFileStatsCommand {
+    blob: BlobDesc,
+    result: Arc<Mutex<Vec<BlockStats>>>,
+}
+
+impl CalcFileStatsCommand {
+    pub(crate) fn new(
+        blob: BlobDesc,
+        result: Arc<Mutex<Vec<BlockStats>>>,
+    ) -> CalcFileStatsCommand {
+        CalcFileStatsCommand {
+            blob,
+            result,
+        }
+    }
+}
+
+impl Command for CalcFileStatsCommand {
+    fn execute(&self) -> Result<(), anyhow::Error> {
+        let file_block = FileBlock::from_blob_desc(&self.blob)?;
+        if file_block.is_osm_data() {
+            let mut block_stats = BlockStats::new(self.blob.index());
+            for element in file_block.elements() {
+                block_stats.add(element);
+            }
+            for changeset in file_block.changesets() {
+                block_stats.add_changeset(changeset);
+            }
+            self.result.lock().unwrap().push(block_stats);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use std::sync::{Arc, Mutex};
+
+use command_executor::command::Command;
+
+use crate::osm::pbf::blob_desc::BlobDesc;
+use crate::osm::pbf::file_block::FileBlock;
+use crate::osm::stats::tag_counts::TagCounts;
+
+pub(crate) struct CalcTagStatsCommand {
+    blob: BlobDesc,
+    result: Arc<Mutex<TagCounts>>,
+}
+
+impl CalcTagStatsCommand {
+    pub(crate) fn new(
+        blob: BlobDesc,
+        result: Arc<Mutex<TagCounts>>,
+    ) -> CalcTagStatsCommand {
+        CalcTagStatsCommand {
+            blob,
+            result,
+        }
+    }
+}
+
+impl Command for CalcTagStatsCommand {
+    fn execute(&self) -> Result<(), anyhow::Error> {
+        let file_block = FileBlock::from_blob_desc(&self.blob)?;
+        if file_block.is_osm_data() {
+            let mut tag_counts = TagCounts::default();
+            for element in file_block.elements() {
+                tag_counts.add(element);
+            }
+            self.result.lock().unwrap().merge(tag_counts);
+        }
+        Ok(())
+    }
+}
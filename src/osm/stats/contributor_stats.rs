@@ -0,0 +1,383 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use json::JsonValue;
+
+use crate::osm::apidb_dump;
+use crate::osm::converters::timestamp_to_iso8601_seconds;
+use crate::osm::model::element::Element;
+use crate::osm::model::user::User;
+use crate::osm::pbf;
+
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+/// The length of the periods of a [ContributorStats] report
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Period {
+    Day,
+    /// A week starting on Monday
+    Week,
+}
+
+impl Period {
+    /// The start of the period containing the timestamp, in milliseconds
+    pub fn start(&self, timestamp: i64) -> i64 {
+        let day = timestamp.div_euclid(DAY_MILLIS);
+        match self {
+            Period::Day => {
+                day * DAY_MILLIS
+            }
+            Period::Week => {
+                // 1970-01-01 was a Thursday
+                (day - (day + 3).rem_euclid(7)) * DAY_MILLIS
+            }
+        }
+    }
+}
+
+impl Display for Period {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Period::Day => write!(f, "day"),
+            Period::Week => write!(f, "week"),
+        }
+    }
+}
+
+impl FromStr for Period {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Period::Day),
+            "week" => Ok(Period::Week),
+            _ => Err(anyhow!("Expected day or week. Got {} instead", s)),
+        }
+    }
+}
+
+/// The edits of a changeset found in the data
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangesetActivity {
+    pub id: i64,
+    pub uid: i32,
+    pub user: String,
+    pub edits: u64,
+    pub first_timestamp: i64,
+    pub last_timestamp: i64,
+}
+
+/// The edits of a day or a week
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodActivity {
+    /// Start of the period in milliseconds
+    pub start: i64,
+    pub edits: u64,
+    /// Number of distinct users
+    pub users: u64,
+    /// Number of distinct changesets
+    pub changesets: u64,
+}
+
+struct UserActivity {
+    name: String,
+    name_timestamp: i64,
+    edits: u64,
+    created: u64,
+    modified: u64,
+    deleted: u64,
+    first_timestamp: i64,
+    last_timestamp: i64,
+}
+
+#[derive(Default)]
+struct PeriodAccumulator {
+    edits: u64,
+    users: HashSet<i32>,
+    changesets: HashSet<i64>,
+}
+
+/// Extend the [first, last] range with a timestamp. Readers use 0 or -1 for no timestamp, and an
+/// empty range is 0
+fn extend(first: &mut i64, last: &mut i64, timestamp: i64) {
+    if timestamp <= 0 {
+        return;
+    }
+    if *first == 0 || timestamp < *first {
+        *first = timestamp;
+    }
+    if timestamp > *last {
+        *last = timestamp;
+    }
+}
+
+/// Edit activity per user, per changeset and per day or week
+///
+/// Every object version is an edit attributed to its uid, user and changeset. The name of a user is
+/// the name on the latest edit. Versions without a timestamp are counted but do not contribute to
+/// the activity periods. Versions without a uid or a changeset, such as anonymous edits or data
+/// stripped of metadata, are counted in the periods but not attributed to a user or a changeset.
+/// The input is usually history data, in any order.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::stats::contributor_stats::{ContributorStats, Period};
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/history-niue-230109.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let stats = ContributorStats::from_pbf(&reader, Period::Week)?;
+///     for user in stats.users().iter().take(10) {
+///         println!("{} {}", user.name(), user.edits());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContributorStats {
+    period: Period,
+    users: Vec<User>,
+    changesets: Vec<ChangesetActivity>,
+    periods: Vec<PeriodActivity>,
+}
+
+impl ContributorStats {
+    pub fn from_pbf(reader: &pbf::reader::Reader, period: Period) -> Result<ContributorStats, anyhow::Error> {
+        Ok(Self::compute(period, reader.elements()?))
+    }
+
+    pub fn from_apidb(reader: &apidb_dump::read::reader::Reader, period: Period) -> Result<ContributorStats, anyhow::Error> {
+        Ok(Self::compute(period, reader.elements()?))
+    }
+
+    pub fn compute(period: Period, elements: impl Iterator<Item=Element>) -> ContributorStats {
+        let mut users: HashMap<i32, UserActivity> = HashMap::new();
+        let mut changesets: HashMap<i64, ChangesetActivity> = HashMap::new();
+        let mut periods: BTreeMap<i64, PeriodAccumulator> = BTreeMap::new();
+
+        for element in elements {
+            let (version, timestamp, changeset, uid, user, visible) = match &element {
+                Element::Node { node } => {
+                    (node.version(), node.timestamp(), node.changeset(), node.uid(), node.user(), node.visible())
+                }
+                Element::Way { way } => {
+                    (way.version(), way.timestamp(), way.changeset(), way.uid(), way.user(), way.visible())
+                }
+                Element::Relation { relation } => {
+                    (relation.version(), relation.timestamp(), relation.changeset(), relation.uid(), relation.user(), relation.visible())
+                }
+                Element::Sentinel => {
+                    continue;
+                }
+            };
+
+            if uid > 0 {
+                let user_activity = users.entry(uid).or_insert_with(|| {
+                    UserActivity {
+                        name: user.clone(),
+                        name_timestamp: timestamp,
+                        edits: 0,
+                        created: 0,
+                        modified: 0,
+                        deleted: 0,
+                        first_timestamp: 0,
+                        last_timestamp: 0,
+                    }
+                });
+                if timestamp > user_activity.name_timestamp {
+                    user_activity.name.clone_from(user);
+                    user_activity.name_timestamp = timestamp;
+                }
+                user_activity.edits += 1;
+                if !visible {
+                    user_activity.deleted += 1;
+                } else if version == 1 {
+                    user_activity.created += 1;
+                } else {
+                    user_activity.modified += 1;
+                }
+                extend(&mut user_activity.first_timestamp, &mut user_activity.last_timestamp, timestamp);
+            }
+
+            if changeset > 0 {
+                let changeset_activity = changesets.entry(changeset).or_insert_with(|| {
+                    ChangesetActivity {
+                        id: changeset,
+                        uid,
+                        user: user.clone(),
+                        edits: 0,
+                        first_timestamp: 0,
+                        last_timestamp: 0,
+                    }
+                });
+                changeset_activity.edits += 1;
+                extend(&mut changeset_activity.first_timestamp, &mut changeset_activity.last_timestamp, timestamp);
+            }
+
+            if timestamp > 0 {
+                let period_accumulator = periods.entry(period.start(timestamp)).or_default();
+                period_accumulator.edits += 1;
+                if uid > 0 {
+                    period_accumulator.users.insert(uid);
+                }
+                if changeset > 0 {
+                    period_accumulator.changesets.insert(changeset);
+                }
+            }
+        }
+
+        let mut user_changesets: HashMap<i32, u64> = HashMap::new();
+        for changeset_activity in changesets.values() {
+            *user_changesets.entry(changeset_activity.uid).or_default() += 1;
+        }
+        let mut users: Vec<User> = users.into_iter()
+            .map(|(uid, activity)| {
+                User::new(
+                    uid,
+                    activity.name,
+                    activity.edits,
+                    activity.created,
+                    activity.modified,
+                    activity.deleted,
+                    user_changesets.get(&uid).copied().unwrap_or(0),
+                    activity.first_timestamp,
+                    activity.last_timestamp,
+                )
+            })
+            .collect();
+        users.sort_by(|a, b| b.edits().cmp(&a.edits()).then_with(|| a.uid().cmp(&b.uid())));
+
+        let mut changesets: Vec<ChangesetActivity> = changesets.into_values().collect();
+        changesets.sort_by_key(|changeset_activity| changeset_activity.id);
+
+        let periods = periods.into_iter()
+            .map(|(start, accumulator)| {
+                PeriodActivity {
+                    start,
+                    edits: accumulator.edits,
+                    users: accumulator.users.len() as u64,
+                    changesets: accumulator.changesets.len() as u64,
+                }
+            })
+            .collect();
+
+        ContributorStats {
+            period,
+            users,
+            changesets,
+            periods,
+        }
+    }
+
+    pub fn period(&self) -> Period {
+        self.period
+    }
+
+    /// Users sorted by descending number of edits, then by uid
+    pub fn users(&self) -> &Vec<User> {
+        &self.users
+    }
+
+    pub fn user(&self, uid: i32) -> Option<&User> {
+        self.users.iter().find(|user| user.uid() == uid)
+    }
+
+    /// Changesets sorted by id
+    pub fn changesets(&self) -> &Vec<ChangesetActivity> {
+        &self.changesets
+    }
+
+    pub fn changeset(&self, id: i64) -> Option<&ChangesetActivity> {
+        self.changesets.binary_search_by_key(&id, |changeset_activity| changeset_activity.id)
+            .ok()
+            .map(|i| &self.changesets[i])
+    }
+
+    /// Periods with edits, sorted by start
+    pub fn periods(&self) -> &Vec<PeriodActivity> {
+        &self.periods
+    }
+
+    fn timestamp_to_json(timestamp: i64) -> JsonValue {
+        if timestamp <= 0 {
+            return JsonValue::Null;
+        }
+        match timestamp_to_iso8601_seconds(timestamp * 1000) {
+            Ok(timestamp) => {
+                timestamp.into()
+            }
+            Err(_) => {
+                JsonValue::Null
+            }
+        }
+    }
+
+    /// The report as a JSON object with users, changesets and periods arrays
+    pub fn to_json(&self) -> JsonValue {
+        let mut users = JsonValue::new_array();
+        for user in &self.users {
+            // pushing into an array can not fail
+            users.push(
+                json::object! {
+                    "uid": user.uid(),
+                    "user": user.name().as_str(),
+                    "edits": user.edits(),
+                    "created": user.created(),
+                    "modified": user.modified(),
+                    "deleted": user.deleted(),
+                    "changesets": user.changesets(),
+                    "first": Self::timestamp_to_json(user.first_timestamp()),
+                    "last": Self::timestamp_to_json(user.last_timestamp()),
+                }
+            ).unwrap();
+        }
+        let mut changesets = JsonValue::new_array();
+        for changeset in &self.changesets {
+            changesets.push(
+                json::object! {
+                    "id": changeset.id,
+                    "uid": changeset.uid,
+                    "user": changeset.user.as_str(),
+                    "edits": changeset.edits,
+                    "first": Self::timestamp_to_json(changeset.first_timestamp),
+                    "last": Self::timestamp_to_json(changeset.last_timestamp),
+                }
+            ).unwrap();
+        }
+        let mut periods = JsonValue::new_array();
+        for period in &self.periods {
+            periods.push(
+                json::object! {
+                    "start": Self::timestamp_to_json(period.start),
+                    "edits": period.edits,
+                    "users": period.users,
+                    "changesets": period.changesets,
+                }
+            ).unwrap();
+        }
+        json::object! {
+            "period": self.period.to_string(),
+            "users": users,
+            "changesets": changesets,
+            "periods": periods,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osm::converters::iso8601_to_timestamp_millis;
+
+    #[test]
+    fn test_period_start() {
+        let timestamp = iso8601_to_timestamp_millis("2009-03-25T00:53:03Z").unwrap();
+        assert_eq!(Period::Day.start(timestamp), iso8601_to_timestamp_millis("2009-03-25T00:00:00Z").unwrap());
+        assert_eq!(Period::Week.start(timestamp), iso8601_to_timestamp_millis("2009-03-23T00:00:00Z").unwrap());
+        let monday = iso8601_to_timestamp_millis("2009-03-23T00:00:00Z").unwrap();
+        assert_eq!(Period::Week.start(monday), monday);
+        assert_eq!(Period::Week.start(monday - 1), monday - 7 * DAY_MILLIS);
+    }
+}
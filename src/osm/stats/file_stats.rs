@@ -0,0 +1,342 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Context};
+use command_executor::shutdown_mode::ShutdownMode;
+use command_executor::thread_pool_builder::ThreadPoolBuilder;
+use json::JsonValue;
+
+use crate::osm::converters::timestamp_to_iso8601_seconds;
+use crate::osm::filter::get_id::ObjectType;
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::pbf::file_info::FileInfo;
+use crate::osm::pbf::reader::Reader;
+use crate::osm::stats::block_stats::BlockStats;
+use crate::osm::stats::calc_file_stats_command::CalcFileStatsCommand;
+
+/// Which metadata attributes are present
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetadataPresence {
+    pub version: bool,
+    pub timestamp: bool,
+    pub changeset: bool,
+    pub user: bool,
+    pub uid: bool,
+}
+
+impl MetadataPresence {
+    pub(crate) fn all(value: bool) -> MetadataPresence {
+        MetadataPresence {
+            version: value,
+            timestamp: value,
+            changeset: value,
+            user: value,
+            uid: value,
+        }
+    }
+
+    pub(crate) fn and(&mut self, other: &MetadataPresence) {
+        self.version &= other.version;
+        self.timestamp &= other.timestamp;
+        self.changeset &= other.changeset;
+        self.user &= other.user;
+        self.uid &= other.uid;
+    }
+
+    pub(crate) fn or(&mut self, other: &MetadataPresence) {
+        self.version |= other.version;
+        self.timestamp |= other.timestamp;
+        self.changeset |= other.changeset;
+        self.user |= other.user;
+        self.uid |= other.uid;
+    }
+}
+
+/// Count and id range of the objects of one type
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TypeStats {
+    pub count: u64,
+    pub min_id: i64,
+    pub max_id: i64,
+}
+
+impl TypeStats {
+    pub(crate) fn add(&mut self, id: i64) {
+        if self.count == 0 {
+            self.min_id = id;
+            self.max_id = id;
+        } else {
+            self.min_id = self.min_id.min(id);
+            self.max_id = self.max_id.max(id);
+        }
+        self.count += 1;
+    }
+
+    pub(crate) fn merge(&mut self, other: &TypeStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+        } else {
+            self.count += other.count;
+            self.min_id = self.min_id.min(other.min_id);
+            self.max_id = self.max_id.max(other.max_id);
+        }
+    }
+}
+
+/// Extended statistics of a *.osm.pbf file
+///
+/// Computed in a single parallel pass over the data blocks and serialized to the JSON schema of
+/// `osmium fileinfo --extended --json`, see [FileStats::to_json]. The osmium specific crc32 and
+/// buffers entries are not produced.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::stats::file_stats::FileStats;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let file_stats = FileStats::calc(&reader)?;
+///     println!("{}", file_stats.to_json().pretty(4));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileStats {
+    path: PathBuf,
+    size: u64,
+    info: FileInfo,
+    stats: BlockStats,
+}
+
+impl FileStats {
+    pub fn calc(reader: &Reader) -> Result<FileStats, anyhow::Error> {
+        let mut tp = ThreadPoolBuilder::new()
+            .with_name_str("file-stats-calculator")
+            .with_tasks(num_cpus::get())
+            .with_queue_size(1024)
+            .with_shutdown_mode(ShutdownMode::CompletePending)
+            .build()?;
+
+        let result = Arc::new(Mutex::new(Vec::new()));
+        for blob in reader.blobs()? {
+            tp.submit(
+                Box::new(
+                    CalcFileStatsCommand::new(
+                        blob,
+                        result.clone(),
+                    )
+                )
+            );
+        }
+        tp.shutdown();
+        tp.join()?;
+
+        let mut blocks = std::mem::take(&mut *result.lock().unwrap());
+        blocks.sort_by_key(|block_stats| block_stats.index);
+        let mut stats = BlockStats::new(0);
+        for block_stats in &blocks {
+            stats.merge(block_stats);
+        }
+        if blocks.is_empty() {
+            stats.all_objects = MetadataPresence::all(false);
+        }
+
+        let path = reader.path().clone();
+        let size = fs::metadata(&path)
+            .with_context(|| anyhow!("path: {}", path.display()))?
+            .len();
+        Ok(
+            FileStats {
+                path,
+                size,
+                info: reader.info().clone(),
+                stats,
+            }
+        )
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// File size in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn info(&self) -> &FileInfo {
+        &self.info
+    }
+
+    /// The file has the HistoricalInformation feature
+    pub fn with_history(&self) -> bool {
+        self.info.required("HistoricalInformation") || self.info.optional("HistoricalInformation")
+    }
+
+    /// Count and id range of the objects of a type, ids are 0 if there are no such objects
+    pub fn type_stats(&self, object_type: ObjectType) -> &TypeStats {
+        self.stats.type_stats(object_type)
+    }
+
+    /// Count and id range of the changesets in changeset groups
+    pub fn changesets(&self) -> &TypeStats {
+        &self.stats.changesets
+    }
+
+    /// Bounding box of the visible nodes
+    pub fn bounding_box(&self) -> &Option<BoundingBox> {
+        &self.stats.bounding_box
+    }
+
+    /// The earliest object timestamp in milliseconds
+    pub fn first_timestamp(&self) -> Option<i64> {
+        self.stats.first_timestamp
+    }
+
+    /// The latest object timestamp in milliseconds
+    pub fn last_timestamp(&self) -> Option<i64> {
+        self.stats.last_timestamp
+    }
+
+    /// The objects are sorted by type, id and version
+    pub fn objects_ordered(&self) -> bool {
+        self.stats.objects_ordered
+    }
+
+    /// Some objects have more than one version
+    pub fn multiple_versions(&self) -> bool {
+        self.stats.multiple_versions
+    }
+
+    /// The metadata attributes present in all the objects
+    pub fn all_objects(&self) -> &MetadataPresence {
+        &self.stats.all_objects
+    }
+
+    /// The metadata attributes present in at least one object
+    pub fn some_objects(&self) -> &MetadataPresence {
+        &self.stats.some_objects
+    }
+
+    fn round7(value: f64) -> f64 {
+        (value * 1E7).round() / 1E7
+    }
+
+    fn bounding_box_to_json(bounding_box: &BoundingBox) -> JsonValue {
+        json::array![
+            Self::round7(bounding_box.left()),
+            Self::round7(bounding_box.bottom()),
+            Self::round7(bounding_box.right()),
+            Self::round7(bounding_box.top()),
+        ]
+    }
+
+    fn timestamp_to_json(timestamp: Option<i64>) -> JsonValue {
+        match timestamp.and_then(|timestamp| timestamp_to_iso8601_seconds(timestamp * 1000).ok()) {
+            None => {
+                JsonValue::Null
+            }
+            Some(timestamp) => {
+                timestamp.into()
+            }
+        }
+    }
+
+    fn metadata_to_json(metadata: &MetadataPresence) -> JsonValue {
+        json::object! {
+            "version": metadata.version,
+            "timestamp": metadata.timestamp,
+            "changeset": metadata.changeset,
+            "user": metadata.user,
+            "uid": metadata.uid,
+        }
+    }
+
+    fn header_to_json(&self) -> JsonValue {
+        let mut boxes = JsonValue::new_array();
+        if let Some(bounding_box) = self.info.bounding_box() {
+            // pushing into an array can not fail
+            boxes.push(Self::bounding_box_to_json(bounding_box)).unwrap();
+        }
+        let mut option = JsonValue::new_object();
+        if let Some(generator) = self.info.writingprogram() {
+            option["generator"] = generator.clone().into();
+        }
+        if let Some(timestamp) = self.info.osmosis_replication_timestamp() {
+            option["osmosis_replication_timestamp"] = Self::timestamp_to_json(Some(timestamp * 1000));
+        }
+        if let Some(sequence_number) = self.info.osmosis_replication_sequence_number() {
+            option["osmosis_replication_sequence_number"] = sequence_number.to_string().into();
+        }
+        if let Some(base_url) = self.info.osmosis_replication_base_url() {
+            option["osmosis_replication_base_url"] = base_url.clone().into();
+        }
+        option["pbf_dense_nodes"] = self.info.required("DenseNodes").to_string().into();
+        for (i, feature) in self.info.optional_features().iter().enumerate() {
+            option[format!("pbf_optional_feature_{}", i)] = feature.clone().into();
+        }
+        if self.info.optional("Sort.Type_then_ID") {
+            option["sorting"] = "Type_then_ID".into();
+        }
+        json::object! {
+            "boxes": boxes,
+            "with_history": self.with_history(),
+            "option": option,
+        }
+    }
+
+    fn data_to_json(&self) -> JsonValue {
+        let types = [ObjectType::Node, ObjectType::Way, ObjectType::Relation];
+        let [nodes, ways, relations] = types.map(|object_type| *self.type_stats(object_type));
+        json::object! {
+            "bbox": self.stats.bounding_box.as_ref().map_or(JsonValue::new_array(), Self::bounding_box_to_json),
+            "timestamp": {
+                "first": Self::timestamp_to_json(self.stats.first_timestamp),
+                "last": Self::timestamp_to_json(self.stats.last_timestamp),
+            },
+            "objects_ordered": self.stats.objects_ordered,
+            "multiple_versions": self.stats.multiple_versions,
+            "count": {
+                "changesets": self.stats.changesets.count,
+                "nodes": nodes.count,
+                "ways": ways.count,
+                "relations": relations.count,
+            },
+            "minid": {
+                "changesets": self.stats.changesets.min_id,
+                "nodes": nodes.min_id,
+                "ways": ways.min_id,
+                "relations": relations.min_id,
+            },
+            "maxid": {
+                "changesets": self.stats.changesets.max_id,
+                "nodes": nodes.max_id,
+                "ways": ways.max_id,
+                "relations": relations.max_id,
+            },
+            "metadata": {
+                "all_objects": Self::metadata_to_json(&self.stats.all_objects),
+                "some_objects": Self::metadata_to_json(&self.stats.some_objects),
+            },
+        }
+    }
+
+    /// Serialize to the JSON schema of `osmium fileinfo --extended --json`
+    pub fn to_json(&self) -> JsonValue {
+        json::object! {
+            "file": {
+                "name": self.path.to_string_lossy().to_string(),
+                "format": "PBF",
+                "compression": "none",
+                "size": self.size,
+            },
+            "header": self.header_to_json(),
+            "data": self.data_to_json(),
+        }
+    }
+}
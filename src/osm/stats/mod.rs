@@ -0,0 +1,8 @@
+pub mod contributor_stats;
+pub mod file_stats;
+pub mod tag_stats;
+
+pub(crate) mod block_stats;
+pub(crate) mod calc_file_stats_command;
+pub(crate) mod calc_tag_stats_command;
+pub(crate) mod tag_counts;
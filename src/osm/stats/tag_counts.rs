@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use crate::osm::filter::get_id::ObjectType;
+use crate::osm::model::element::Element;
+use crate::osm::model::tag::Tag;
+use crate::osm::stats::tag_stats::TypeCounts;
+
+/// Tag usage counts of a single data block, or of several blocks merged
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TagCounts {
+    pub(crate) keys: HashMap<String, TypeCounts>,
+    pub(crate) tags: HashMap<(String, String), TypeCounts>,
+    pub(crate) combinations: HashMap<(String, String), TypeCounts>,
+}
+
+impl TagCounts {
+    pub(crate) fn add(&mut self, element: &Element) {
+        let (object_type, tags) = match element {
+            Element::Node { node } => {
+                (ObjectType::Node, node.tags())
+            }
+            Element::Way { way } => {
+                (ObjectType::Way, way.tags())
+            }
+            Element::Relation { relation } => {
+                (ObjectType::Relation, relation.tags())
+            }
+            Element::Sentinel => {
+                return;
+            }
+        };
+        let mut keys: Vec<&String> = tags.iter().map(Tag::k).collect();
+        keys.sort();
+        keys.dedup();
+
+        for tag in tags {
+            self.tags.entry((tag.k().clone(), tag.v().clone()))
+                .or_default()
+                .add(object_type);
+        }
+        for (i, key) in keys.iter().enumerate() {
+            self.keys.entry((*key).clone())
+                .or_default()
+                .add(object_type);
+            for other in &keys[i + 1..] {
+                self.combinations.entry(((*key).clone(), (*other).clone()))
+                    .or_default()
+                    .add(object_type);
+            }
+        }
+    }
+
+    pub(crate) fn merge(&mut self, other: TagCounts) {
+        for (key, counts) in other.keys {
+            self.keys.entry(key).or_default().merge(&counts);
+        }
+        for (tag, counts) in other.tags {
+            self.tags.entry(tag).or_default().merge(&counts);
+        }
+        for (combination, counts) in other.combinations {
+            self.combinations.entry(combination).or_default().merge(&counts);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::osm::model::coordinate::Coordinate;
+    use crate::osm::model::node::Node;
+    use crate::osm::model::way::Way;
+
+    use super::*;
+
+    fn tags(tags: &[(&str, &str)]) -> Vec<Tag> {
+        tags.iter().map(|(k, v)| Tag::new(k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_tag_counts() {
+        let mut counts = TagCounts::default();
+        counts.add(&Element::Node { node: Node::new(1, 1, Coordinate::new(0.0, 0.0), 0, 0, 0, String::new(), true, tags(&[("name", "a"), ("amenity", "cafe")])) });
+        let mut other = TagCounts::default();
+        other.add(&Element::Way { way: Way::new(2, 1, 0, 0, 0, String::new(), true, vec![1], tags(&[("name", "a"), ("highway", "path")])) });
+        counts.merge(other);
+
+        assert_eq!(counts.keys["name"], TypeCounts { all: 2, nodes: 1, ways: 1, relations: 0 });
+        assert_eq!(counts.tags[&("amenity".to_string(), "cafe".to_string())].nodes, 1);
+        assert_eq!(counts.combinations[&("amenity".to_string(), "name".to_string())].all, 1);
+        assert_eq!(counts.combinations[&("highway".to_string(), "name".to_string())].ways, 1);
+        assert_eq!(counts.combinations.len(), 2);
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Context};
+use command_executor::shutdown_mode::ShutdownMode;
+use command_executor::thread_pool_builder::ThreadPoolBuilder;
+use json::JsonValue;
+
+use crate::osm::filter::get_id::ObjectType;
+use crate::osm::pbf::reader::Reader;
+use crate::osm::stats::calc_tag_stats_command::CalcTagStatsCommand;
+use crate::osm::stats::tag_counts::TagCounts;
+
+/// Number of objects in total and of each type
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TypeCounts {
+    pub all: u64,
+    pub nodes: u64,
+    pub ways: u64,
+    pub relations: u64,
+}
+
+impl TypeCounts {
+    pub(crate) fn add(&mut self, object_type: ObjectType) {
+        self.all += 1;
+        match object_type {
+            ObjectType::Node => {
+                self.nodes += 1;
+            }
+            ObjectType::Way => {
+                self.ways += 1;
+            }
+            ObjectType::Relation => {
+                self.relations += 1;
+            }
+        }
+    }
+
+    pub(crate) fn merge(&mut self, other: &TypeCounts) {
+        self.all += other.all;
+        self.nodes += other.nodes;
+        self.ways += other.ways;
+        self.relations += other.relations;
+    }
+}
+
+/// The table of a [TagStats] report
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TagStatsKind {
+    /// Objects with each key
+    Keys,
+    /// Objects with each key=value pair
+    Tags,
+    /// Objects with each pair of keys
+    Combinations,
+}
+
+impl Display for TagStatsKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagStatsKind::Keys => write!(f, "keys"),
+            TagStatsKind::Tags => write!(f, "tags"),
+            TagStatsKind::Combinations => write!(f, "combinations"),
+        }
+    }
+}
+
+impl FromStr for TagStatsKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "keys" => Ok(TagStatsKind::Keys),
+            "tags" => Ok(TagStatsKind::Tags),
+            "combinations" => Ok(TagStatsKind::Combinations),
+            _ => Err(anyhow!("Expected keys, tags or combinations. Got {} instead", s)),
+        }
+    }
+}
+
+/// One row of a [TagStats] report, the key and, for tags and combinations, the value or second key
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagStatsRow<'a> {
+    pub key: &'a str,
+    pub other: Option<&'a str>,
+    pub counts: TypeCounts,
+}
+
+/// Tag usage statistics of a *.osm.pbf file
+///
+/// Counts the objects with each key, each key=value pair and each pair of keys, in total and per
+/// object type, in a single parallel pass over the data blocks. The reports are sorted by
+/// descending total count and can be limited with [TagStats::with_min_count] and
+/// [TagStats::with_top].
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::stats::tag_stats::{TagStats, TagStatsKind};
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109-keys.csv");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let mut tag_stats = TagStats::calc(&reader)?;
+///     tag_stats.with_min_count(10);
+///     tag_stats.with_top(Some(100));
+///     tag_stats.write_csv(TagStatsKind::Keys, &output_path)?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TagStats {
+    counts: TagCounts,
+    min_count: u64,
+    top: Option<usize>,
+}
+
+impl TagStats {
+    pub fn calc(reader: &Reader) -> Result<TagStats, anyhow::Error> {
+        let mut tp = ThreadPoolBuilder::new()
+            .with_name_str("tag-stats-calculator")
+            .with_tasks(num_cpus::get())
+            .with_queue_size(1024)
+            .with_shutdown_mode(ShutdownMode::CompletePending)
+            .build()?;
+
+        let result = Arc::new(Mutex::new(TagCounts::default()));
+        for blob in reader.blobs()? {
+            tp.submit(
+                Box::new(
+                    CalcTagStatsCommand::new(
+                        blob,
+                        result.clone(),
+                    )
+                )
+            );
+        }
+        tp.shutdown();
+        tp.join()?;
+
+        let counts = std::mem::take(&mut *result.lock().unwrap());
+        Ok(
+            TagStats {
+                counts,
+                min_count: 0,
+                top: None,
+            }
+        )
+    }
+
+    /// Report only the rows used by at least min_count objects
+    pub fn with_min_count(&mut self, min_count: u64) {
+        self.min_count = min_count;
+    }
+
+    /// Report only the top rows of each table, all rows by default
+    pub fn with_top(&mut self, top: Option<usize>) {
+        self.top = top;
+    }
+
+    /// Number of distinct keys, regardless of the thresholds
+    pub fn key_count(&self) -> usize {
+        self.counts.keys.len()
+    }
+
+    /// Number of distinct key=value pairs, regardless of the thresholds
+    pub fn tag_count(&self) -> usize {
+        self.counts.tags.len()
+    }
+
+    /// Counts of a key, regardless of the thresholds
+    pub fn key(&self, key: &str) -> Option<&TypeCounts> {
+        self.counts.keys.get(key)
+    }
+
+    /// Counts of a key=value pair, regardless of the thresholds
+    pub fn tag(&self, key: &str, value: &str) -> Option<&TypeCounts> {
+        self.counts.tags.get(&(key.to_string(), value.to_string()))
+    }
+
+    /// Counts of a pair of keys in any order, regardless of the thresholds
+    pub fn combination(&self, key1: &str, key2: &str) -> Option<&TypeCounts> {
+        let (key1, key2) = if key1 <= key2 { (key1, key2) } else { (key2, key1) };
+        self.counts.combinations.get(&(key1.to_string(), key2.to_string()))
+    }
+
+    /// The rows of a table sorted by descending total count, then by key, after the thresholds
+    pub fn rows(&self, kind: TagStatsKind) -> Vec<TagStatsRow<'_>> {
+        let mut rows: Vec<TagStatsRow> = match kind {
+            TagStatsKind::Keys => {
+                self.counts.keys.iter()
+                    .map(|(key, counts)| TagStatsRow { key, other: None, counts: *counts })
+                    .collect()
+            }
+            TagStatsKind::Tags => {
+                self.counts.tags.iter()
+                    .map(|((key, value), counts)| TagStatsRow { key, other: Some(value), counts: *counts })
+                    .collect()
+            }
+            TagStatsKind::Combinations => {
+                self.counts.combinations.iter()
+                    .map(|((key1, key2), counts)| TagStatsRow { key: key1, other: Some(key2), counts: *counts })
+                    .collect()
+            }
+        };
+        rows.retain(|row| row.counts.all >= self.min_count);
+        rows.sort_by(|a, b| {
+            b.counts.all.cmp(&a.counts.all)
+                .then_with(|| a.key.cmp(b.key))
+                .then_with(|| a.other.cmp(&b.other))
+        });
+        if let Some(top) = self.top {
+            rows.truncate(top);
+        }
+        rows
+    }
+
+    fn header(kind: TagStatsKind) -> &'static [&'static str] {
+        match kind {
+            TagStatsKind::Keys => &["key"],
+            TagStatsKind::Tags => &["key", "value"],
+            TagStatsKind::Combinations => &["key1", "key2"],
+        }
+    }
+
+    /// Write a table as CSV with a header line, key columns followed by count_all, count_nodes,
+    /// count_ways and count_relations
+    pub fn write_csv(&self, kind: TagStatsKind, path: &PathBuf) -> Result<(), anyhow::Error> {
+        let file = File::create(path)
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        let mut output = BufWriter::new(file);
+        self.write_csv_to(kind, &mut output)?;
+        output.flush()?;
+        Ok(())
+    }
+
+    /// Write a table as CSV, see [TagStats::write_csv]
+    pub fn write_csv_to(&self, kind: TagStatsKind, output: &mut impl Write) -> Result<(), anyhow::Error> {
+        writeln!(output, "{},count_all,count_nodes,count_ways,count_relations", Self::header(kind).join(","))?;
+        for row in self.rows(kind) {
+            write!(output, "{}", csv_escape(row.key))?;
+            if let Some(other) = row.other {
+                write!(output, ",{}", csv_escape(other))?;
+            }
+            writeln!(output, ",{},{},{},{}", row.counts.all, row.counts.nodes, row.counts.ways, row.counts.relations)?;
+        }
+        Ok(())
+    }
+
+    fn table_to_json(&self, kind: TagStatsKind) -> JsonValue {
+        let header = Self::header(kind);
+        let mut table = JsonValue::new_array();
+        for row in self.rows(kind) {
+            let mut value = JsonValue::new_object();
+            value[header[0]] = row.key.into();
+            if let Some(other) = row.other {
+                value[header[1]] = other.into();
+            }
+            value["count_all"] = row.counts.all.into();
+            value["count_nodes"] = row.counts.nodes.into();
+            value["count_ways"] = row.counts.ways.into();
+            value["count_relations"] = row.counts.relations.into();
+            // pushing into an array can not fail
+            table.push(value).unwrap();
+        }
+        table
+    }
+
+    /// All the tables as a JSON object with keys, tags and combinations arrays
+    pub fn to_json(&self) -> JsonValue {
+        json::object! {
+            "keys": self.table_to_json(TagStatsKind::Keys),
+            "tags": self.table_to_json(TagStatsKind::Tags),
+            "combinations": self.table_to_json(TagStatsKind::Combinations),
+        }
+    }
+}
+
+/// Quote a CSV field if it contains a separator, a quote or a line break
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("name"), "name");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,122 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+
+use crate::osm::index::node_location_index::{NodeLocationIndex, parallel_fill, way_coordinates};
+use crate::osm::model::element::Element;
+use crate::osm::pbf;
+use crate::osm::pbf::compression_type::CompressionType;
+use crate::osm::pbf::file_info::FileInfo;
+
+/// The optional *.osm.pbf feature set when ways carry node locations
+pub const LOCATIONS_ON_WAYS: &str = "LocationsOnWays";
+
+/// Add node locations to ways, producing *.osm.pbf files with the LocationsOnWays feature
+///
+/// The node locations are collected into a [NodeLocationIndex] in a first pass over the input,
+/// the second pass writes all elements with the locations set on every way.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use std::sync::Arc;
+/// use osm_io::osm::index::sparse_mem_location_index::SparseMemLocationIndex;
+/// use osm_io::osm::pbf::compression_type::CompressionType;
+/// use osm_io::osm::transform::locations_on_ways::LocationsOnWays;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109-low.osm.pbf");
+///     let mut locations_on_ways = LocationsOnWays::new(Arc::new(SparseMemLocationIndex::new()));
+///     locations_on_ways.with_ignore_missing_nodes(true);
+///     locations_on_ways.run(&input_path, output_path, CompressionType::Zlib, 4)?;
+///     Ok(())
+/// }
+/// ```
+pub struct LocationsOnWays {
+    index: Arc<dyn NodeLocationIndex>,
+    ignore_missing_nodes: bool,
+}
+
+impl LocationsOnWays {
+    /// Create a new transform using the index to store the node locations
+    pub fn new(index: Arc<dyn NodeLocationIndex>) -> LocationsOnWays {
+        LocationsOnWays {
+            index,
+            ignore_missing_nodes: false,
+        }
+    }
+
+    /// Leave ways referencing nodes that are missing in the input without locations instead of
+    /// failing. Useful for extracts where ways cross the extract boundary
+    pub fn with_ignore_missing_nodes(&mut self, ignore_missing_nodes: bool) {
+        self.ignore_missing_nodes = ignore_missing_nodes;
+    }
+
+    /// The node location index
+    pub fn index(&self) -> &Arc<dyn NodeLocationIndex> {
+        &self.index
+    }
+
+    /// Copy of file_info with the LocationsOnWays optional feature
+    pub fn file_info(file_info: &FileInfo) -> FileInfo {
+        let mut result = file_info.clone();
+        if !result.optional(LOCATIONS_ON_WAYS) {
+            let mut optional_features = result.optional_features().clone();
+            optional_features.push(LOCATIONS_ON_WAYS.to_string());
+            result.with_optional_features(&optional_features);
+        }
+        result
+    }
+
+    /// Set node locations on a visible way from the index, other elements are returned as is
+    pub fn transform(&self, element: Element) -> Result<Element, anyhow::Error> {
+        match element {
+            Element::Way { mut way } => {
+                if way.visible() {
+                    match way_coordinates(self.index.as_ref(), &way) {
+                        Ok(coordinates) => {
+                            way.with_coordinates(coordinates);
+                        }
+                        Err(e) => {
+                            if !self.ignore_missing_nodes {
+                                return Err(e);
+                            }
+                        }
+                    }
+                }
+                Ok(Element::Way { way })
+            }
+            _ => {
+                Ok(element)
+            }
+        }
+    }
+
+    /// Fill the index from the input in parallel, then write the output with node locations on
+    /// all ways
+    ///
+    /// * input_path - *.osm.pbf input
+    /// * output_path - *.osm.pbf output with the LocationsOnWays optional feature
+    /// * compression_type - output compression
+    /// * tasks - number of threads used to fill the index
+    pub fn run(&self, input_path: &Path, output_path: PathBuf, compression_type: CompressionType, tasks: usize) -> Result<(), anyhow::Error> {
+        let reader = pbf::reader::Reader::new(input_path)?;
+        parallel_fill(self.index.clone(), &reader, tasks)?;
+
+        let mut writer = pbf::writer::Writer::from_file_info(
+            output_path,
+            Self::file_info(reader.info()),
+            compression_type,
+        )?;
+        writer.write_header()?;
+        for element in reader.elements()? {
+            if let Element::Sentinel = element {
+                continue;
+            }
+            let element = self.transform(element)
+                .map_err(|e| anyhow!("{}, input: {}", e, input_path.display()))?;
+            writer.write_element(element)?;
+        }
+        writer.close()
+    }
+}
@@ -0,0 +1 @@
+pub mod locations_on_ways;
@@ -0,0 +1,117 @@
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::osm::model::element::Element;
+use crate::osm::pbf::file_info::FileInfo;
+use crate::osm::xml::compression_type::CompressionType;
+use crate::osm::xml::writer::Writer;
+
+/// The action of an osmChange block
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeAction {
+    Create,
+    Modify,
+    Delete,
+}
+
+impl Display for ChangeAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeAction::Create => {
+                write!(f, "create")
+            }
+            ChangeAction::Modify => {
+                write!(f, "modify")
+            }
+            ChangeAction::Delete => {
+                write!(f, "delete")
+            }
+        }
+    }
+}
+
+/// *.osc file writer
+///
+/// Write an osmChange 0.6 file, optionally compressed with gzip or bzip2. Consecutive changes with
+/// the same action are written in a single action block.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::xml::change_writer::{ChangeAction, ChangeWriter};
+/// use osm_io::osm::xml::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109-create.osc");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let mut writer = ChangeWriter::new(output_path, CompressionType::Uncompressed)?;
+///     writer.write_header()?;
+///     for element in reader.elements()? {
+///         writer.write_change(ChangeAction::Create, element)?;
+///     }
+///     writer.close()?;
+///     Ok(())
+/// }
+/// ```
+pub struct ChangeWriter {
+    writer: Writer,
+    action: Option<ChangeAction>,
+}
+
+impl ChangeWriter {
+    pub fn new(path: PathBuf, compression_type: CompressionType) -> Result<ChangeWriter, anyhow::Error> {
+        Ok(
+            ChangeWriter {
+                writer: Writer::from_file_info(path, FileInfo::default(), compression_type)?,
+                action: None,
+            }
+        )
+    }
+
+    /// Write the XML declaration and the root element
+    ///
+    /// Must be called before writing changes.
+    pub fn write_header(&mut self) -> Result<(), anyhow::Error> {
+        let output = self.writer.output()?;
+        output.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")?;
+        output.write_all(b"<osmChange version=\"0.6\" generator=\"osm-io\">\n")?;
+        Ok(())
+    }
+
+    /// Write element in an action block
+    ///
+    /// [Element::Sentinel] is ignored
+    pub fn write_change(&mut self, action: ChangeAction, element: Element) -> Result<(), anyhow::Error> {
+        if let Element::Sentinel = element {
+            return Ok(());
+        }
+        let mut buf = String::with_capacity(256);
+        if self.action != Some(action) {
+            if let Some(previous) = self.action {
+                buf.push_str(&format!("  </{}>\n", previous));
+            }
+            buf.push_str(&format!("  <{}>\n", action));
+            self.action = Some(action);
+        }
+        self.writer.format_element(&element, &mut buf)?;
+        self.writer.output()?.write_all(buf.as_bytes())?;
+        Ok(())
+    }
+
+    /// Close the last action block and the root element and flush the compression stream
+    ///
+    /// Must be called in the end. The writer can not be used after it was closed
+    pub fn close(&mut self) -> Result<(), anyhow::Error> {
+        if let Some(action) = self.action.take() {
+            writeln!(self.writer.output()?, "  </{}>", action)?;
+        }
+        self.writer.output()?.write_all(b"</osmChange>\n")?;
+        self.writer.finish()
+    }
+
+    /// Output path
+    pub fn path(&self) -> &PathBuf {
+        self.writer.path()
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use anyhow::{anyhow, Context};
+use quick_xml::events::Event;
+
+use crate::osm::converters::iso8601_to_timestamp_millis;
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::model::changeset::{Changeset, ChangesetComment};
+use crate::osm::model::tag::Tag;
+use crate::osm::xml::element_iterator::ElementIterator;
+
+/// Iterate over changesets in a changeset dump, such as changesets-latest.osm.bz2
+///
+/// The file is parsed as a stream, so only the changeset currently being parsed, with its tags and
+/// discussion, is kept in memory.
+pub struct ChangesetIterator {
+    reader: quick_xml::Reader<Box<dyn BufRead + Send>>,
+    buf: Vec<u8>,
+}
+
+impl ChangesetIterator {
+    pub(crate) fn new(input: Box<dyn BufRead + Send>) -> ChangesetIterator {
+        let mut reader = quick_xml::Reader::from_reader(input);
+        reader.expand_empty_elements(true);
+        reader.trim_text(true);
+        ChangesetIterator {
+            reader,
+            buf: Vec::with_capacity(1024),
+        }
+    }
+
+    fn timestamp(attributes: &HashMap<String, String>, name: &str) -> Result<Option<i64>, anyhow::Error> {
+        attributes.get(name)
+            .map(|timestamp| iso8601_to_timestamp_millis(timestamp))
+            .transpose()
+    }
+
+    /// The bounding box is present only if all four coordinates are
+    fn bounding_box(attributes: &HashMap<String, String>) -> Result<Option<BoundingBox>, anyhow::Error> {
+        let coordinates = ["min_lon", "min_lat", "max_lon", "max_lat"].iter()
+            .map(|name| {
+                attributes.get(*name)
+                    .map(|value| value.parse::<f64>().map_err(|_| anyhow!("Invalid value of attribute {}: {}", name, value)))
+                    .transpose()
+            })
+            .collect::<Result<Vec<Option<f64>>, anyhow::Error>>()?;
+        match coordinates.as_slice() {
+            [Some(left), Some(bottom), Some(right), Some(top)] => {
+                Ok(Some(BoundingBox::new(*left, *bottom, *right, *top)))
+            }
+            _ => {
+                Ok(None)
+            }
+        }
+    }
+
+    /// Read the text content of the current element until its end tag
+    fn read_text(&mut self, end: &[u8]) -> Result<String, anyhow::Error> {
+        let mut text = String::new();
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into(&mut self.buf)? {
+                Event::Text(t) => {
+                    text.push_str(&t.unescape()?);
+                }
+                Event::CData(c) => {
+                    text.push_str(&String::from_utf8(c.into_inner().to_vec())?);
+                }
+                Event::End(e) if e.name().as_ref() == end => {
+                    break;
+                }
+                Event::Eof => {
+                    return Err(anyhow!("Unexpected end of file"));
+                }
+                _ => {}
+            }
+        }
+        Ok(text)
+    }
+
+    fn read_comment(&mut self, mut attributes: HashMap<String, String>) -> Result<ChangesetComment, anyhow::Error> {
+        let date = Self::timestamp(&attributes, "date")?
+            .ok_or(anyhow!("Missing attribute date"))?;
+        let uid = ElementIterator::parse(&attributes, "uid", -1)?;
+        let user = attributes.remove("user").unwrap_or_default();
+        let mut text = String::new();
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(start) if start.name().as_ref() == b"text" => {
+                    text = self.read_text(b"text")?;
+                }
+                Event::End(e) if e.name().as_ref() == b"comment" => {
+                    break;
+                }
+                Event::Eof => {
+                    return Err(anyhow!("Unexpected end of file"));
+                }
+                _ => {}
+            }
+        }
+        Ok(ChangesetComment::new(date, uid, user, text))
+    }
+
+    fn read_changeset(&mut self, mut attributes: HashMap<String, String>) -> Result<Changeset, anyhow::Error> {
+        let mut tags = Vec::new();
+        let mut comments = Vec::new();
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(start) => {
+                    match start.name().as_ref() {
+                        b"tag" => {
+                            let mut tag_attributes = ElementIterator::attributes(&start)?;
+                            let k = tag_attributes.remove("k").ok_or(anyhow!("Missing attribute k"))?;
+                            let v = tag_attributes.remove("v").ok_or(anyhow!("Missing attribute v"))?;
+                            tags.push(Tag::new(k, v));
+                        }
+                        b"comment" => {
+                            let comment_attributes = ElementIterator::attributes(&start)?;
+                            comments.push(self.read_comment(comment_attributes)?);
+                        }
+                        _ => {}
+                    }
+                }
+                Event::End(e) if e.name().as_ref() == b"changeset" => {
+                    break;
+                }
+                Event::Eof => {
+                    return Err(anyhow!("Unexpected end of file"));
+                }
+                _ => {}
+            }
+        }
+
+        let created_at = Self::timestamp(&attributes, "created_at")?
+            .ok_or(anyhow!("Missing attribute created_at"))?;
+        let closed_at = Self::timestamp(&attributes, "closed_at")?;
+        let mut changeset = Changeset::new(
+            ElementIterator::required(&attributes, "id")?,
+            created_at,
+            closed_at,
+            ElementIterator::parse(&attributes, "open", false)?,
+            ElementIterator::parse(&attributes, "uid", -1)?,
+            attributes.remove("user").unwrap_or_default(),
+            Self::bounding_box(&attributes)?,
+            ElementIterator::parse(&attributes, "num_changes", 0)?,
+            ElementIterator::parse(&attributes, "comments_count", comments.len() as i32)?,
+            tags,
+        );
+        changeset.with_comments(comments);
+        Ok(changeset)
+    }
+
+    fn next_changeset(&mut self) -> Result<Option<Changeset>, anyhow::Error> {
+        loop {
+            self.buf.clear();
+            let attributes = match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(start) if start.name().as_ref() == b"changeset" => {
+                    ElementIterator::attributes(&start)?
+                }
+                Event::Eof => {
+                    return Ok(None);
+                }
+                _ => {
+                    continue;
+                }
+            };
+            return self.read_changeset(attributes).map(Some);
+        }
+    }
+}
+
+impl Iterator for ChangesetIterator {
+    type Item = Changeset;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.reader.buffer_position();
+        self.next_changeset()
+            .with_context(|| anyhow!("Failed to parse changeset near byte {}", position))
+            .unwrap_or_else(|e| panic!("{:?}", e))
+    }
+}
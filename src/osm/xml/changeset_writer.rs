@@ -0,0 +1,122 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::osm::converters::timestamp_to_iso8601_seconds;
+use crate::osm::model::changeset::Changeset;
+use crate::osm::pbf::file_info::FileInfo;
+use crate::osm::xml::compression_type::CompressionType;
+use crate::osm::xml::writer::{escape, Writer};
+
+/// Changeset dump writer
+///
+/// Write changesets with their tags and discussions in the format of the changesets-latest.osm.bz2
+/// planet dump, optionally compressed with gzip or bzip2.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::xml;
+/// use osm_io::osm::xml::changeset_writer::ChangesetWriter;
+/// use osm_io::osm::xml::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/changesets.osm");
+///     let output_path = PathBuf::from("./target/results/changesets.osm.bz2");
+///     let reader = xml::reader::Reader::new(&input_path)?;
+///     let mut writer = ChangesetWriter::new(output_path, CompressionType::Bzip2)?;
+///     writer.write_header()?;
+///     for changeset in reader.changesets()? {
+///         writer.write_changeset(changeset)?;
+///     }
+///     writer.close()?;
+///     Ok(())
+/// }
+/// ```
+pub struct ChangesetWriter {
+    writer: Writer,
+}
+
+impl ChangesetWriter {
+    pub fn new(path: PathBuf, compression_type: CompressionType) -> Result<ChangesetWriter, anyhow::Error> {
+        Ok(
+            ChangesetWriter {
+                writer: Writer::from_file_info(path, FileInfo::default(), compression_type)?,
+            }
+        )
+    }
+
+    /// Write the XML declaration and the root element
+    ///
+    /// Must be called before writing changesets.
+    pub fn write_header(&mut self) -> Result<(), anyhow::Error> {
+        self.writer.write_header()
+    }
+
+    /// Write changeset
+    ///
+    /// The user and uid attributes are omitted for an anonymous changeset, that is one with a
+    /// negative uid, and the bounding box attributes for a changeset without one.
+    pub fn write_changeset(&mut self, changeset: Changeset) -> Result<(), anyhow::Error> {
+        let mut buf = String::with_capacity(512);
+        Self::format_changeset(&changeset, &mut buf)?;
+        self.writer.output()?.write_all(buf.as_bytes())?;
+        Ok(())
+    }
+
+    /// Write the closing tag of the root element and flush the compression stream
+    ///
+    /// Must be called in the end. The writer can not be used after it was closed
+    pub fn close(&mut self) -> Result<(), anyhow::Error> {
+        self.writer.close()
+    }
+
+    /// Output path
+    pub fn path(&self) -> &PathBuf {
+        self.writer.path()
+    }
+
+    fn format_changeset(changeset: &Changeset, buf: &mut String) -> Result<(), anyhow::Error> {
+        buf.push_str(&format!("  <changeset id=\"{}\"", changeset.id()));
+        buf.push_str(&format!(" created_at=\"{}\"", timestamp_to_iso8601_seconds(changeset.created_at() * 1000)?));
+        if let Some(closed_at) = changeset.closed_at() {
+            buf.push_str(&format!(" closed_at=\"{}\"", timestamp_to_iso8601_seconds(closed_at * 1000)?));
+        }
+        buf.push_str(&format!(" open=\"{}\"", changeset.open()));
+        if changeset.uid() >= 0 {
+            buf.push_str(&format!(" user=\"{}\" uid=\"{}\"", escape(changeset.user()), changeset.uid()));
+        }
+        if let Some(bounding_box) = changeset.bounding_box() {
+            buf.push_str(
+                &format!(
+                    " min_lat=\"{:.7}\" min_lon=\"{:.7}\" max_lat=\"{:.7}\" max_lon=\"{:.7}\"",
+                    bounding_box.bottom(),
+                    bounding_box.left(),
+                    bounding_box.top(),
+                    bounding_box.right(),
+                )
+            );
+        }
+        buf.push_str(&format!(" num_changes=\"{}\" comments_count=\"{}\"", changeset.num_changes(), changeset.comments_count()));
+        if changeset.tags().is_empty() && changeset.comments().is_empty() {
+            buf.push_str("/>\n");
+            return Ok(());
+        }
+        buf.push_str(">\n");
+        for tag in changeset.tags() {
+            buf.push_str(&format!("    <tag k=\"{}\" v=\"{}\"/>\n", escape(tag.k()), escape(tag.v())));
+        }
+        if !changeset.comments().is_empty() {
+            buf.push_str("    <discussion>\n");
+            for comment in changeset.comments() {
+                buf.push_str("      <comment");
+                if comment.uid() >= 0 {
+                    buf.push_str(&format!(" uid=\"{}\" user=\"{}\"", comment.uid(), escape(comment.user())));
+                }
+                buf.push_str(&format!(" date=\"{}\">\n", timestamp_to_iso8601_seconds(comment.date() * 1000)?));
+                buf.push_str(&format!("        <text>{}</text>\n", escape(comment.text())));
+                buf.push_str("      </comment>\n");
+            }
+            buf.push_str("    </discussion>\n");
+        }
+        buf.push_str("  </changeset>\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use std::path::Path;
+
+/// Compression applied to an OSM XML or OPL file
+#[derive(Clone, Debug, PartialEq)]
+pub enum CompressionType {
+    Uncompressed,
+    Gzip,
+    Bzip2,
+}
+
+impl CompressionType {
+    /// Guess the compression type from the file extension, e.g. *.osm.gz, *.osm.bz2 or *.osm
+    pub fn from_path(path: &Path) -> CompressionType {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("gz") => {
+                CompressionType::Gzip
+            }
+            Some("bz2") => {
+                CompressionType::Bzip2
+            }
+            _ => {
+                CompressionType::Uncompressed
+            }
+        }
+    }
+}
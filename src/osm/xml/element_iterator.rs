@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use quick_xml::events::{BytesStart, Event};
+
+use crate::osm::converters::iso8601_to_timestamp_millis;
+use crate::osm::model::coordinate::Coordinate;
+use crate::osm::model::element::Element;
+use crate::osm::model::node::Node;
+use crate::osm::model::relation::{Member, MemberData, Relation};
+use crate::osm::model::tag::Tag;
+use crate::osm::model::way::Way;
+
+#[derive(Default)]
+struct Children {
+    tags: Vec<Tag>,
+    refs: Vec<i64>,
+    members: Vec<Member>,
+}
+
+/// Iterate over elements in *.osm file
+///
+/// The file is parsed as a stream, so only the element currently being parsed is kept in memory.
+pub struct ElementIterator {
+    reader: quick_xml::Reader<Box<dyn BufRead + Send>>,
+    buf: Vec<u8>,
+}
+
+impl ElementIterator {
+    pub(crate) fn new(input: Box<dyn BufRead + Send>) -> ElementIterator {
+        let mut reader = quick_xml::Reader::from_reader(input);
+        reader.expand_empty_elements(true);
+        reader.trim_text(true);
+        ElementIterator {
+            reader,
+            buf: Vec::with_capacity(1024),
+        }
+    }
+
+    pub(crate) fn attributes(start: &BytesStart) -> Result<HashMap<String, String>, anyhow::Error> {
+        let mut attributes = HashMap::new();
+        for attribute in start.attributes() {
+            let attribute = attribute?;
+            attributes.insert(
+                String::from_utf8(attribute.key.as_ref().to_vec())?,
+                attribute.unescape_value()?.to_string(),
+            );
+        }
+        Ok(attributes)
+    }
+
+    pub(crate) fn parse<T: FromStr>(attributes: &HashMap<String, String>, name: &str, default: T) -> Result<T, anyhow::Error> {
+        match attributes.get(name) {
+            None => {
+                Ok(default)
+            }
+            Some(value) => {
+                value.parse::<T>()
+                    .map_err(|_| anyhow!("Invalid value of attribute {}: {}", name, value))
+            }
+        }
+    }
+
+    pub(crate) fn required<T: FromStr>(attributes: &HashMap<String, String>, name: &str) -> Result<T, anyhow::Error> {
+        let value = attributes.get(name)
+            .ok_or(anyhow!("Missing attribute {}", name))?;
+        value.parse::<T>()
+            .map_err(|_| anyhow!("Invalid value of attribute {}: {}", name, value))
+    }
+
+    fn timestamp(attributes: &HashMap<String, String>) -> Result<i64, anyhow::Error> {
+        match attributes.get("timestamp") {
+            None => {
+                Ok(-1)
+            }
+            Some(timestamp) => {
+                iso8601_to_timestamp_millis(timestamp)
+            }
+        }
+    }
+
+    fn visible(attributes: &HashMap<String, String>) -> bool {
+        !matches!(attributes.get("visible").map(|v| v.as_str()), Some("false"))
+    }
+
+    fn user(attributes: &mut HashMap<String, String>) -> String {
+        attributes.remove("user").unwrap_or_default()
+    }
+
+    /// Read the children of the current element until its end tag
+    fn read_children(&mut self, end: &[u8]) -> Result<Children, anyhow::Error> {
+        let mut children = Children::default();
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(start) => {
+                    match start.name().as_ref() {
+                        b"tag" => {
+                            let mut attributes = Self::attributes(&start)?;
+                            let k = attributes.remove("k").ok_or(anyhow!("Missing attribute k"))?;
+                            let v = attributes.remove("v").ok_or(anyhow!("Missing attribute v"))?;
+                            children.tags.push(Tag::new(k, v));
+                        }
+                        b"nd" => {
+                            let attributes = Self::attributes(&start)?;
+                            children.refs.push(Self::required::<i64>(&attributes, "ref")?);
+                        }
+                        b"member" => {
+                            let mut attributes = Self::attributes(&start)?;
+                            let id = Self::required::<i64>(&attributes, "ref")?;
+                            let role = attributes.remove("role").unwrap_or_default();
+                            let member = MemberData::new(id, role);
+                            match attributes.get("type").map(|t| t.as_str()) {
+                                Some("node") => {
+                                    children.members.push(Member::Node { member });
+                                }
+                                Some("way") => {
+                                    children.members.push(Member::Way { member });
+                                }
+                                Some("relation") => {
+                                    children.members.push(Member::Relation { member });
+                                }
+                                t => {
+                                    return Err(anyhow!("Invalid relation member type: {:?}", t));
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::End(e) if e.name().as_ref() == end => {
+                    break;
+                }
+                Event::Eof => {
+                    return Err(anyhow!("Unexpected end of file"));
+                }
+                _ => {}
+            }
+        }
+        Ok(children)
+    }
+
+    fn read_node(&mut self, mut attributes: HashMap<String, String>) -> Result<Element, anyhow::Error> {
+        let children = self.read_children(b"node")?;
+        let coordinate = Coordinate::new(
+            Self::parse::<f64>(&attributes, "lat", 0.0)?,
+            Self::parse::<f64>(&attributes, "lon", 0.0)?,
+        );
+        Ok(
+            Element::Node {
+                node: Node::new(
+                    Self::required(&attributes, "id")?,
+                    Self::parse(&attributes, "version", 0)?,
+                    coordinate,
+                    Self::timestamp(&attributes)?,
+                    Self::parse(&attributes, "changeset", -1)?,
+                    Self::parse(&attributes, "uid", -1)?,
+                    Self::user(&mut attributes),
+                    Self::visible(&attributes),
+                    children.tags,
+                )
+            }
+        )
+    }
+
+    fn read_way(&mut self, mut attributes: HashMap<String, String>) -> Result<Element, anyhow::Error> {
+        let children = self.read_children(b"way")?;
+        Ok(
+            Element::Way {
+                way: Way::new(
+                    Self::required(&attributes, "id")?,
+                    Self::parse(&attributes, "version", 0)?,
+                    Self::timestamp(&attributes)?,
+                    Self::parse(&attributes, "changeset", -1)?,
+                    Self::parse(&attributes, "uid", -1)?,
+                    Self::user(&mut attributes),
+                    Self::visible(&attributes),
+                    children.refs,
+                    children.tags,
+                )
+            }
+        )
+    }
+
+    fn read_relation(&mut self, mut attributes: HashMap<String, String>) -> Result<Element, anyhow::Error> {
+        let children = self.read_children(b"relation")?;
+        Ok(
+            Element::Relation {
+                relation: Relation::new(
+                    Self::required(&attributes, "id")?,
+                    Self::parse(&attributes, "version", 0)?,
+                    Self::timestamp(&attributes)?,
+                    Self::parse(&attributes, "changeset", -1)?,
+                    Self::parse(&attributes, "uid", -1)?,
+                    Self::user(&mut attributes),
+                    Self::visible(&attributes),
+                    children.members,
+                    children.tags,
+                )
+            }
+        )
+    }
+
+    fn next_element(&mut self) -> Result<Option<Element>, anyhow::Error> {
+        loop {
+            self.buf.clear();
+            let (name, attributes) = match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(start) => {
+                    (start.name().as_ref().to_vec(), Self::attributes(&start)?)
+                }
+                Event::Eof => {
+                    return Ok(None);
+                }
+                _ => {
+                    continue;
+                }
+            };
+            match name.as_slice() {
+                b"node" => {
+                    return self.read_node(attributes).map(Some);
+                }
+                b"way" => {
+                    return self.read_way(attributes).map(Some);
+                }
+                b"relation" => {
+                    return self.read_relation(attributes).map(Some);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Iterator for ElementIterator {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.reader.buffer_position();
+        self.next_element()
+            .with_context(|| anyhow!("Failed to parse OSM XML element near byte {}", position))
+            .unwrap_or_else(|e| panic!("{:?}", e))
+    }
+}
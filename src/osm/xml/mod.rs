@@ -0,0 +1,7 @@
+pub mod reader;
+pub mod element_iterator;
+pub mod writer;
+pub mod compression_type;
+pub mod change_writer;
+pub mod changeset_iterator;
+pub mod changeset_writer;
@@ -0,0 +1,201 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use bzip2::read::MultiBzDecoder;
+use flate2::read::MultiGzDecoder;
+use quick_xml::events::Event;
+
+use crate::osm::model::bounding_box::BoundingBox;
+use crate::osm::pbf::file_info::FileInfo;
+use crate::osm::xml::changeset_iterator::ChangesetIterator;
+use crate::osm::xml::compression_type::CompressionType;
+use crate::osm::xml::element_iterator::ElementIterator;
+
+/// *.osm, *.osm.gz and *.osm.bz2 file reader
+///
+/// Prepare the OSM XML file for reading. The actual reading is performed by associated iterators.
+#[derive(Debug, Clone)]
+pub struct Reader {
+    path: PathBuf,
+    compression_type: CompressionType,
+    info: FileInfo,
+}
+
+impl Reader {
+    /// Create a new Reader
+    ///
+    /// * path - a path to a valid *.osm, *.osm.gz or *.osm.bz2 file
+    ///
+    /// The compression is deduced from the file extension.
+    ///
+    /// Example:
+    /// ```
+    /// use std::path::PathBuf;
+    /// use osm_io::osm::xml::reader::Reader;
+    /// let input_path = PathBuf::from("./map.osm.bz2");
+    /// let reader = Reader::new(&input_path);
+    /// ```
+    pub fn new(path: &Path) -> Result<Reader, anyhow::Error> {
+        let mut reader = Reader {
+            path: path.to_path_buf(),
+            compression_type: CompressionType::from_path(path),
+            info: FileInfo::default(),
+        };
+        reader.info = reader.read_info()
+            .with_context(|| anyhow!("Failed to parse file header, path: {}", path.display()))?;
+        Ok(reader)
+    }
+
+    pub(crate) fn open(path: &Path, compression_type: &CompressionType) -> Result<Box<dyn BufRead + Send>, anyhow::Error> {
+        let file = File::open(path)
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        let input: Box<dyn BufRead + Send> = match compression_type {
+            CompressionType::Uncompressed => {
+                Box::new(BufReader::new(file))
+            }
+            CompressionType::Gzip => {
+                Box::new(BufReader::new(MultiGzDecoder::new(file)))
+            }
+            CompressionType::Bzip2 => {
+                Box::new(BufReader::new(MultiBzDecoder::new(file)))
+            }
+        };
+        Ok(input)
+    }
+
+    /// Read the root element and the optional bounds element that precede the data
+    fn read_info(&self) -> Result<FileInfo, anyhow::Error> {
+        let mut info = FileInfo::default();
+        let mut reader = quick_xml::Reader::from_reader(Self::open(&self.path, &self.compression_type)?);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(start) | Event::Empty(start) => {
+                    match start.name().as_ref() {
+                        b"osm" | b"osmChange" => {
+                            let attributes = ElementIterator::attributes(&start)?;
+                            if let Some(generator) = attributes.get("generator") {
+                                info.with_writingprogram_str(generator);
+                            }
+                        }
+                        b"bounds" => {
+                            let attributes = ElementIterator::attributes(&start)?;
+                            info.with_bounding_box(
+                                &Some(
+                                    BoundingBox::new(
+                                        ElementIterator::parse(&attributes, "minlon", -180.0)?,
+                                        ElementIterator::parse(&attributes, "minlat", -90.0)?,
+                                        ElementIterator::parse(&attributes, "maxlon", 180.0)?,
+                                        ElementIterator::parse(&attributes, "maxlat", 90.0)?,
+                                    )
+                                )
+                            );
+                        }
+                        b"bound" => {
+                            // osmosis variant: <bound box="minlat,minlon,maxlat,maxlon" origin="..."/>
+                            let attributes = ElementIterator::attributes(&start)?;
+                            let bound_box = attributes.get("box").ok_or(anyhow!("Missing attribute box"))?;
+                            let parts = bound_box.split(',')
+                                .map(|s| s.trim().parse::<f64>())
+                                .collect::<Result<Vec<f64>, _>>()?;
+                            if parts.len() != 4 {
+                                return Err(anyhow!("Invalid bound box: {}", bound_box));
+                            }
+                            info.with_bounding_box(&Some(BoundingBox::new(parts[1], parts[0], parts[3], parts[2])));
+                        }
+                        b"node" | b"way" | b"relation" | b"changeset" => {
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Eof => {
+                    break;
+                }
+                _ => {}
+            }
+        }
+        Ok(info)
+    }
+
+    /// Iterator used to iterate over elements.
+    /// Example:
+    /// ```
+    /// use std::path::PathBuf;
+    /// use osm_io::osm::model::element::Element;
+    /// use osm_io::osm::xml;
+    /// fn example() -> Result<(), anyhow::Error> {
+    ///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.bz2");
+    ///     let reader = xml::reader::Reader::new(&input_path)?;
+    ///
+    ///     let mut nodes = 0usize;
+    ///     let mut ways = 0usize;
+    ///     let mut relations = 0usize;
+    ///
+    ///     for element in reader.elements()? {
+    ///         match element {
+    ///             Element::Node { node } => {
+    ///                 nodes += 1;
+    ///             }
+    ///             Element::Way { way } => {
+    ///                 ways += 1;
+    ///             }
+    ///             Element::Relation { relation } => {
+    ///                 relations += 1;
+    ///             }
+    ///             Element::Sentinel => {
+    ///             }
+    ///         }
+    ///     }
+    ///
+    ///     println!("nodes: {}", nodes);
+    ///     println!("ways: {}", ways);
+    ///     println!("relations: {}", relations);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn elements(&self) -> Result<ElementIterator, anyhow::Error> {
+        Ok(
+            ElementIterator::new(Self::open(&self.path, &self.compression_type)?)
+        )
+    }
+
+    /// Create iterator over the changesets of a changeset dump, such as changesets-latest.osm.bz2
+    ///
+    /// Each changeset carries its tags and discussion comments.
+    /// Example:
+    /// ```
+    /// use std::path::PathBuf;
+    /// use osm_io::osm::xml::reader::Reader;
+    /// fn example() -> Result<(), anyhow::Error> {
+    ///     let reader = Reader::new(&PathBuf::from("./tests/fixtures/changesets.osm"))?;
+    ///     for changeset in reader.changesets()? {
+    ///         println!("{}: {} comments", changeset.id(), changeset.comments().len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn changesets(&self) -> Result<ChangesetIterator, anyhow::Error> {
+        Ok(
+            ChangesetIterator::new(Self::open(&self.path, &self.compression_type)?)
+        )
+    }
+
+    /// Header data. The bounding box is taken from the bounds element and the writing program
+    /// from the generator attribute
+    pub fn info(&self) -> &FileInfo {
+        &self.info
+    }
+
+    pub fn compression_type(&self) -> &CompressionType {
+        &self.compression_type
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
@@ -0,0 +1,366 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context};
+use bzip2::write::BzEncoder;
+use flate2::write::GzEncoder;
+
+use crate::osm::converters::timestamp_to_iso8601_seconds;
+use crate::osm::model::element::Element;
+use crate::osm::model::node::Node;
+use crate::osm::model::relation::{Member, Relation};
+use crate::osm::model::tag::Tag;
+use crate::osm::model::way::Way;
+use crate::osm::pbf::file_info::FileInfo;
+use crate::osm::xml::compression_type::CompressionType;
+
+pub(crate) enum Output {
+    Uncompressed(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Bzip2(BzEncoder<BufWriter<File>>),
+}
+
+impl Output {
+    pub(crate) fn new(file: File, compression_type: &CompressionType) -> Output {
+        let buffered = BufWriter::new(file);
+        match compression_type {
+            CompressionType::Uncompressed => {
+                Output::Uncompressed(buffered)
+            }
+            CompressionType::Gzip => {
+                Output::Gzip(GzEncoder::new(buffered, flate2::Compression::default()))
+            }
+            CompressionType::Bzip2 => {
+                Output::Bzip2(BzEncoder::new(buffered, bzip2::Compression::default()))
+            }
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<(), anyhow::Error> {
+        let mut buffered = match self {
+            Output::Uncompressed(buffered) => {
+                buffered
+            }
+            Output::Gzip(encoder) => {
+                encoder.finish()?
+            }
+            Output::Bzip2(encoder) => {
+                encoder.finish()?
+            }
+        };
+        buffered.flush()?;
+        Ok(())
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Output::Uncompressed(w) => {
+                w.write(buf)
+            }
+            Output::Gzip(w) => {
+                w.write(buf)
+            }
+            Output::Bzip2(w) => {
+                w.write(buf)
+            }
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Output::Uncompressed(w) => {
+                w.flush()
+            }
+            Output::Gzip(w) => {
+                w.flush()
+            }
+            Output::Bzip2(w) => {
+                w.flush()
+            }
+        }
+    }
+}
+
+/// *.osm file writer
+///
+/// Write an OSM XML 0.6 file, optionally compressed with gzip or bzip2.
+/// Example:
+/// ```
+/// use std::path::PathBuf;
+/// use osm_io::osm::pbf;
+/// use osm_io::osm::xml;
+/// use osm_io::osm::xml::compression_type::CompressionType;
+/// fn example() -> Result<(), anyhow::Error> {
+///     let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+///     let output_path = PathBuf::from("./target/results/niue-230109.osm.bz2");
+///     let reader = pbf::reader::Reader::new(&input_path)?;
+///     let mut writer = xml::writer::Writer::from_file_info(
+///         output_path,
+///         reader.info().clone(),
+///         CompressionType::Bzip2,
+///     )?;
+///     writer.with_metadata(false);
+///
+///     writer.write_header()?;
+///     for element in reader.elements()? {
+///         writer.write_element(element)?;
+///     }
+///     writer.close()?;
+///     Ok(())
+/// }
+/// ```
+pub struct Writer {
+    path: PathBuf,
+    file_info: FileInfo,
+    metadata: bool,
+    output: Option<Output>,
+}
+
+impl Writer {
+    /// Create a new [Writer] from [FileInfo]
+    ///
+    /// The bounding box and the writing program are taken from the file_info. User and changeset
+    /// metadata are written by default, see [Writer::with_metadata]
+    pub fn from_file_info(
+        path: PathBuf,
+        file_info: FileInfo,
+        compression_type: CompressionType,
+    ) -> Result<Writer, anyhow::Error> {
+        let file = File::create(path.clone())
+            .with_context(|| anyhow!("path: {}", path.display()))?;
+        Ok(
+            Writer {
+                path,
+                file_info,
+                metadata: true,
+                output: Some(Output::new(file, &compression_type)),
+            }
+        )
+    }
+
+    /// Write or omit version, timestamp, changeset, uid and user attributes
+    pub fn with_metadata(&mut self, metadata: bool) {
+        self.metadata = metadata;
+    }
+
+    pub(crate) fn output(&mut self) -> Result<&mut Output, anyhow::Error> {
+        self.output.as_mut()
+            .ok_or(anyhow!("Writer is closed, path: {}", self.path.display()))
+    }
+
+    /// Write the XML declaration, the root element and the bounds element
+    ///
+    /// Must be called before writing elements.
+    pub fn write_header(&mut self) -> Result<(), anyhow::Error> {
+        let generator = self.file_info.writingprogram().clone()
+            .unwrap_or("osm-io".to_string());
+        let bounds = self.file_info.bounding_box().as_ref().map(|bounding_box| {
+            format!(
+                "  <bounds minlat=\"{:.7}\" minlon=\"{:.7}\" maxlat=\"{:.7}\" maxlon=\"{:.7}\"/>\n",
+                bounding_box.bottom(),
+                bounding_box.left(),
+                bounding_box.top(),
+                bounding_box.right(),
+            )
+        });
+        let output = self.output()?;
+        output.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")?;
+        writeln!(output, "<osm version=\"0.6\" generator=\"{}\">", escape(&generator))?;
+        if let Some(bounds) = bounds {
+            output.write_all(bounds.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Write element
+    ///
+    /// [Element::Sentinel] is ignored
+    pub fn write_element(&mut self, element: Element) -> Result<(), anyhow::Error> {
+        let mut buf = String::with_capacity(256);
+        self.format_element(&element, &mut buf)?;
+        self.output()?.write_all(buf.as_bytes())?;
+        Ok(())
+    }
+
+    /// Write elements
+    pub fn write_elements(&mut self, elements: Vec<Element>) -> Result<(), anyhow::Error> {
+        for element in elements {
+            self.write_element(element)?;
+        }
+        Ok(())
+    }
+
+    /// Write the closing tag of the root element and flush the compression stream
+    ///
+    /// Must be called in the end. The writer can not be used after it was closed
+    pub fn close(&mut self) -> Result<(), anyhow::Error> {
+        self.output()?.write_all(b"</osm>\n")?;
+        self.finish()
+    }
+
+    /// Flush the compression stream without writing the closing tag
+    pub(crate) fn finish(&mut self) -> Result<(), anyhow::Error> {
+        self.output.take()
+            .ok_or(anyhow!("Writer is closed, path: {}", self.path.display()))?
+            .finish()
+    }
+
+    /// Format element without writing it
+    pub(crate) fn format_element(&self, element: &Element, buf: &mut String) -> Result<(), anyhow::Error> {
+        match element {
+            Element::Node { node } => {
+                self.format_node(node, buf)
+            }
+            Element::Way { way } => {
+                self.format_way(way, buf)
+            }
+            Element::Relation { relation } => {
+                self.format_relation(relation, buf)
+            }
+            Element::Sentinel => {
+                Ok(())
+            }
+        }
+    }
+
+    /// Output path
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn format_common(&self, id: i64, version: i32, timestamp: i64, changeset: i64, uid: i32, user: &str, visible: bool, buf: &mut String) -> Result<(), anyhow::Error> {
+        buf.push_str(&format!(" id=\"{}\"", id));
+        if self.metadata {
+            buf.push_str(&format!(" version=\"{}\"", version));
+            if timestamp >= 0 {
+                buf.push_str(&format!(" timestamp=\"{}\"", timestamp_to_iso8601_seconds(timestamp * 1000)?));
+            }
+            if changeset >= 0 {
+                buf.push_str(&format!(" changeset=\"{}\"", changeset));
+            }
+            if uid >= 0 {
+                buf.push_str(&format!(" uid=\"{}\"", uid));
+                buf.push_str(&format!(" user=\"{}\"", escape(user)));
+            }
+        }
+        if !visible || self.file_info.required("HistoricalInformation") {
+            buf.push_str(&format!(" visible=\"{}\"", visible));
+        }
+        Ok(())
+    }
+
+    fn format_tags(tags: &[Tag], buf: &mut String) {
+        for tag in tags {
+            buf.push_str(&format!("    <tag k=\"{}\" v=\"{}\"/>\n", escape(tag.k()), escape(tag.v())));
+        }
+    }
+
+    fn format_node(&self, node: &Node, buf: &mut String) -> Result<(), anyhow::Error> {
+        buf.push_str("  <node");
+        self.format_common(node.id(), node.version(), node.timestamp(), node.changeset(), node.uid(), node.user(), node.visible(), buf)?;
+        if node.visible() {
+            buf.push_str(&format!(" lat=\"{:.7}\" lon=\"{:.7}\"", node.coordinate().lat(), node.coordinate().lon()));
+        }
+        if node.tags().is_empty() {
+            buf.push_str("/>\n");
+        } else {
+            buf.push_str(">\n");
+            Self::format_tags(node.tags(), buf);
+            buf.push_str("  </node>\n");
+        }
+        Ok(())
+    }
+
+    fn format_way(&self, way: &Way, buf: &mut String) -> Result<(), anyhow::Error> {
+        buf.push_str("  <way");
+        self.format_common(way.id(), way.version(), way.timestamp(), way.changeset(), way.uid(), way.user(), way.visible(), buf)?;
+        if way.refs().is_empty() && way.tags().is_empty() {
+            buf.push_str("/>\n");
+        } else {
+            buf.push_str(">\n");
+            for r in way.refs() {
+                buf.push_str(&format!("    <nd ref=\"{}\"/>\n", r));
+            }
+            Self::format_tags(way.tags(), buf);
+            buf.push_str("  </way>\n");
+        }
+        Ok(())
+    }
+
+    fn format_relation(&self, relation: &Relation, buf: &mut String) -> Result<(), anyhow::Error> {
+        buf.push_str("  <relation");
+        self.format_common(relation.id(), relation.version(), relation.timestamp(), relation.changeset(), relation.uid(), relation.user(), relation.visible(), buf)?;
+        if relation.members().is_empty() && relation.tags().is_empty() {
+            buf.push_str("/>\n");
+        } else {
+            buf.push_str(">\n");
+            for member in relation.members() {
+                let (member_type, member_data) = match member {
+                    Member::Node { member } => {
+                        ("node", member)
+                    }
+                    Member::Way { member } => {
+                        ("way", member)
+                    }
+                    Member::Relation { member } => {
+                        ("relation", member)
+                    }
+                };
+                buf.push_str(
+                    &format!(
+                        "    <member type=\"{}\" ref=\"{}\" role=\"{}\"/>\n",
+                        member_type,
+                        member_data.id(),
+                        escape(member_data.role())
+                    )
+                );
+            }
+            Self::format_tags(relation.tags(), buf);
+            buf.push_str("  </relation>\n");
+        }
+        Ok(())
+    }
+}
+
+/// Escape a string for use in a double quoted XML attribute value
+///
+/// Besides the markup characters, tabs and line breaks are escaped as character references so that
+/// they survive attribute value normalization when read back.
+pub fn escape(s: &str) -> Cow<'_, str> {
+    if !s.contains(['&', '<', '>', '"', '\'', '\t', '\n', '\r']) {
+        return Cow::Borrowed(s);
+    }
+    let mut escaped = String::with_capacity(s.len() + 16);
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' => escaped.push_str("&#9;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            _ => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("plain"), "plain");
+        assert!(matches!(escape("plain"), Cow::Borrowed(_)));
+        assert_eq!(escape("Tom & \"Jerry\" <cat's>"), "Tom &amp; &quot;Jerry&quot; &lt;cat&apos;s&gt;");
+        assert_eq!(escape("line\nbreak\tand\rtab"), "line&#10;break&#9;and&#13;tab");
+    }
+}
@@ -1,8 +1,11 @@
 use std::fs;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use json::JsonValue;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::compression_type::CompressionType;
 use osm_io::osm::pbf::reader::Reader;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicI64, Ordering};
@@ -21,6 +24,66 @@ pub fn setup() {
     }
 }
 
+#[allow(dead_code)]
+/// Convert ./tests/fixtures/<name>.opl to ./target/results/<output>
+pub fn opl_fixture_to_pbf(name: &str, output: &str) -> Result<PathBuf, anyhow::Error> {
+    setup();
+    let path = PathBuf::from("./target/results").join(output);
+    let reader = opl::reader::Reader::new(&PathBuf::from(format!("./tests/fixtures/{}.opl", name)))?;
+    let mut writer = pbf::writer::Writer::from_file_info(path.clone(), reader.info().clone(), CompressionType::Zlib)?;
+    writer.write_header()?;
+    for element in reader.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+    Ok(path)
+}
+
+#[allow(dead_code)]
+/// All elements of a *.osm.pbf file without the sentinels
+pub fn read_pbf_elements(path: &Path) -> Result<Vec<Element>, anyhow::Error> {
+    Ok(
+        Reader::new(path)?.elements()?
+            .filter(|element| !matches!(element, Element::Sentinel))
+            .collect()
+    )
+}
+
+#[allow(dead_code)]
+/// Compare two elements including their metadata and the coordinates of visible nodes
+pub fn assert_same_element(expected: &Element, actual: &Element) {
+    assert_eq!(expected, actual);
+    match (expected, actual) {
+        (Element::Node { node: expected }, Element::Node { node: actual }) => {
+            assert_eq!(expected.visible(), actual.visible());
+            if expected.visible() {
+                assert_eq!(expected.coordinate().lat7(), actual.coordinate().lat7());
+                assert_eq!(expected.coordinate().lon7(), actual.coordinate().lon7());
+            }
+            assert_eq!(expected.tags(), actual.tags());
+            assert_eq!(expected.timestamp(), actual.timestamp());
+            assert_eq!(expected.changeset(), actual.changeset());
+            assert_eq!(expected.uid(), actual.uid());
+            assert_eq!(expected.user(), actual.user());
+        }
+        (Element::Way { way: expected }, Element::Way { way: actual }) => {
+            assert_eq!(expected.visible(), actual.visible());
+            assert_eq!(expected.refs(), actual.refs());
+            assert_eq!(expected.tags(), actual.tags());
+            assert_eq!(expected.user(), actual.user());
+        }
+        (Element::Relation { relation: expected }, Element::Relation { relation: actual }) => {
+            assert_eq!(expected.visible(), actual.visible());
+            assert_eq!(expected.members(), actual.members());
+            assert_eq!(expected.tags(), actual.tags());
+            assert_eq!(expected.user(), actual.user());
+        }
+        _ => {
+            panic!("element type mismatch");
+        }
+    }
+}
+
 pub fn read_fixture_analysis(path: &PathBuf) -> JsonValue {
     let fixture_analysis_string = fs::read_to_string(path)
         .with_context(|| format!("path: {}", path.to_string_lossy()))
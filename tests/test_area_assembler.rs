@@ -0,0 +1,124 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use osm_io::osm::geometry::area::{Area, AreaSource};
+use osm_io::osm::geometry::area_assembler::{AreaAssembler, assemble_areas, GeometryProblem, is_area_way};
+use osm_io::osm::index::node_location_index::fill;
+use osm_io::osm::index::sparse_mem_location_index::SparseMemLocationIndex;
+use osm_io::osm::model::coordinate::Coordinate;
+use osm_io::osm::model::element::Element;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+
+fn find(areas: &[Area], source: AreaSource) -> &Area {
+    areas.iter().find(|area| *area.source() == source).unwrap()
+}
+
+#[test]
+fn test_area_assembler() -> Result<(), anyhow::Error> {
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/areas.opl"))?;
+    let index = Arc::new(SparseMemLocationIndex::new());
+    fill(index.as_ref(), reader.elements()?)?;
+
+    let mut assembler = AreaAssembler::new(index);
+    let mut relations = 0;
+    for element in reader.elements()? {
+        if let Element::Relation { relation } = element {
+            if assembler.add_relation(&relation) {
+                relations += 1;
+            }
+        }
+    }
+    assert_eq!(relations, 4);
+
+    let mut results = Vec::new();
+    for element in reader.elements()? {
+        if let Element::Way { way } = element {
+            if let Some(result) = assembler.add_way(&way) {
+                results.push(result);
+            }
+        }
+    }
+    // only the building, the fence is tagged area=no, w22 has no tags and w20, w21 are not closed
+    assert_eq!(results.len(), 1);
+    results.append(&mut assembler.assemble_relations());
+
+    let areas: Vec<Area> = results.iter().filter_map(|r| r.as_ref().ok().cloned()).collect();
+    let invalid: Vec<_> = results.iter().filter_map(|r| r.as_ref().err().cloned()).collect();
+    assert_eq!(areas.len(), 3);
+    assert_eq!(invalid.len(), 2);
+
+    let building = find(&areas, AreaSource::Way(30));
+    assert_eq!(building.id(), 60);
+    assert_eq!(building.polygons().len(), 1);
+    assert!(building.polygons()[0].outer().is_counter_clockwise());
+    assert_eq!(building.area(), 1.0);
+
+    let forest = find(&areas, AreaSource::Relation(10));
+    assert_eq!(forest.id(), 21);
+    assert_eq!(forest.tags().len(), 1);
+    assert_eq!(forest.tags()[0].k(), "landuse");
+    assert_eq!(forest.polygons().len(), 1);
+    let polygon = &forest.polygons()[0];
+    assert_eq!(polygon.outer().coordinates().len(), 5);
+    assert_eq!(polygon.inners().len(), 1);
+    assert!(polygon.outer().is_counter_clockwise());
+    assert!(!polygon.inners()[0].is_counter_clockwise());
+    assert_eq!(forest.area(), 96.0);
+    assert!(forest.contains(&Coordinate::new(1.0, 1.0)));
+    assert!(!forest.contains(&Coordinate::new(5.0, 5.0)));
+
+    let boundary = find(&areas, AreaSource::Relation(13));
+    assert_eq!(boundary.polygons().len(), 2);
+    assert_eq!(boundary.area(), 5.0);
+
+    for report in invalid {
+        match report.source() {
+            AreaSource::Relation(11) => {
+                assert_eq!(report.problem(), &GeometryProblem::UnclosedRing { first_node_id: 11, last_node_id: 12 });
+            }
+            AreaSource::Relation(12) => {
+                assert_eq!(report.problem(), &GeometryProblem::MissingWay { way_id: 99 });
+                assert_eq!(report.to_string(), "invalid geometry of relation 12: missing way 99");
+            }
+            source => {
+                panic!("unexpected report for {}", source);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_area_rules() -> Result<(), anyhow::Error> {
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/area-rules.opl"))?;
+    let area_way_ids: Vec<i64> = reader.elements()?
+        .filter_map(|element| match element {
+            Element::Way { way } if is_area_way(&way) => Some(way.id()),
+            _ => None,
+        })
+        .collect();
+    // closed highways, barriers, canals and coastlines are lines unless tagged area=yes, leisure=park
+    // is tagged area=no and w50 is not closed
+    assert_eq!(area_way_ids, vec![41, 42, 44, 47, 49]);
+    Ok(())
+}
+
+#[test]
+fn test_assemble_areas_from_pbf() -> Result<(), anyhow::Error> {
+    let reader = pbf::reader::Reader::new(&PathBuf::from("./tests/fixtures/niue-230109.osm.pbf"))?;
+    let (areas, invalid) = assemble_areas(&reader, Arc::new(SparseMemLocationIndex::new()), 4)?;
+    assert!(!areas.is_empty());
+    for area in &areas {
+        assert!(area.area() > 0.0);
+        for polygon in area.polygons() {
+            assert!(polygon.outer().is_closed());
+            assert!(polygon.inners().iter().all(|inner| inner.is_closed()));
+        }
+    }
+    assert_eq!(areas.len(), 2282);
+    assert_eq!(areas.iter().filter(|area| matches!(area.source(), AreaSource::Relation(_))).count(), 125);
+    // the extract is reference complete, so every area relation is assembled
+    assert!(invalid.is_empty(), "{}", invalid.iter().map(|report| report.to_string()).collect::<Vec<String>>().join("\n"));
+    Ok(())
+}
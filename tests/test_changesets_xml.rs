@@ -0,0 +1,168 @@
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use osm_io::osm::apidb_dump;
+use osm_io::osm::converters::iso8601_to_timestamp_millis;
+use osm_io::osm::model::changeset::Changeset;
+use osm_io::osm::opl;
+use osm_io::osm::xml;
+use osm_io::osm::xml::changeset_writer::ChangesetWriter;
+use osm_io::osm::xml::compression_type::CompressionType;
+
+#[allow(dead_code)]
+mod common;
+
+fn read_changesets(path: &Path) -> Result<Vec<Changeset>, anyhow::Error> {
+    Ok(xml::reader::Reader::new(path)?.changesets()?.collect())
+}
+
+#[test]
+fn test_changesets_xml_reader() -> Result<(), anyhow::Error> {
+    let reader = xml::reader::Reader::new(&PathBuf::from("./tests/fixtures/changesets.osm"))?;
+    assert_eq!(reader.info().writingprogram(), &Some("planet-dump-ng 1.2.4".to_string()));
+    let changesets: Vec<Changeset> = reader.changesets()?.collect();
+    assert_eq!(changesets.iter().map(|changeset| changeset.id()).collect::<Vec<i64>>(), vec![1, 2, 907211, 907212, 130000000, 130000001]);
+
+    let anonymous = &changesets[1];
+    assert_eq!(anonymous.uid(), -1);
+    assert!(anonymous.user().is_empty());
+    assert_eq!(anonymous.num_changes(), 8);
+
+    let discussed = &changesets[2];
+    assert_eq!(discussed.user(), "Sarah & \"Jo\"");
+    assert_eq!(discussed.created_at(), iso8601_to_timestamp_millis("2009-04-23T08:05:56Z")?);
+    assert_eq!(discussed.closed_at(), Some(discussed.created_at() + 100_000));
+    assert!(!discussed.open());
+    let bounding_box = discussed.bounding_box().as_ref().unwrap();
+    assert_eq!(bounding_box.left(), -169.93);
+    assert_eq!(bounding_box.top(), -19.05);
+    assert_eq!(discussed.tags().len(), 2);
+    assert_eq!(discussed.tags()[1].v(), "Alofi <roads>");
+    assert_eq!(discussed.comments_count(), 2);
+    let comments = discussed.comments();
+    assert_eq!(comments.len(), 2);
+    assert_eq!((comments[0].uid(), comments[0].user().as_str()), (1, "Steve"));
+    assert_eq!(comments[0].date(), iso8601_to_timestamp_millis("2014-05-12T10:01:00Z")?);
+    assert_eq!(comments[0].text(), "Thanks! Is the road at \"Tuapa\" paved?\nSecond line");
+    assert_eq!(comments[1].text(), "Yes & it is.");
+
+    let empty = &changesets[3];
+    assert!(empty.bounding_box().is_none());
+    assert_eq!(empty.tags().len(), 1);
+    assert!(empty.comments().is_empty());
+
+    assert!(changesets[4].open());
+    assert_eq!(changesets[4].closed_at(), Some(iso8601_to_timestamp_millis("2023-01-09T01:40:00Z")?));
+    let open = &changesets[5];
+    assert!(open.open());
+    assert!(open.closed_at().is_none());
+    assert!(open.bounding_box().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_changesets_xml_writer() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/changesets.osm");
+    let expected = read_changesets(&input_path)?;
+    for (output_path, compression_type) in [
+        ("./target/results/changesets.osm", CompressionType::Uncompressed),
+        ("./target/results/changesets.osm.gz", CompressionType::Gzip),
+        ("./target/results/changesets.osm.bz2", CompressionType::Bzip2),
+    ] {
+        let output_path = PathBuf::from(output_path);
+        let mut writer = ChangesetWriter::new(output_path.clone(), compression_type)?;
+        writer.write_header()?;
+        for changeset in xml::reader::Reader::new(&input_path)?.changesets()? {
+            writer.write_changeset(changeset)?;
+        }
+        writer.close()?;
+        assert_eq!(read_changesets(&output_path)?, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_changesets_apidb() -> Result<(), anyhow::Error> {
+    common::setup();
+    let dump_path = PathBuf::from("./target/results/history-sample-changesets");
+    let mut writer = apidb_dump::write::writer::Writer::new(dump_path.clone(), 0)?;
+    for element in opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample.opl"))?.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+
+    // public.changeset_comments (id, changeset_id, author_id, body, created_at, visible)
+    let mut changeset_comments = OpenOptions::new().append(true).open(dump_path.join("4219.dat"))?;
+    changeset_comments.write_all(b"2\t907211\t18204\tsecond\\tline\t2012-01-02 00:00:00\tt\n")?;
+    changeset_comments.write_all(b"1\t907211\t18203\tfirst\t2012-01-01 00:00:00\tt\n")?;
+    changeset_comments.write_all(b"3\t907211\t18204\thidden\t2012-01-03 00:00:00\tf\n")?;
+
+    let reader = apidb_dump::read::reader::Reader::new(dump_path, PathBuf::from("./target/results/history-sample-changesets-tmp"))?;
+    let changesets: Vec<Changeset> = reader.changesets()?.collect();
+    assert_eq!(changesets.len(), 4);
+    assert!(changesets.windows(2).all(|pair| pair[0].id() < pair[1].id()));
+    for changeset in &changesets {
+        assert_eq!(changeset.tags().len(), 2);
+        assert!(changeset.tags().iter().any(|tag| tag.k() == "replication" && tag.v() == "true"));
+        assert!(!changeset.user().is_empty());
+    }
+
+    let discussed = changesets.iter().find(|changeset| changeset.id() == 907211).unwrap();
+    assert_eq!(discussed.user(), "alice");
+    assert_eq!(discussed.comments_count(), 2);
+    let comments = discussed.comments();
+    assert_eq!(comments.iter().map(|comment| comment.text().as_str()).collect::<Vec<&str>>(), vec!["first", "second\tline"]);
+    assert_eq!((comments[1].uid(), comments[1].user().as_str()), (18204, "bob & co"));
+    assert_eq!(comments[0].date(), iso8601_to_timestamp_millis("2012-01-01T00:00:00Z")?);
+
+    let output_path = PathBuf::from("./target/results/history-sample-changesets.osm");
+    let mut writer = ChangesetWriter::new(output_path.clone(), CompressionType::Uncompressed)?;
+    writer.write_header()?;
+    for changeset in changesets.clone() {
+        writer.write_changeset(changeset)?;
+    }
+    writer.close()?;
+    // the creation and closing times of the written dump have sub second precision
+    for (read, written) in read_changesets(&output_path)?.iter().zip(changesets.iter()) {
+        assert_eq!((read.id(), read.uid(), read.user()), (written.id(), written.uid(), written.user()));
+        assert_eq!(read.bounding_box(), written.bounding_box());
+        assert_eq!(read.tags(), written.tags());
+        assert_eq!(read.comments(), written.comments());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_changesets_apidb_dump_time() -> Result<(), anyhow::Error> {
+    common::setup();
+    let dump_path = PathBuf::from("./target/results/history-sample-changesets-dump-time");
+    let before = chrono::Utc::now().timestamp() * 1000;
+    let mut writer = apidb_dump::write::writer::Writer::new(dump_path.clone(), 0)?;
+    for element in opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample.opl"))?.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+
+    // public.changesets (id, user_id, created_at, min_lat, max_lat, min_lon, max_lon, closed_at, num_changes)
+    // 907211 closes after the latest creation time, 4511221 is still open
+    fs::write(
+        dump_path.join("4222.dat"),
+        "907211\t18203\t2012-01-01 00:00:00\t-190554340\t-190500000\t-1699300000\t-1699218762\t2012-01-01 01:00:00\t1\n\
+        4511220\t18204\t2012-01-01 00:30:00\t-190554340\t-190500000\t-1699300000\t-1699218762\t2012-01-01 00:40:00\t1\n\
+        4511221\t18204\t2012-01-01 00:20:00\t-190554340\t-190500000\t-1699300000\t-1699218762\t3000-01-01 00:00:00\t1\n",
+    )?;
+
+    let reader = apidb_dump::read::reader::Reader::new(dump_path, PathBuf::from("./target/results/history-sample-changesets-dump-time-tmp"))?;
+    assert!(reader.dump_time() >= before && reader.dump_time() <= chrono::Utc::now().timestamp_millis());
+    let changesets: Vec<Changeset> = reader.changesets()?.collect();
+    assert_eq!(changesets.iter().map(|changeset| changeset.id()).collect::<Vec<i64>>(), vec![907211, 4511220, 4511221]);
+    assert!(!changesets[0].open());
+    assert_eq!(changesets[0].closed_at(), Some(iso8601_to_timestamp_millis("2012-01-01T01:00:00Z")?));
+    assert!(!changesets[1].open());
+    assert!(changesets[2].open());
+    assert!(changesets[2].closed_at().is_none());
+    Ok(())
+}
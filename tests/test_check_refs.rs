@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+
+use osm_io::osm::apidb_dump;
+use osm_io::osm::check_refs::check_report::CheckReport;
+use osm_io::osm::check_refs::checker::CheckRefs;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+
+#[allow(dead_code)]
+mod common;
+
+fn assert_broken_refs(report: &CheckReport) {
+    assert_eq!((report.nodes(), report.ways(), report.relations()), (5, 3, 3));
+    assert!(!report.is_ok());
+    assert_eq!(report.missing_way_nodes().count(), 2);
+    assert_eq!(report.missing_way_nodes().examples(), &vec!["n4 in w10", "n5 in w11"]);
+    assert_eq!(report.missing_node_members().count(), 1);
+    assert_eq!(report.missing_node_members().examples(), &vec!["n6 in r20"]);
+    assert_eq!(report.missing_way_members().count(), 1);
+    assert_eq!(report.missing_relation_members().count(), 1);
+    assert_eq!(report.missing_relation_members().examples(), &vec!["r23 in r21"]);
+    assert_eq!(report.duplicate_nodes().count(), 1);
+    assert_eq!(report.duplicate_nodes().examples(), &vec!["n2 v1"]);
+    assert_eq!(report.duplicate_ways().count(), 1);
+    assert_eq!(report.duplicate_relations().count(), 0);
+}
+
+#[test]
+fn test_check_refs() -> Result<(), anyhow::Error> {
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/broken-refs.opl"))?;
+    let report = CheckRefs::new().check(reader.elements()?);
+    assert_broken_refs(&report);
+
+    let json = report.to_json();
+    assert_eq!(json["ok"].as_bool(), Some(false));
+    assert_eq!(json["problems"][0]["count"].as_usize(), Some(2));
+    assert_eq!(json["problems"][0]["examples"][1].as_str(), Some("n5 in w11"));
+    assert!(report.to_string().contains("nodes referenced by ways missing: 2, e.g. n4 in w10, n5 in w11"));
+    assert!(report.to_string().ends_with("FAILED"));
+
+    let mut check_refs = CheckRefs::new();
+    check_refs.with_max_examples(1);
+    let report = check_refs.check(reader.elements()?);
+    assert_eq!(report.missing_way_nodes().count(), 2);
+    assert_eq!(report.missing_way_nodes().examples(), &vec!["n4 in w10"]);
+
+    let report = CheckRefs::new().check_pbf(&pbf::reader::Reader::new(&common::opl_fixture_to_pbf("broken-refs", "broken-refs-check-refs.osm.pbf")?)?)?;
+    assert_broken_refs(&report);
+    Ok(())
+}
+
+#[test]
+fn test_check_refs_apidb() -> Result<(), anyhow::Error> {
+    let input_path = common::opl_fixture_to_pbf("areas", "areas-check-refs.osm.pbf")?;
+    let pbf_report = CheckRefs::new().check_pbf(&pbf::reader::Reader::new(&input_path)?)?;
+    assert_eq!(pbf_report.missing_way_members().examples(), &vec!["w99 in r12"]);
+    assert_eq!(pbf_report.problems().iter().map(|problem| problem.count()).sum::<usize>(), 1);
+
+    let dump_path = PathBuf::from("./target/results/areas-check-refs");
+    let mut writer = apidb_dump::write::writer::Writer::new(dump_path.clone(), 0)?;
+    for element in pbf::reader::Reader::new(&input_path)?.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+
+    let reader = apidb_dump::read::reader::Reader::new(dump_path, PathBuf::from("./target/results/areas-check-refs-tmp"))?;
+    let apidb_report = CheckRefs::new().check_apidb(&reader)?;
+    assert_eq!(apidb_report.to_json(), pbf_report.to_json());
+    Ok(())
+}
+
+#[test]
+fn test_check_refs_report() -> Result<(), anyhow::Error> {
+    let input_path = common::opl_fixture_to_pbf("broken-refs", "broken-refs-check-refs.osm.pbf")?;
+    let mut check_refs = CheckRefs::new();
+    check_refs.with_max_examples(1);
+    let json = check_refs.check_pbf(&pbf::reader::Reader::new(&input_path)?)?.to_json();
+    assert_eq!(json["nodes"].as_u64(), Some(5));
+    assert_eq!(json["problems"][0]["examples"].len(), 1);
+
+    let report = CheckRefs::new().check_pbf(&pbf::reader::Reader::new(&PathBuf::from("./tests/fixtures/niue-230109.osm.pbf"))?)?;
+    let text = report.to_string();
+    assert!(text.starts_with("nodes: "));
+    assert_eq!(report.is_ok(), text.ends_with("OK"));
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+use osm_io::osm::apidb_dump;
+use osm_io::osm::converters::{iso8601_to_timestamp_millis, timestamp_to_iso8601_seconds};
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+use osm_io::osm::stats::contributor_stats::{ContributorStats, Period};
+
+#[allow(dead_code)]
+mod common;
+
+#[test]
+fn test_contributor_stats() -> Result<(), anyhow::Error> {
+    let input_path = common::opl_fixture_to_pbf("history-sample", "history-sample-contributors.osm.pbf")?;
+    let stats = ContributorStats::from_pbf(&pbf::reader::Reader::new(&input_path)?, Period::Week)?;
+
+    assert_eq!(stats.users().iter().map(|user| user.uid()).collect::<Vec<i32>>(), vec![18203, 18204]);
+    let alice = stats.user(18203).unwrap();
+    assert_eq!(alice.name(), "alice");
+    assert_eq!((alice.edits(), alice.created(), alice.modified(), alice.deleted()), (5, 4, 0, 1));
+    assert_eq!(alice.changesets(), 2);
+    assert_eq!(alice.first_timestamp(), iso8601_to_timestamp_millis("2009-03-25T00:53:03Z")?);
+    assert_eq!(alice.last_timestamp(), iso8601_to_timestamp_millis("2011-01-01T00:00:00Z")?);
+    let bob = stats.user(18204).unwrap();
+    assert_eq!(bob.name(), "bob & co");
+    assert_eq!((bob.edits(), bob.created(), bob.modified(), bob.deleted()), (2, 0, 2, 0));
+
+    assert_eq!(stats.changesets().len(), 4);
+    let changeset = stats.changeset(907211).unwrap();
+    assert_eq!((changeset.uid, changeset.edits), (18203, 4));
+    assert_eq!(changeset.last_timestamp - changeset.first_timestamp, 57_000);
+
+    let starts: Vec<String> = stats.periods().iter()
+        .map(|period| timestamp_to_iso8601_seconds(period.start * 1000).unwrap())
+        .collect();
+    assert_eq!(starts, vec!["2009-03-23T00:00:00Z", "2010-04-26T00:00:00Z", "2010-12-27T00:00:00Z", "2012-01-30T00:00:00Z"]);
+    assert_eq!((stats.periods()[0].edits, stats.periods()[0].users, stats.periods()[0].changesets), (4, 1, 1));
+
+    let json = stats.to_json();
+    assert_eq!(json["period"], "week");
+    assert_eq!(json["users"][1]["user"], "bob & co");
+    assert_eq!(json["changesets"][0]["first"], "2009-03-25T00:53:03Z");
+    Ok(())
+}
+
+#[test]
+fn test_contributor_stats_without_timestamp() -> Result<(), anyhow::Error> {
+    let elements = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample.opl"))?.elements()?
+        .chain(opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample-no-timestamp.opl"))?.elements()?);
+    let stats = ContributorStats::compute(Period::Week, elements);
+
+    let alice = stats.user(18203).unwrap();
+    assert_eq!((alice.edits(), alice.created()), (6, 5));
+    assert_eq!(alice.first_timestamp(), iso8601_to_timestamp_millis("2009-03-25T00:53:03Z")?);
+    let changeset = stats.changeset(907211).unwrap();
+    assert_eq!(changeset.edits, 5);
+    assert_eq!(changeset.first_timestamp, iso8601_to_timestamp_millis("2009-03-25T00:53:03Z")?);
+    assert_eq!(stats.periods().len(), 4);
+    assert_eq!(stats.periods().iter().map(|period| period.edits).sum::<u64>(), 7);
+    assert_eq!(stats.to_json()["changesets"][0]["first"], "2009-03-25T00:53:03Z");
+
+    // the version without uid and changeset is not attributed to a user or a changeset
+    assert_eq!(stats.users().iter().map(|user| user.uid()).collect::<Vec<i32>>(), vec![18203, 18204]);
+    assert_eq!(stats.users().iter().map(|user| user.edits()).sum::<u64>(), 8);
+    assert_eq!(stats.changesets().len(), 4);
+    assert!(stats.changesets().iter().all(|changeset| changeset.id > 0));
+    Ok(())
+}
+
+#[test]
+fn test_contributor_stats_apidb() -> Result<(), anyhow::Error> {
+    let input_path = common::opl_fixture_to_pbf("history-sample", "history-sample-contributors.osm.pbf")?;
+    let pbf_stats = ContributorStats::from_pbf(&pbf::reader::Reader::new(&input_path)?, Period::Day)?;
+
+    let dump_path = PathBuf::from("./target/results/history-sample-contributors");
+    let mut writer = apidb_dump::write::writer::Writer::new(dump_path.clone(), 0)?;
+    for element in pbf::reader::Reader::new(&input_path)?.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+
+    let reader = apidb_dump::read::reader::Reader::new(dump_path, PathBuf::from("./target/results/history-sample-contributors-tmp"))?;
+    let apidb_stats = ContributorStats::from_apidb(&reader, Period::Day)?;
+    assert_eq!(apidb_stats.to_json(), pbf_stats.to_json());
+    Ok(())
+}
+
+#[test]
+fn test_contributor_stats_history_pbf() -> Result<(), anyhow::Error> {
+    let reader = pbf::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-niue-230109.osm.pbf"))?;
+    let json = ContributorStats::from_pbf(&reader, Period::Week)?.to_json();
+    let edits: u64 = json["users"].members().map(|user| user["edits"].as_u64().unwrap()).sum();
+    assert_eq!(edits, 61628 + 4728 + 197);
+    Ok(())
+}
@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use osm_io::osm::extract::extractor::extract;
+use osm_io::osm::extract::multi_extract::{ExtractConfig, ExtractDefinition, multi_extract};
+use osm_io::osm::extract::poly_file;
+use osm_io::osm::extract::region::Region;
+use osm_io::osm::extract::region_extract::RegionExtract;
+use osm_io::osm::extract::strategy::Strategy;
+use osm_io::osm::model::bounding_box::BoundingBox;
+use osm_io::osm::model::element::Element;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::compression_type::CompressionType;
+
+#[allow(dead_code)]
+mod common;
+
+fn element_ids(elements: &[Element]) -> Vec<String> {
+    elements.iter()
+        .map(|element| {
+            match element {
+                Element::Node { node } => {
+                    format!("n{}", node.id())
+                }
+                Element::Way { way } => {
+                    format!("w{}", way.id())
+                }
+                Element::Relation { relation } => {
+                    format!("r{}", relation.id())
+                }
+                Element::Sentinel => {
+                    "sentinel".to_string()
+                }
+            }
+        })
+        .collect()
+}
+
+fn extract_fixture(fixture: &str, strategy: Strategy) -> Result<Vec<String>, anyhow::Error> {
+    let reader = opl::reader::Reader::new(&PathBuf::from(fixture))?;
+    let bounding_box = BoundingBox::from_str("-0.5,-0.5,0.5,0.5")?;
+    let mut region_extract = RegionExtract::new(Box::new(bounding_box), strategy);
+    for element in reader.elements()? {
+        region_extract.first_pass(&element);
+    }
+    region_extract.finish_first_pass();
+    if region_extract.needs_second_pass() {
+        for element in reader.elements()? {
+            region_extract.second_pass(&element);
+        }
+    }
+    let elements: Vec<Element> = reader.elements()?
+        .filter(|element| region_extract.contains(element))
+        .collect();
+    Ok(element_ids(&elements))
+}
+
+#[test]
+fn test_extract_strategies() -> Result<(), anyhow::Error> {
+    assert_eq!(
+        extract_fixture("./tests/fixtures/areas.opl", Strategy::Simple)?,
+        vec!["n1", "w20", "w21", "r9", "r10"],
+    );
+    assert_eq!(
+        extract_fixture("./tests/fixtures/areas.opl", Strategy::CompleteWays)?,
+        vec!["n1", "n2", "n3", "n4", "w20", "w21", "r9", "r10"],
+    );
+    assert_eq!(
+        extract_fixture("./tests/fixtures/areas.opl", Strategy::Smart)?,
+        vec!["n1", "n2", "n3", "n4", "n5", "n6", "n7", "n8", "w20", "w21", "w22", "r9", "r10"],
+    );
+    assert_eq!(Strategy::from_str("complete_ways")?, Strategy::CompleteWays);
+    assert_eq!(Strategy::Smart.to_string(), "smart");
+    assert!(Strategy::from_str("complete").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_extract_parent_relations() -> Result<(), anyhow::Error> {
+    // r5 is included through its child r6, which comes later in the input
+    assert_eq!(
+        extract_fixture("./tests/fixtures/parent-relations.opl", Strategy::Simple)?,
+        vec!["n1", "w10", "r5", "r6"],
+    );
+    assert_eq!(
+        extract_fixture("./tests/fixtures/parent-relations.opl", Strategy::Smart)?,
+        vec!["n1", "n2", "n5", "n6", "n7", "w10", "w11", "r5", "r6"],
+    );
+    Ok(())
+}
+
+#[test]
+fn test_extract_pbf() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let bounding_box = BoundingBox::from_str("-169.95,-19.1,-169.85,-19.0")?;
+    for strategy in [Strategy::Simple, Strategy::CompleteWays, Strategy::Smart] {
+        let output_path = PathBuf::from(format!("./target/results/niue-230109-{}.osm.pbf", strategy));
+        extract(&reader, Box::new(bounding_box.clone()), strategy.clone(), output_path.clone(), CompressionType::Zlib)?;
+
+        let output = pbf::reader::Reader::new(&output_path)?;
+        let header_box = output.info().bounding_box().clone().unwrap();
+        assert!((header_box.left() - bounding_box.left()).abs() < 1e-7);
+        assert!((header_box.top() - bounding_box.top()).abs() < 1e-7);
+
+        let mut node_ids = HashSet::new();
+        let mut outside = 0;
+        let mut ways = 0;
+        let mut complete_ways = 0;
+        for element in output.elements()? {
+            match element {
+                Element::Node { node } => {
+                    if !bounding_box.contains(node.coordinate()) {
+                        outside += 1;
+                    }
+                    node_ids.insert(node.id());
+                }
+                Element::Way { way } => {
+                    ways += 1;
+                    if way.refs().iter().all(|id| node_ids.contains(id)) {
+                        complete_ways += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        assert!(!node_ids.is_empty());
+        match strategy {
+            Strategy::Simple => {
+                assert_eq!(outside, 0);
+                assert!(complete_ways < ways);
+            }
+            _ => {
+                assert!(outside > 0);
+                assert_eq!(complete_ways, ways);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_poly_extract() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let output_path = PathBuf::from("./target/results/niue-230109-alofi.osm.pbf");
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let region = poly_file::read(&PathBuf::from("./tests/fixtures/alofi.poly"))?;
+    assert_eq!(region.name(), "alofi");
+    assert_eq!(region.polygons().len(), 1);
+    assert_eq!(region.polygons()[0].inners().len(), 1);
+    let envelope = region.bounding_box();
+    assert_eq!(envelope.left(), -169.95);
+    assert_eq!(envelope.right(), -169.83);
+
+    let mut inside = 0;
+    for element in reader.elements()? {
+        if let Element::Node { node } = element {
+            if region.contains(node.coordinate()) {
+                inside += 1;
+                assert!(envelope.contains(node.coordinate()));
+            }
+        }
+    }
+    assert!(inside > 0);
+
+    extract(&reader, Box::new(region.clone()), Strategy::Simple, output_path.clone(), CompressionType::Zlib)?;
+    let output = pbf::reader::Reader::new(&output_path)?;
+    let header_box = output.info().bounding_box().clone().unwrap();
+    assert!((header_box.right() - envelope.right()).abs() < 1e-7);
+    assert!((header_box.bottom() - envelope.bottom()).abs() < 1e-7);
+    let mut nodes = 0;
+    for element in output.elements()? {
+        if let Element::Node { node } = element {
+            assert!(region.contains(node.coordinate()));
+            nodes += 1;
+        }
+    }
+    assert_eq!(nodes, inside);
+    Ok(())
+}
+
+fn read_ids(path: &Path) -> Result<Vec<String>, anyhow::Error> {
+    let reader = pbf::reader::Reader::new(path)?;
+    let elements: Vec<Element> = reader.elements()?
+        .filter(|element| !matches!(element, Element::Sentinel))
+        .collect();
+    Ok(element_ids(&elements))
+}
+
+#[test]
+fn test_multi_extract() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let config_path = PathBuf::from("./tests/fixtures/extracts.json");
+    let reader = pbf::reader::Reader::new(&input_path)?;
+
+    for strategy in [Strategy::Simple, Strategy::CompleteWays, Strategy::Smart] {
+        let mut config = ExtractConfig::from_file(&config_path)?;
+        assert_eq!(config.strategy(), &Strategy::CompleteWays);
+        assert_eq!(config.extracts().len(), 5);
+        assert_eq!(config.extracts()[0].description(), &Some("Alofi bounding box".to_string()));
+        config.with_strategy(strategy.clone());
+        let outputs: Vec<PathBuf> = config.extracts().iter()
+            .map(|extract| config.directory().join(extract.output()))
+            .collect();
+        multi_extract(&reader, config, CompressionType::Zlib)?;
+
+        let config = ExtractConfig::from_file(&config_path)?;
+        for (definition, output) in config.into_extracts().into_iter().zip(outputs) {
+            let single_output = PathBuf::from("./target/results/niue-single.osm.pbf");
+            let region = definition.region().bounding_box();
+            extract_single(&reader, definition, strategy.clone(), single_output.clone())?;
+            let ids = read_ids(&output)?;
+            assert!(!ids.is_empty(), "{}", output.display());
+            assert_eq!(ids, read_ids(&single_output)?, "{}", output.display());
+            let header_box = pbf::reader::Reader::new(&output)?.info().bounding_box().clone().unwrap();
+            assert!((header_box.left() - region.left()).abs() < 1e-7);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_multi_extract_parent_relations() -> Result<(), anyhow::Error> {
+    let input_path = common::opl_fixture_to_pbf("parent-relations", "parent-relations.osm.pbf")?;
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let bounding_box = BoundingBox::from_str("-0.5,-0.5,0.5,0.5")?;
+    for strategy in [Strategy::Simple, Strategy::CompleteWays, Strategy::Smart] {
+        let output = PathBuf::from(format!("parent-relations-multi-{}.osm.pbf", strategy));
+        let config = ExtractConfig::new(
+            PathBuf::from("./target/results"),
+            strategy.clone(),
+            vec![ExtractDefinition::new(output.clone(), None, Box::new(bounding_box.clone()))],
+        );
+        multi_extract(&reader, config, CompressionType::Zlib)?;
+        let single_output = PathBuf::from(format!("./target/results/parent-relations-single-{}.osm.pbf", strategy));
+        extract(&reader, Box::new(bounding_box.clone()), strategy.clone(), single_output.clone(), CompressionType::Zlib)?;
+
+        let ids = read_ids(&PathBuf::from("./target/results").join(output))?;
+        assert!(ids.contains(&"r5".to_string()), "{}", strategy);
+        assert_eq!(ids, read_ids(&single_output)?, "{}", strategy);
+    }
+    Ok(())
+}
+
+fn extract_single(reader: &pbf::reader::Reader, definition: ExtractDefinition, strategy: Strategy, output_path: PathBuf) -> Result<(), anyhow::Error> {
+    extract(reader, definition.into_region(), strategy, output_path, CompressionType::Zlib)
+}
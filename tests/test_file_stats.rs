@@ -0,0 +1,101 @@
+use std::path::PathBuf;
+
+use json::JsonValue;
+
+use osm_io::osm::filter::get_id::ObjectType;
+use osm_io::osm::pbf;
+use osm_io::osm::stats::file_stats::FileStats;
+
+use crate::common::read_fixture_analysis;
+
+#[allow(dead_code)]
+mod common;
+
+/// Compare recursively, skipping the osmium internal crc32 and buffers entries
+fn assert_json_eq(path: &str, actual: &JsonValue, expected: &JsonValue) {
+    match expected {
+        JsonValue::Object(object) => {
+            assert!(actual.is_object(), "{}: expected an object, got {}", path, actual);
+            let mut compared = 0;
+            for (key, value) in object.iter() {
+                if path == "data" && (key == "crc32" || key == "buffers") {
+                    continue;
+                }
+                assert_json_eq(&format!("{}.{}", path, key), &actual[key], value);
+                compared += 1;
+            }
+            assert_eq!(actual.len(), compared, "{}: unexpected entries in {}", path, actual);
+        }
+        JsonValue::Array(array) => {
+            assert_eq!(actual.len(), array.len(), "{}", path);
+            for (i, value) in array.iter().enumerate() {
+                assert_json_eq(&format!("{}[{}]", path, i), &actual[i], value);
+            }
+        }
+        JsonValue::Number(_) => {
+            let expected = expected.as_f64().unwrap();
+            let actual = actual.as_f64().unwrap_or_else(|| panic!("{}: expected a number, got {}", path, actual));
+            assert!((expected - actual).abs() < 1E-9, "{}: expected {}, got {}", path, expected, actual);
+        }
+        _ => {
+            assert_eq!(actual, expected, "{}", path);
+        }
+    }
+}
+
+fn test_fixture(input: &str) -> Result<FileStats, anyhow::Error> {
+    let input_path = PathBuf::from(input);
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let file_stats = FileStats::calc(&reader)?;
+    let fixture_analysis = read_fixture_analysis(&PathBuf::from(format!("{}.analysis.json", input)));
+    for (key, value) in fixture_analysis.entries() {
+        assert_json_eq(key, &file_stats.to_json()[key], value);
+    }
+    Ok(file_stats)
+}
+
+#[test]
+fn test_file_stats() -> Result<(), anyhow::Error> {
+    let file_stats = test_fixture("./tests/fixtures/niue-230109.osm.pbf")?;
+    assert!(!file_stats.with_history());
+    assert!(file_stats.objects_ordered());
+    assert!(!file_stats.multiple_versions());
+    assert_eq!(file_stats.type_stats(ObjectType::Way).count, 3007);
+    assert!(file_stats.bounding_box().is_some());
+    assert!(file_stats.first_timestamp().unwrap() < file_stats.last_timestamp().unwrap());
+    Ok(())
+}
+
+#[test]
+fn test_history_file_stats() -> Result<(), anyhow::Error> {
+    let file_stats = test_fixture("./tests/fixtures/history-niue-230109.osm.pbf")?;
+    assert!(file_stats.with_history());
+    assert!(file_stats.multiple_versions());
+    assert!(file_stats.all_objects().version);
+    Ok(())
+}
+
+#[test]
+fn test_no_metadata_file_stats() -> Result<(), anyhow::Error> {
+    let output_path = common::opl_fixture_to_pbf("no-metadata", "no-metadata.osm.pbf")?;
+
+    let file_stats = FileStats::calc(&pbf::reader::Reader::new(&output_path)?)?;
+    assert_eq!(file_stats.type_stats(ObjectType::Node).count, 3);
+    assert!(file_stats.first_timestamp().is_none());
+    assert!(file_stats.last_timestamp().is_none());
+    let some_objects = file_stats.some_objects();
+    assert!(!some_objects.version && !some_objects.timestamp && !some_objects.changeset && !some_objects.user && !some_objects.uid);
+    let json = file_stats.to_json();
+    assert!(json["data"]["timestamp"]["first"].is_null());
+    assert_eq!(json["data"]["metadata"]["some_objects"]["changeset"], false);
+    assert_eq!(json["data"]["metadata"]["some_objects"]["uid"], false);
+    Ok(())
+}
+
+#[test]
+fn test_file_info_json() -> Result<(), anyhow::Error> {
+    let reader = pbf::reader::Reader::new(&PathBuf::from("./tests/fixtures/niue-230109.osm.pbf"))?;
+    let file_info = json::parse(&FileStats::calc(&reader)?.to_json().pretty(4))?;
+    assert_eq!(file_info["data"]["count"]["nodes"].as_u64(), Some(41816));
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use json::JsonValue;
+
+use osm_io::osm::geojson::exporter::Exporter;
+use osm_io::osm::geojson::file_type::FileType;
+use osm_io::osm::geojson::writer::Writer;
+use osm_io::osm::index::sparse_mem_location_index::SparseMemLocationIndex;
+use osm_io::osm::model::element::Element;
+use osm_io::osm::opl;
+use osm_io::osm::xml::compression_type::CompressionType;
+
+#[allow(dead_code)]
+mod common;
+
+fn export_areas_fixture(metadata: bool) -> Result<(Vec<JsonValue>, Exporter), anyhow::Error> {
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/areas.opl"))?;
+    let mut exporter = Exporter::new(Arc::new(SparseMemLocationIndex::new()));
+    for element in reader.elements()? {
+        if let Element::Relation { relation } = element {
+            exporter.add_relation(&relation);
+        }
+    }
+    // the flag applies to the relations registered before
+    exporter.with_metadata(metadata);
+    let mut features = Vec::new();
+    for element in reader.elements()? {
+        if let Some(feature) = exporter.feature(&element)? {
+            features.push(feature);
+        }
+    }
+    features.append(&mut exporter.finish());
+    Ok((features, exporter))
+}
+
+fn find<'a>(features: &'a [JsonValue], id: &str) -> &'a JsonValue {
+    features.iter().find(|feature| feature["id"] == id).unwrap()
+}
+
+#[test]
+fn test_export_features() -> Result<(), anyhow::Error> {
+    let (features, exporter) = export_areas_fixture(false)?;
+    let ids: Vec<String> = features.iter().map(|feature| feature["id"].to_string()).collect();
+    assert_eq!(ids, vec!["w30", "w31", "w32", "r10", "r13"]);
+
+    let building = find(&features, "w30");
+    assert_eq!(building["geometry"]["type"], "Polygon");
+    assert_eq!(building["geometry"]["coordinates"][0].len(), 5);
+    assert_eq!(building["properties"].dump(), r#"{"building":"yes"}"#);
+
+    let fence = find(&features, "w31");
+    assert_eq!(fence["geometry"]["type"], "LineString");
+    assert_eq!(fence["geometry"]["coordinates"].len(), 4);
+
+    let path = find(&features, "w32");
+    assert_eq!(path["geometry"].dump(), r#"{"type":"LineString","coordinates":[[20,20],[20,21]]}"#);
+
+    let forest = find(&features, "r10");
+    assert_eq!(forest["geometry"]["type"], "MultiPolygon");
+    assert_eq!(forest["geometry"]["coordinates"].len(), 1);
+    assert_eq!(forest["geometry"]["coordinates"][0].len(), 2);
+    assert_eq!(forest["properties"].dump(), r#"{"landuse":"forest"}"#);
+
+    let boundary = find(&features, "r13");
+    assert_eq!(boundary["geometry"]["coordinates"].len(), 2);
+
+    assert_eq!(exporter.invalid().len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_export_area_rules() -> Result<(), anyhow::Error> {
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/area-rules.opl"))?;
+    let mut exporter = Exporter::new(Arc::new(SparseMemLocationIndex::new()));
+    let mut features = Vec::new();
+    for element in reader.elements()? {
+        if let Some(feature) = exporter.feature(&element)? {
+            features.push(feature);
+        }
+    }
+    assert_eq!(find(&features, "w40")["geometry"]["type"], "LineString");
+    assert_eq!(find(&features, "w41")["geometry"]["type"], "Polygon");
+    assert_eq!(find(&features, "w43")["geometry"]["type"], "LineString");
+    assert_eq!(find(&features, "w44")["geometry"]["type"], "Polygon");
+    Ok(())
+}
+
+#[test]
+fn test_export_metadata() -> Result<(), anyhow::Error> {
+    let (features, _) = export_areas_fixture(true)?;
+    let building = find(&features, "w30");
+    assert_eq!(building["properties"]["@type"], "way");
+    assert_eq!(building["properties"]["@id"], 30);
+    assert_eq!(building["properties"]["@version"], 1);
+    assert_eq!(building["properties"]["building"], "yes");
+    // the fixture has no changeset, uid and user
+    for key in ["@changeset", "@uid", "@user", "@timestamp"] {
+        assert!(!building["properties"].has_key(key), "{}", key);
+    }
+    let forest = find(&features, "r10");
+    assert_eq!(forest["properties"]["@type"], "relation");
+    assert_eq!(forest["properties"]["@id"], 10);
+    assert_eq!(forest["properties"]["@version"], 1);
+    Ok(())
+}
+
+#[test]
+fn test_export_pbf() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let geojson_path = PathBuf::from("./target/results/niue-230109.geojson");
+    let geojsonseq_path = PathBuf::from("./target/results/niue-230109.geojsonseq");
+
+    let mut writer = Writer::new(geojson_path.clone(), FileType::GeoJson, CompressionType::Uncompressed)?;
+    Exporter::new(Arc::new(SparseMemLocationIndex::new())).run(&input_path, &mut writer)?;
+    let collection = json::parse(&fs::read_to_string(&geojson_path)?)?;
+    assert_eq!(collection["type"], "FeatureCollection");
+    let features = &collection["features"];
+    assert!(!features.is_empty());
+    for feature in features.members() {
+        assert_eq!(feature["type"], "Feature");
+        assert!(!feature["properties"].is_empty());
+    }
+
+    let mut writer = Writer::new(geojsonseq_path.clone(), FileType::GeoJsonSeq, CompressionType::Uncompressed)?;
+    Exporter::new(Arc::new(SparseMemLocationIndex::new())).run(&input_path, &mut writer)?;
+    let sequence = fs::read_to_string(&geojsonseq_path)?;
+    let lines: Vec<&str> = sequence.lines().collect();
+    assert_eq!(lines.len(), features.len());
+    for (line, expected) in lines.iter().zip(features.members()) {
+        assert!(line.starts_with('\u{1e}'));
+        assert_eq!(&json::parse(&line[1..])?, expected);
+    }
+    Ok(())
+}
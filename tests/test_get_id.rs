@@ -0,0 +1,141 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use osm_io::osm::filter::get_id::{GetId, ObjectType, read_ids, TypedId};
+use osm_io::osm::model::element::Element;
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::block_index::BlockIndex;
+use osm_io::osm::pbf::compression_type::CompressionType;
+
+#[allow(dead_code)]
+mod common;
+
+fn read_ids_of(path: &Path) -> Result<Vec<String>, anyhow::Error> {
+    Ok(
+        pbf::reader::Reader::new(path)?.elements()?
+            .filter_map(|element| TypedId::of(&element))
+            .map(|id| id.to_string())
+            .collect()
+    )
+}
+
+fn run(input_path: &Path, ids: &[&str], add_referenced: bool, block_index: Option<BlockIndex>) -> Result<(Vec<String>, Vec<String>), anyhow::Error> {
+    let output_path = PathBuf::from(format!("./target/results/areas-get-id-{}-{}-{}.osm.pbf", ids.join("-"), add_referenced, block_index.is_some()));
+    let reader = pbf::reader::Reader::new(input_path)?;
+    let ids = ids.iter()
+        .map(|id| TypedId::from_str(id))
+        .collect::<Result<Vec<TypedId>, anyhow::Error>>()?;
+    let mut get_id = GetId::new(ids);
+    get_id.with_add_referenced(add_referenced);
+    get_id.with_block_index(block_index);
+    let missing = get_id.run(&reader, output_path.clone(), CompressionType::Zlib)?;
+    Ok((read_ids_of(&output_path)?, missing.iter().map(|id| id.to_string()).collect()))
+}
+
+#[test]
+fn test_typed_id() -> Result<(), anyhow::Error> {
+    let id = TypedId::from_str("w456")?;
+    assert_eq!(id.object_type(), ObjectType::Way);
+    assert_eq!(id.id(), 456);
+    assert_eq!(TypedId::from_str("n-1")?.to_string(), "n-1");
+    assert!(TypedId::from_str("x1").is_err());
+    assert!(TypedId::from_str("r").is_err());
+
+    let ids: Vec<String> = read_ids(&PathBuf::from("./tests/fixtures/get-ids.txt"))?.iter()
+        .map(|id| id.to_string())
+        .collect();
+    assert_eq!(ids, vec!["w30", "r9", "n5", "r99"]);
+    Ok(())
+}
+
+#[test]
+fn test_get_id() -> Result<(), anyhow::Error> {
+    let input_path = common::opl_fixture_to_pbf("areas", "areas-get-id.osm.pbf")?;
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let block_index = BlockIndex::build(&reader)?;
+
+    for index in [None, Some(block_index)] {
+        let (ids, missing) = run(&input_path, &["w30", "n5", "r99"], false, index.clone())?;
+        assert_eq!(ids, vec!["n5", "w30"]);
+        assert_eq!(missing, vec!["r99"]);
+
+        let (ids, missing) = run(&input_path, &["w32"], true, index.clone())?;
+        assert_eq!(ids, vec!["n11", "n12", "w32"]);
+        assert!(missing.is_empty());
+
+        let (ids, missing) = run(&input_path, &["r9", "r12"], true, index.clone())?;
+        assert_eq!(ids, vec!["n1", "n2", "n3", "n4", "n5", "n6", "n7", "n8", "w20", "w21", "w22", "r9", "r10", "r12"]);
+        assert!(missing.is_empty());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_get_id_block_index() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./target/results/niue-230109-get-id.osm.pbf");
+    fs::copy("./tests/fixtures/niue-230109.osm.pbf", &input_path)?;
+    let index_path = BlockIndex::index_path(&input_path);
+    if index_path.exists() {
+        fs::remove_file(&index_path)?;
+    }
+
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let mut ids = Vec::new();
+    for element in reader.elements()? {
+        match &element {
+            Element::Way { way } if ids.len() < 3 => {
+                ids.push(format!("w{}", way.id()));
+            }
+            Element::Relation { relation } if ids.len() < 6 => {
+                ids.push(format!("r{}", relation.id()));
+            }
+            _ => {}
+        }
+    }
+
+    // use the index next to the input if it is not stale, optionally creating it
+    let run_with_index = |output_path: &Path, create_index: bool| -> Result<(), anyhow::Error> {
+        let reader = pbf::reader::Reader::new(&input_path)?;
+        let mut block_index = BlockIndex::find(&reader)?;
+        if block_index.is_none() && create_index {
+            let index = BlockIndex::build(&reader)?;
+            index.save(&index_path)?;
+            block_index = Some(index);
+        }
+        let typed_ids = ids.iter()
+            .map(|id| TypedId::from_str(id))
+            .collect::<Result<Vec<TypedId>, anyhow::Error>>()?;
+        let mut get_id = GetId::new(typed_ids);
+        get_id.with_add_referenced(true);
+        get_id.with_block_index(block_index);
+        assert!(get_id.run(&reader, output_path.to_path_buf(), CompressionType::Zlib)?.is_empty());
+        Ok(())
+    };
+
+    let without_index = PathBuf::from("./target/results/niue-230109-get-id-without-index.osm.pbf");
+    run_with_index(&without_index, false)?;
+    assert!(!index_path.exists());
+
+    let with_index = PathBuf::from("./target/results/niue-230109-get-id-with-index.osm.pbf");
+    run_with_index(&with_index, true)?;
+    assert!(index_path.exists());
+    let block_index = BlockIndex::find(&reader)?.unwrap();
+    assert!(!block_index.entries().is_empty());
+
+    let expected = read_ids_of(&without_index)?;
+    assert!(ids.iter().all(|id| expected.contains(id)));
+    assert_eq!(read_ids_of(&with_index)?, expected);
+
+    // a stale index is ignored and rebuilt on request
+    fs::File::options().write(true).open(&input_path)?.set_modified(SystemTime::now() + Duration::from_secs(60))?;
+    assert!(BlockIndex::find(&reader)?.is_none());
+    run_with_index(&without_index, false)?;
+    assert_eq!(read_ids_of(&without_index)?, expected);
+    run_with_index(&with_index, true)?;
+    assert!(BlockIndex::find(&reader)?.is_some());
+    assert_eq!(read_ids_of(&with_index)?, expected);
+    Ok(())
+}
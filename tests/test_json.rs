@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::PathBuf;
+
+use osm_io::osm::json;
+use osm_io::osm::model::element::Element;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+use osm_io::osm::xml;
+use osm_io::osm::xml::compression_type::CompressionType;
+
+use crate::common::read_fixture_analysis;
+
+#[allow(dead_code)]
+mod common;
+
+#[test]
+fn test_json_reader() -> Result<(), anyhow::Error> {
+    let reader = json::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample.json"))?;
+    let bounding_box = reader.info().bounding_box().clone().unwrap();
+    assert_eq!(bounding_box.left(), -169.96);
+    assert_eq!(bounding_box.top(), -19.04);
+    assert_eq!(reader.info().writingprogram(), &Some("Overpass API 0.7.61.5 4133829e".to_string()));
+    assert_eq!(reader.info().osmosis_replication_timestamp(), &Some(1673299274));
+
+    let json_elements: Vec<Element> = reader.elements()?.collect();
+    let xml_elements: Vec<Element> = xml::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample.osm"))?
+        .elements()?
+        .collect();
+    assert_eq!(json_elements.len(), 7);
+    for (expected, actual) in xml_elements.iter().zip(json_elements.iter()) {
+        common::assert_same_element(expected, actual);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_pbf_reader_json_writer_pipe() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let output_path = PathBuf::from("./target/results/niue-230109.json");
+    let fixture_analysis_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf.analysis.json");
+
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let mut writer = json::writer::Writer::from_file_info(
+        output_path.clone(),
+        reader.info().clone(),
+        CompressionType::Uncompressed,
+    )?;
+    writer.write_header()?;
+    for element in reader.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+
+    let json_reader = json::reader::Reader::new(&output_path)?;
+    assert_eq!(
+        json_reader.info().bounding_box().as_ref().map(|b| b.to_string()),
+        reader.info().bounding_box().as_ref().map(|b| b.to_string()),
+    );
+    let (mut nodes, mut ways, mut relations) = (0_i64, 0_i64, 0_i64);
+    let mut pbf_elements = reader.elements()?.filter(|e| !matches!(e, Element::Sentinel));
+    for json_element in json_reader.elements()? {
+        let pbf_element = pbf_elements.next().expect("json file has more elements than the pbf file");
+        common::assert_same_element(&pbf_element, &json_element);
+        match json_element {
+            Element::Node { .. } => nodes += 1,
+            Element::Way { .. } => ways += 1,
+            Element::Relation { .. } => relations += 1,
+            Element::Sentinel => {}
+        }
+    }
+    assert!(pbf_elements.next().is_none());
+
+    let fixture_analysis = read_fixture_analysis(&fixture_analysis_path);
+    assert_eq!(nodes, fixture_analysis["data"]["count"]["nodes"].as_i64().unwrap());
+    assert_eq!(ways, fixture_analysis["data"]["count"]["ways"].as_i64().unwrap());
+    assert_eq!(relations, fixture_analysis["data"]["count"]["relations"].as_i64().unwrap());
+    Ok(())
+}
+
+#[test]
+fn test_json_writer_deleted_and_without_metadata() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/history-sample.osm");
+    let output_path = PathBuf::from("./target/results/history-sample-no-metadata.json.gz");
+
+    let reader = xml::reader::Reader::new(&input_path)?;
+    let mut writer = json::writer::Writer::from_file_info(
+        output_path.clone(),
+        reader.info().clone(),
+        CompressionType::Gzip,
+    )?;
+    writer.with_metadata(false);
+    writer.write_header()?;
+    writer.write_elements(reader.elements()?.collect())?;
+    writer.close()?;
+
+    let written: Vec<Element> = json::reader::Reader::new(&output_path)?.elements()?.collect();
+    assert_eq!(written.len(), 7);
+    match &written[4] {
+        Element::Node { node } => {
+            assert_eq!(node.id(), 102);
+            assert!(!node.visible());
+            assert_eq!(node.user(), "");
+            assert_eq!(node.changeset(), -1);
+        }
+        _ => panic!("expected a node"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_json_writer_missing_metadata() -> Result<(), anyhow::Error> {
+    common::setup();
+    let output_path = PathBuf::from("./target/results/no-metadata.json");
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/no-metadata.opl"))?;
+    let mut writer = json::writer::Writer::from_file_info(output_path.clone(), reader.info().clone(), CompressionType::Uncompressed)?;
+    writer.write_header()?;
+    writer.write_elements(reader.elements()?.collect())?;
+    writer.close()?;
+
+    let document = ::json::parse(&fs::read_to_string(&output_path)?)?;
+    for value in document["elements"].members() {
+        for field in ["version", "changeset", "user", "uid", "timestamp"] {
+            assert!(!value.has_key(field), "{}", value.dump());
+        }
+    }
+    let expected: Vec<Element> = reader.elements()?.collect();
+    let actual: Vec<Element> = json::reader::Reader::new(&output_path)?.elements()?.collect();
+    assert_eq!(expected.len(), actual.len());
+    for (expected, actual) in expected.iter().zip(actual.iter()) {
+        common::assert_same_element(expected, actual);
+    }
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_non_existent_json_input() {
+    let input_path = PathBuf::from("./tests/fixtures/non-existent.json");
+    json::reader::Reader::new(&input_path).expect("path doesn't exist");
+}
@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use osm_io::osm::index::dense_mmap_location_index::DenseMmapLocationIndex;
+use osm_io::osm::index::sparse_mem_location_index::SparseMemLocationIndex;
+use osm_io::osm::model::element::Element;
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::compression_type::CompressionType;
+use osm_io::osm::transform::locations_on_ways::{LOCATIONS_ON_WAYS, LocationsOnWays};
+
+#[allow(dead_code)]
+mod common;
+
+#[test]
+fn test_add_locations_to_ways() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let output_path = PathBuf::from("./target/results/niue-230109-locations-on-ways.osm.pbf");
+
+    let locations_on_ways = LocationsOnWays::new(Arc::new(DenseMmapLocationIndex::temporary()?));
+    locations_on_ways.run(&input_path, output_path.clone(), CompressionType::Zlib, 4)?;
+
+    let reader = pbf::reader::Reader::new(&output_path)?;
+    assert!(reader.info().optional(LOCATIONS_ON_WAYS));
+    let mut ways = 0;
+    for element in reader.elements()? {
+        if let Element::Way { way } = element {
+            ways += 1;
+            assert_eq!(way.coordinates().len(), way.refs().len());
+            for (r, coordinate) in way.refs().iter().zip(way.coordinates()) {
+                let expected = locations_on_ways.index().get(*r).unwrap();
+                assert_eq!(coordinate.lat7(), expected.lat7());
+                assert_eq!(coordinate.lon7(), expected.lon7());
+            }
+        }
+    }
+    assert!(ways > 0);
+
+    // the locations survive another round trip through the writer
+    let round_trip_path = PathBuf::from("./target/results/niue-230109-locations-on-ways-copy.osm.pbf");
+    let mut file_info = reader.info().clone();
+    let mut required_features = file_info.required_features().clone();
+    required_features.push(LOCATIONS_ON_WAYS.to_string());
+    file_info.with_required_features(&required_features);
+    let mut writer = pbf::writer::Writer::from_file_info(round_trip_path.clone(), file_info, CompressionType::Zlib)?;
+    writer.write_header()?;
+    for element in reader.elements()? {
+        if !matches!(element, Element::Sentinel) {
+            writer.write_element(element)?;
+        }
+    }
+    writer.close()?;
+
+    let round_trip_reader = pbf::reader::Reader::new(&round_trip_path)?;
+    assert!(round_trip_reader.info().required(LOCATIONS_ON_WAYS));
+    for (expected, actual) in reader.elements()?.zip(round_trip_reader.elements()?) {
+        if let (Element::Way { way: expected }, Element::Way { way: actual }) = (expected, actual) {
+            assert_eq!(expected.coordinates(), actual.coordinates());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_missing_nodes() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let output_path = PathBuf::from("./target/results/niue-230109-missing-nodes.osm.pbf");
+
+    // an empty index makes every node missing
+    let index = Arc::new(SparseMemLocationIndex::new());
+    let mut locations_on_ways = LocationsOnWays::new(index);
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let way = reader.elements()?.find(|e| e.is_way()).unwrap();
+    assert!(locations_on_ways.transform(way.clone()).is_err());
+    locations_on_ways.with_ignore_missing_nodes(true);
+    match locations_on_ways.transform(way)? {
+        Element::Way { way } => {
+            assert!(way.coordinates().is_empty());
+        }
+        _ => panic!("expected a way"),
+    }
+
+    let mut full = LocationsOnWays::new(Arc::new(SparseMemLocationIndex::new()));
+    full.with_ignore_missing_nodes(true);
+    full.run(&input_path, output_path, CompressionType::Zlib, 2)?;
+    assert!(!full.index().is_empty());
+    Ok(())
+}
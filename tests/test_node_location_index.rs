@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use osm_io::osm::index::btree_location_index::BtreeLocationIndex;
+use osm_io::osm::index::dense_mmap_location_index::DenseMmapLocationIndex;
+use osm_io::osm::index::node_location_index::{fill, NodeLocationIndex, parallel_fill, way_coordinates};
+use osm_io::osm::index::sparse_mem_location_index::SparseMemLocationIndex;
+use osm_io::osm::model::element::Element;
+use osm_io::osm::pbf;
+
+use crate::common::read_fixture_analysis;
+
+#[allow(dead_code)]
+mod common;
+
+fn verify_index(index: &dyn NodeLocationIndex, reader: &pbf::reader::Reader) -> Result<(), anyhow::Error> {
+    let fixture_analysis = read_fixture_analysis(&PathBuf::from("./tests/fixtures/niue-230109.osm.pbf.analysis.json"));
+    assert_eq!(index.len() as i64, fixture_analysis["data"]["count"]["nodes"].as_i64().unwrap());
+    for element in reader.elements()? {
+        match element {
+            Element::Node { node } => {
+                let coordinate = index.get(node.id()).unwrap();
+                assert_eq!(coordinate.lat7(), node.coordinate().lat7());
+                assert_eq!(coordinate.lon7(), node.coordinate().lon7());
+            }
+            Element::Way { way } => {
+                assert_eq!(way_coordinates(index, &way)?.len(), way.refs().len());
+            }
+            _ => {}
+        }
+    }
+    assert!(index.get(i64::MAX / 16).is_none());
+    Ok(())
+}
+
+#[test]
+fn test_node_location_index_fill() -> Result<(), anyhow::Error> {
+    let reader = pbf::reader::Reader::new(&PathBuf::from("./tests/fixtures/niue-230109.osm.pbf"))?;
+    let indexes: Vec<Box<dyn NodeLocationIndex>> = vec![
+        Box::new(SparseMemLocationIndex::new()),
+        Box::new(DenseMmapLocationIndex::temporary()?),
+        Box::new(BtreeLocationIndex::new()?),
+    ];
+    for index in indexes {
+        fill(index.as_ref(), reader.elements()?)?;
+        verify_index(index.as_ref(), &reader)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_node_location_index_parallel_fill() -> Result<(), anyhow::Error> {
+    common::setup();
+    let reader = pbf::reader::Reader::new(&PathBuf::from("./tests/fixtures/niue-230109.osm.pbf"))?;
+    let indexes: Vec<Arc<dyn NodeLocationIndex>> = vec![
+        Arc::new(SparseMemLocationIndex::new()),
+        Arc::new(DenseMmapLocationIndex::new(&PathBuf::from("./target/results/niue-230109.locations.idx"))?),
+        Arc::new(BtreeLocationIndex::new()?),
+    ];
+    for index in indexes {
+        parallel_fill(index.clone(), &reader, 4)?;
+        verify_index(index.as_ref(), &reader)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use std::fs;
+use std::path::PathBuf;
+
+use osm_io::osm::model::coordinate::Coordinate;
+use osm_io::osm::model::element::Element;
+use osm_io::osm::model::node::Node;
+use osm_io::osm::o5m;
+use osm_io::osm::o5m::file_type::FileType;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+use osm_io::osm::xml;
+
+use crate::common::read_fixture_analysis;
+
+#[allow(dead_code)]
+mod common;
+
+#[test]
+fn test_pbf_reader_o5m_writer_pipe() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let output_path = PathBuf::from("./target/results/niue-230109.o5m");
+    let fixture_analysis_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf.analysis.json");
+
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let mut writer = o5m::writer::Writer::from_file_info(
+        output_path.clone(),
+        reader.info().clone(),
+        FileType::O5m,
+    )?;
+    writer.write_header()?;
+    for element in reader.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+
+    let o5m_reader = o5m::reader::Reader::new(&output_path)?;
+    assert_eq!(o5m_reader.file_type(), &FileType::O5m);
+    assert_eq!(
+        o5m_reader.info().bounding_box().as_ref().map(|b| b.to_string()),
+        reader.info().bounding_box().as_ref().map(|b| b.to_string()),
+    );
+    assert_eq!(
+        o5m_reader.info().osmosis_replication_timestamp(),
+        reader.info().osmosis_replication_timestamp(),
+    );
+
+    let (mut nodes, mut ways, mut relations) = (0_i64, 0_i64, 0_i64);
+    let mut pbf_elements = reader.elements()?.filter(|e| !matches!(e, Element::Sentinel));
+    for o5m_element in o5m_reader.elements()? {
+        let pbf_element = pbf_elements.next().expect("o5m file has more elements than the pbf file");
+        common::assert_same_element(&pbf_element, &o5m_element);
+        match o5m_element {
+            Element::Node { .. } => nodes += 1,
+            Element::Way { .. } => ways += 1,
+            Element::Relation { .. } => relations += 1,
+            Element::Sentinel => {}
+        }
+    }
+    assert!(pbf_elements.next().is_none());
+
+    let fixture_analysis = read_fixture_analysis(&fixture_analysis_path);
+    assert_eq!(nodes, fixture_analysis["data"]["count"]["nodes"].as_i64().unwrap());
+    assert_eq!(ways, fixture_analysis["data"]["count"]["ways"].as_i64().unwrap());
+    assert_eq!(relations, fixture_analysis["data"]["count"]["relations"].as_i64().unwrap());
+    Ok(())
+}
+
+#[test]
+fn test_o5c_deletions() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/history-sample.osm");
+    let output_path = PathBuf::from("./target/results/history-sample.o5c");
+
+    let reader = xml::reader::Reader::new(&input_path)?;
+    let mut writer = o5m::writer::Writer::from_file_info(
+        output_path.clone(),
+        reader.info().clone(),
+        FileType::O5c,
+    )?;
+    writer.write_header()?;
+    writer.write_elements(reader.elements()?.collect())?;
+    writer.close()?;
+
+    let o5c_reader = o5m::reader::Reader::new(&output_path)?;
+    assert_eq!(o5c_reader.file_type(), &FileType::O5c);
+    let expected: Vec<Element> = reader.elements()?.collect();
+    let actual: Vec<Element> = o5c_reader.elements()?.collect();
+    assert_eq!(expected.len(), actual.len());
+    for (expected, actual) in expected.iter().zip(actual.iter()) {
+        common::assert_same_element(expected, actual);
+    }
+    match &actual[4] {
+        Element::Node { node } => {
+            assert_eq!(node.id(), 102);
+            assert!(!node.visible());
+        }
+        _ => panic!("expected a node"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_o5m_deletions_rejected() -> Result<(), anyhow::Error> {
+    common::setup();
+    let reader = xml::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample.osm"))?;
+    let mut writer = o5m::writer::Writer::from_file_info(
+        PathBuf::from("./target/results/history-sample.o5m"),
+        reader.info().clone(),
+        FileType::O5m,
+    )?;
+    writer.write_header()?;
+    assert!(writer.write_elements(reader.elements()?.collect()).is_err());
+    Ok(())
+}
+
+/// The fixture is the node example of the o5m format specification, which is encoded as
+/// osmconvert encodes it, between the reset and header datasets and the end of file marker
+#[test]
+fn test_o5m_spec_node() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/o5m-spec-node.o5m");
+    let output_path = PathBuf::from("./target/results/o5m-spec-node.o5m");
+
+    let reader = o5m::reader::Reader::new(&input_path)?;
+    let elements: Vec<Element> = reader.elements()?.collect();
+    assert_eq!(elements.len(), 1);
+    match &elements[0] {
+        Element::Node { node } => {
+            assert_eq!(node.id(), 125799);
+            assert_eq!(node.version(), 5);
+            assert_eq!(node.timestamp(), 1285874610000);
+            assert_eq!(node.changeset(), 5922698);
+            assert_eq!((node.uid(), node.user().as_str()), (45445, "UScha"));
+            assert_eq!((node.coordinate().lon7(), node.coordinate().lat7()), (87867843, 530749606));
+        }
+        _ => panic!("expected a node"),
+    }
+
+    let mut writer = o5m::writer::Writer::from_file_info(output_path.clone(), reader.info().clone(), FileType::O5m)?;
+    writer.write_header()?;
+    writer.write_elements(elements)?;
+    writer.close()?;
+    assert_eq!(fs::read(&output_path)?, fs::read(&input_path)?);
+    Ok(())
+}
+
+#[test]
+fn test_o5m_no_author() -> Result<(), anyhow::Error> {
+    common::setup();
+    let output_path = PathBuf::from("./target/results/no-author.o5m");
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/no-metadata.opl"))?;
+    let anonymous = Element::Node {
+        node: Node::new(1, 2, Coordinate::new(-19.05, -169.93), 1285874610000, 5922698, -1, String::new(), true, Vec::new())
+    };
+    let untimed = Element::Node {
+        node: Node::new(4, 3, Coordinate::new(-19.05, -169.93), -1, -1, -1, String::new(), true, Vec::new())
+    };
+    let mut writer = o5m::writer::Writer::from_file_info(output_path.clone(), reader.info().clone(), FileType::O5m)?;
+    writer.write_header()?;
+    writer.write_element(anonymous.clone())?;
+    writer.write_element(untimed.clone())?;
+    writer.write_elements(reader.elements()?.collect())?;
+    writer.close()?;
+
+    let mut expected = vec![anonymous, untimed];
+    expected.extend(reader.elements()?);
+    let actual: Vec<Element> = o5m::reader::Reader::new(&output_path)?.elements()?.collect();
+    assert_eq!(expected.len(), actual.len());
+    for (expected, actual) in expected.iter().zip(actual.iter()) {
+        common::assert_same_element(expected, actual);
+    }
+    for element in &actual[1..] {
+        if let Element::Node { node } = element {
+            assert_eq!(node.timestamp(), -1);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_not_o5m_input() {
+    let input_path = PathBuf::from("./tests/fixtures/history-sample.osm");
+    o5m::reader::Reader::new(&input_path).expect("not an o5m file");
+}
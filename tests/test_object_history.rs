@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+
+use osm_io::osm::history::object_history::{ObjectHistory, ObjectHistoryIterator};
+use osm_io::osm::history::version_change::ChangeKind;
+use osm_io::osm::model::element::Element;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+
+fn histories() -> Result<Vec<ObjectHistory>, anyhow::Error> {
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample.opl"))?;
+    Ok(ObjectHistoryIterator::new(reader.elements()?).collect())
+}
+
+#[test]
+fn test_object_history() -> Result<(), anyhow::Error> {
+    let histories = histories()?;
+    let ids: Vec<String> = histories.iter().map(|history| history.typed_id().to_string()).collect();
+    assert_eq!(ids, vec!["n100", "n101", "n102", "w200", "r300"]);
+
+    let n100 = &histories[0];
+    assert_eq!(n100.versions().len(), 2);
+    assert!(n100.version(2).is_some());
+    assert!(n100.version(3).is_none());
+    assert!(!n100.is_deleted());
+    assert_eq!(n100.contributors(), vec![(18203, "alice".to_string()), (18204, "bob & co".to_string())]);
+
+    let changes = n100.changes();
+    assert_eq!(changes[0].kind(), ChangeKind::Created);
+    assert_eq!(changes[0].user(), "alice");
+    assert!(changes[0].tag_diff().is_empty());
+    assert!(changes[0].node_move().is_none());
+    assert_eq!(changes[1].kind(), ChangeKind::Modified);
+    assert_eq!(changes[1].changeset(), 4511220);
+    assert_eq!(changes[1].uid(), 18204);
+    let added: Vec<(&str, &str)> = changes[1].tag_diff().added().iter()
+        .map(|tag| (tag.k().as_str(), tag.v().as_str()))
+        .collect();
+    assert_eq!(added, vec![("amenity", "cafe"), ("name", "Café \"Niue\" <main>")]);
+    assert!(changes[1].tag_diff().removed().is_empty());
+    let node_move = changes[1].node_move().as_ref().unwrap();
+    assert!(node_move.distance() > 0.5 && node_move.distance() < 2.0, "distance: {}", node_move.distance());
+
+    let n102 = &histories[2];
+    assert!(n102.is_deleted());
+    assert_eq!(n102.changes()[1].kind(), ChangeKind::Deleted);
+    assert!(n102.changes()[1].node_move().is_none());
+
+    let r300 = &histories[4];
+    assert_eq!(r300.changes()[0].kind(), ChangeKind::Created);
+    assert_eq!(r300.changes()[0].tag_diff().added().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_object_history_pbf() -> Result<(), anyhow::Error> {
+    let reader = pbf::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-niue-230109.osm.pbf"))?;
+    let elements = reader.elements()?.filter(|element| !matches!(element, Element::Sentinel)).count();
+    let mut versions = 0;
+    let mut previous = None;
+    let mut moved = 0;
+    for history in ObjectHistoryIterator::new(reader.elements()?) {
+        assert!(previous < Some(history.typed_id()));
+        previous = Some(history.typed_id());
+        versions += history.versions().len();
+        let changes = history.changes();
+        assert_eq!(changes.len(), history.versions().len());
+        moved += changes.iter().filter(|change| change.node_move().is_some()).count();
+    }
+    assert_eq!(versions, elements);
+    assert!(moved > 0);
+    Ok(())
+}
+
+#[test]
+fn test_object_history_new() -> Result<(), anyhow::Error> {
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample.opl"))?;
+    let elements: Vec<Element> = reader.elements()?.filter(|element| !matches!(element, Element::Sentinel)).collect();
+    assert!(ObjectHistory::new(Vec::new()).is_none());
+    assert!(ObjectHistory::new(vec![Element::Sentinel]).is_none());
+    // n100 v1, n100 v2
+    assert_eq!(ObjectHistory::new(elements[0..2].to_vec()).unwrap().versions().len(), 2);
+    // n100 and n101 are different objects
+    assert!(ObjectHistory::new(elements[0..3].to_vec()).is_none());
+    assert!(ObjectHistory::new(vec![elements[0].clone(), Element::Sentinel]).is_none());
+    Ok(())
+}
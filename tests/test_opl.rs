@@ -0,0 +1,111 @@
+use std::fs;
+use std::path::PathBuf;
+
+use osm_io::osm::model::element::Element;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+use osm_io::osm::xml;
+use osm_io::osm::xml::compression_type::CompressionType;
+
+use crate::common::read_fixture_analysis;
+
+#[allow(dead_code)]
+mod common;
+
+#[test]
+fn test_opl_reader() -> Result<(), anyhow::Error> {
+    let opl_elements: Vec<Element> = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample.opl"))?
+        .elements()?
+        .collect();
+    let xml_elements: Vec<Element> = xml::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample.osm"))?
+        .elements()?
+        .collect();
+    assert_eq!(opl_elements.len(), 7);
+    assert_eq!(opl_elements.len(), xml_elements.len());
+    for (expected, actual) in xml_elements.iter().zip(opl_elements.iter()) {
+        common::assert_same_element(expected, actual);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_opl_writer_fixture() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/history-sample.osm");
+    let output_path = PathBuf::from("./target/results/history-sample.opl");
+
+    let reader = xml::reader::Reader::new(&input_path)?;
+    let mut writer = opl::writer::Writer::from_file_info(
+        output_path.clone(),
+        reader.info().clone(),
+        CompressionType::Uncompressed,
+    )?;
+    writer.write_header()?;
+    writer.write_elements(reader.elements()?.collect())?;
+    writer.close()?;
+
+    assert_eq!(
+        fs::read_to_string(&output_path)?,
+        fs::read_to_string("./tests/fixtures/history-sample.opl")?,
+    );
+    Ok(())
+}
+
+#[test]
+fn test_opl_writer_missing_metadata() -> Result<(), anyhow::Error> {
+    let elements: Vec<Element> = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/no-metadata.opl"))?
+        .elements()?
+        .collect();
+    // as osmium, a missing changeset and uid are written as 0
+    assert_eq!(opl::writer::format_element(&elements[0], true)?, "n1 v0 dV c0 t i0 u T x-169.93 y-19.055434");
+    Ok(())
+}
+
+#[test]
+fn test_pbf_reader_opl_writer_pipe() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let output_path = PathBuf::from("./target/results/niue-230109.opl.gz");
+    let fixture_analysis_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf.analysis.json");
+
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let mut writer = opl::writer::Writer::from_file_info(
+        output_path.clone(),
+        reader.info().clone(),
+        CompressionType::Gzip,
+    )?;
+    writer.write_header()?;
+    for element in reader.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+
+    let opl_reader = opl::reader::Reader::new(&output_path)?;
+    assert_eq!(opl_reader.compression_type(), &CompressionType::Gzip);
+    let (mut nodes, mut ways, mut relations) = (0_i64, 0_i64, 0_i64);
+    let mut pbf_elements = reader.elements()?.filter(|e| !matches!(e, Element::Sentinel));
+    for opl_element in opl_reader.elements()? {
+        let pbf_element = pbf_elements.next().expect("opl file has more elements than the pbf file");
+        common::assert_same_element(&pbf_element, &opl_element);
+        match opl_element {
+            Element::Node { .. } => nodes += 1,
+            Element::Way { .. } => ways += 1,
+            Element::Relation { .. } => relations += 1,
+            Element::Sentinel => {}
+        }
+    }
+    assert!(pbf_elements.next().is_none());
+
+    let fixture_analysis = read_fixture_analysis(&fixture_analysis_path);
+    assert_eq!(nodes, fixture_analysis["data"]["count"]["nodes"].as_i64().unwrap());
+    assert_eq!(ways, fixture_analysis["data"]["count"]["ways"].as_i64().unwrap());
+    assert_eq!(relations, fixture_analysis["data"]["count"]["relations"].as_i64().unwrap());
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_non_existent_opl_input() {
+    let input_path = PathBuf::from("./tests/fixtures/non-existent.opl");
+    opl::reader::Reader::new(&input_path).expect("path doesn't exist");
+}
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use osm_io::osm::converters::iso8601_to_timestamp_millis;
+use osm_io::osm::model::bounding_box::BoundingBox;
+use osm_io::osm::model::changeset::Changeset;
+use osm_io::osm::model::element::Element;
+use osm_io::osm::model::tag::Tag;
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::compression_type::CompressionType;
+use osm_io::osm::stats::file_stats::FileStats;
+
+#[allow(dead_code)]
+mod common;
+
+fn changeset(id: i64) -> Result<Changeset, anyhow::Error> {
+    Ok(
+        Changeset::new(
+            id,
+            iso8601_to_timestamp_millis("2009-03-25T00:53:03Z")?,
+            Some(iso8601_to_timestamp_millis("2009-03-25T01:53:03Z")?),
+            false,
+            18203,
+            "alice".to_string(),
+            Some(BoundingBox::new(-169.93, -19.055434, -169.9218762, -19.05)),
+            3,
+            1,
+            vec![
+                Tag::new("comment".to_string(), format!("survey {}", id)),
+                Tag::new("created_by".to_string(), "JOSM".to_string()),
+            ],
+        )
+    )
+}
+
+#[test]
+fn test_pbf_changesets() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let output_path = PathBuf::from("./target/results/niue-230109-changesets.osm.pbf");
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let mut writer = pbf::writer::Writer::from_file_info(output_path.clone(), reader.info().clone(), CompressionType::Zlib)?;
+    writer.write_header()?;
+    let mut elements = 0;
+    for element in reader.elements()? {
+        if !matches!(element, Element::Sentinel) {
+            elements += 1;
+        }
+        writer.write_element(element)?;
+    }
+    let mut expected = Vec::new();
+    for id in 1..=8001 {
+        let changeset = changeset(id)?;
+        writer.write_changeset(changeset.clone())?;
+        expected.push(changeset);
+    }
+    let open = Changeset::new(8002, iso8601_to_timestamp_millis("2009-03-25T00:53:03Z")?, None, true, 0, String::new(), None, 0, 0, Vec::new());
+    writer.write_changeset(open.clone())?;
+    expected.push(open);
+    // a missing creation time stays missing
+    let untimed = Changeset::new(8003, -1, None, false, -1, String::new(), None, 0, 0, Vec::new());
+    writer.write_changeset(untimed.clone())?;
+    expected.push(untimed);
+    writer.close()?;
+
+    let reader = pbf::reader::Reader::new(&output_path)?;
+    let changesets: Vec<Changeset> = reader.changesets()?.collect();
+    assert_eq!(changesets, expected);
+    let read_elements = reader.elements()?
+        .filter(|element| !matches!(element, Element::Sentinel))
+        .count();
+    assert_eq!(read_elements, elements);
+
+    let file_stats = FileStats::calc(&reader)?;
+    assert_eq!((file_stats.changesets().count, file_stats.changesets().min_id, file_stats.changesets().max_id), (8003, 1, 8003));
+    assert!(file_stats.objects_ordered());
+
+    assert_eq!(pbf::reader::Reader::new(&input_path)?.changesets()?.count(), 0);
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use osm_io::osm::filter::reference_complete::{FilterCounts, ReferenceCompleteFilter};
+use osm_io::osm::filter::tag_filter::TagFilter;
+use osm_io::osm::model::element::Element;
+use osm_io::osm::model::relation::Member;
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::compression_type::CompressionType;
+
+#[allow(dead_code)]
+mod common;
+
+fn ids(elements: &[Element]) -> Vec<String> {
+    elements.iter()
+        .map(|element| {
+            match element {
+                Element::Node { node } => format!("n{}", node.id()),
+                Element::Way { way } => format!("w{}", way.id()),
+                Element::Relation { relation } => format!("r{}", relation.id()),
+                Element::Sentinel => "sentinel".to_string(),
+            }
+        })
+        .collect()
+}
+
+fn run(input_path: &Path, expressions: &[&str], omit_referenced_tags: bool) -> Result<(FilterCounts, Vec<Element>), anyhow::Error> {
+    let output_path = PathBuf::from(format!("./target/results/areas-{}.osm.pbf", expressions.join("-").replace('/', "_")));
+    let reader = pbf::reader::Reader::new(input_path)?;
+    let mut reference_complete = ReferenceCompleteFilter::new(TagFilter::parse(expressions)?);
+    reference_complete.with_omit_referenced_tags(omit_referenced_tags);
+    let counts = reference_complete.run(&reader, output_path.clone(), CompressionType::Zlib)?;
+    Ok((counts, common::read_pbf_elements(&output_path)?))
+}
+
+#[test]
+fn test_reference_complete() -> Result<(), anyhow::Error> {
+    let input_path = common::opl_fixture_to_pbf("areas", "areas-reference-complete.osm.pbf")?;
+
+    let (counts, elements) = run(&input_path, &["w/highway"], false)?;
+    assert_eq!(ids(&elements), vec!["n11", "n12", "w32"]);
+    assert_eq!(counts, FilterCounts { matched: 1, referenced: 2 });
+
+    let (counts, elements) = run(&input_path, &["r/landuse=forest"], false)?;
+    assert_eq!(ids(&elements), vec!["n1", "n2", "n3", "n4", "n5", "n6", "n7", "n8", "w20", "w21", "w22", "r10"]);
+    assert_eq!(counts, FilterCounts { matched: 1, referenced: 11 });
+
+    let (counts, elements) = run(&input_path, &["r/type=collection"], true)?;
+    assert_eq!(ids(&elements), vec!["n1", "n2", "n3", "n4", "n5", "n6", "n7", "n8", "w20", "w21", "w22", "r9", "r10"]);
+    assert_eq!(counts, FilterCounts { matched: 1, referenced: 12 });
+    for element in &elements {
+        if let Element::Relation { relation } = element {
+            if relation.id() == 10 {
+                assert!(relation.tags().is_empty());
+            } else {
+                assert!(!relation.tags().is_empty());
+            }
+        }
+    }
+
+    // the missing member way w99 of r12 is not an error
+    let (counts, elements) = run(&input_path, &["r/natural=wood"], false)?;
+    assert_eq!(ids(&elements), vec!["r12"]);
+    assert_eq!(counts, FilterCounts { matched: 1, referenced: 0 });
+    Ok(())
+}
+
+#[test]
+fn test_reference_complete_pbf() -> Result<(), anyhow::Error> {
+    common::setup();
+    let output_path = PathBuf::from("./target/results/niue-230109-cli-reference-complete.osm.pbf");
+    let status = Command::new(env!("CARGO_BIN_EXE_osmio"))
+        .args([
+            "tags-filter",
+            "./tests/fixtures/niue-230109.osm.pbf",
+            "-o",
+            output_path.to_str().unwrap(),
+            "-r",
+            "-t",
+            "w/highway",
+            "r/type=multipolygon,boundary",
+        ])
+        .status()?;
+    assert!(status.success());
+
+    let status = Command::new(env!("CARGO_BIN_EXE_osmio"))
+        .args(["tags-filter", "./tests/fixtures/niue-230109.osm.pbf", "-o", output_path.to_str().unwrap(), "-t", "w/highway"])
+        .status()?;
+    assert!(!status.success());
+
+    let tag_filter = TagFilter::parse(&["w/highway", "r/type=multipolygon,boundary"])?;
+    let mut nodes = HashSet::new();
+    let mut ways = HashSet::new();
+    let mut relations = HashSet::new();
+    let elements = common::read_pbf_elements(&output_path)?;
+    for element in &elements {
+        match element {
+            Element::Node { node } => {
+                nodes.insert(node.id());
+            }
+            Element::Way { way } => {
+                ways.insert(way.id());
+                assert!(way.refs().iter().all(|id| nodes.contains(id)));
+            }
+            Element::Relation { relation } => {
+                relations.insert(relation.id());
+            }
+            Element::Sentinel => {}
+        }
+        if !tag_filter.matches(element) {
+            match element {
+                Element::Node { node } => assert!(node.tags().is_empty()),
+                Element::Way { way } => assert!(way.tags().is_empty()),
+                Element::Relation { relation } => assert!(relation.tags().is_empty()),
+                Element::Sentinel => {}
+            }
+        }
+    }
+    let input: HashSet<String> = ids(&common::read_pbf_elements(&PathBuf::from("./tests/fixtures/niue-230109.osm.pbf"))?).into_iter().collect();
+    for element in &elements {
+        if let Element::Relation { relation } = element {
+            for member in relation.members() {
+                let (id, present) = match member {
+                    Member::Node { member } => (format!("n{}", member.id()), nodes.contains(&member.id())),
+                    Member::Way { member } => (format!("w{}", member.id()), ways.contains(&member.id())),
+                    Member::Relation { member } => (format!("r{}", member.id()), relations.contains(&member.id())),
+                };
+                assert!(present || !input.contains(&id), "missing member {}", id);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use osm_io::osm::filter::get_id::{ObjectType, TypedId};
+use osm_io::osm::model::element::Element;
+use osm_io::osm::model::relation::Member;
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::compression_type::CompressionType;
+use osm_io::osm::renumber::id_mapping::IdMapping;
+use osm_io::osm::renumber::renumberer::Renumber;
+
+#[allow(dead_code)]
+mod common;
+
+/// Element ids and references as strings, with the ids mapped by f
+fn describe(elements: &[Element], f: impl Fn(ObjectType, i64) -> i64) -> Vec<String> {
+    elements.iter()
+        .map(|element| {
+            match element {
+                Element::Node { node } => {
+                    format!("n{}", f(ObjectType::Node, node.id()))
+                }
+                Element::Way { way } => {
+                    let refs: Vec<String> = way.refs().iter().map(|id| format!("n{}", f(ObjectType::Node, *id))).collect();
+                    format!("w{} {}", f(ObjectType::Way, way.id()), refs.join(","))
+                }
+                Element::Relation { relation } => {
+                    let members: Vec<String> = relation.members().iter()
+                        .map(|member| {
+                            match member {
+                                Member::Node { member } => format!("n{}@{}", f(ObjectType::Node, member.id()), member.role()),
+                                Member::Way { member } => format!("w{}@{}", f(ObjectType::Way, member.id()), member.role()),
+                                Member::Relation { member } => format!("r{}@{}", f(ObjectType::Relation, member.id()), member.role()),
+                            }
+                        })
+                        .collect();
+                    format!("r{} {}", f(ObjectType::Relation, relation.id()), members.join(","))
+                }
+                Element::Sentinel => {
+                    String::new()
+                }
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_renumber() -> Result<(), anyhow::Error> {
+    let input_path = common::opl_fixture_to_pbf("areas", "areas-renumber.osm.pbf")?;
+    let output_path = PathBuf::from("./target/results/areas-renumbered.osm.pbf");
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let mut renumber = Renumber::new(IdMapping::new(1, 100, 1000)?);
+    let count = renumber.run(&reader, output_path.clone(), CompressionType::Zlib)?;
+
+    let input = common::read_pbf_elements(&input_path)?;
+    let output = common::read_pbf_elements(&output_path)?;
+    assert_eq!(count as usize, input.len());
+    assert!(pbf::reader::Reader::new(&output_path)?.info().optional("Sort.Type_then_ID"));
+
+    let ids: Vec<String> = output.iter().map(|element| TypedId::of(element).unwrap().to_string()).collect();
+    assert_eq!(&ids[0..3], &["n1", "n2", "n3"]);
+    assert_eq!(ids[11], "n12");
+    assert_eq!(&ids[12..15], &["w100", "w101", "w102"]);
+    assert_eq!(ids[18], "r1000");
+
+    // the references follow the mapping, the missing member w99 of r12 is numbered after the ways
+    let mapping = renumber.into_mapping();
+    assert_eq!(mapping.mapping(ObjectType::Way).get(99)?, Some(106));
+    assert_eq!(mapping.mapping(ObjectType::Way).next(), 107);
+    assert_eq!(mapping.mapping(ObjectType::Relation).get(9)?, Some(1000));
+    assert_eq!(mapping.mapping(ObjectType::Node).len(), 12);
+    let mapped = describe(&input, |object_type, id| mapping.mapping(object_type).get(id).unwrap().unwrap());
+    assert_eq!(describe(&output, |_, id| id), mapped);
+    assert!(mapped.contains(&"r1001 w100@outer,w101@outer,w102@inner".to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_renumber_mapping() -> Result<(), anyhow::Error> {
+    let input_path = common::opl_fixture_to_pbf("areas", "areas-renumber.osm.pbf")?;
+    let mapping_path = PathBuf::from("./target/results/areas-renumber-mapping");
+    if mapping_path.exists() {
+        fs::remove_dir_all(&mapping_path)?;
+    }
+
+    // load the mapping if it was saved before, and save it after renumbering
+    let run = |output_path: &Path| -> Result<(), anyhow::Error> {
+        let reader = pbf::reader::Reader::new(&input_path)?;
+        let mut renumber = Renumber::new(IdMapping::load(&mapping_path, 10, 20, 30)?);
+        renumber.run(&reader, output_path.to_path_buf(), CompressionType::Zlib)?;
+        renumber.mapping().save(&mapping_path)?;
+        Ok(())
+    };
+    let first_path = PathBuf::from("./target/results/areas-renumbered-first.osm.pbf");
+    run(&first_path)?;
+    assert!(mapping_path.join("nodes.map").exists());
+    let second_path = PathBuf::from("./target/results/areas-renumbered-second.osm.pbf");
+    run(&second_path)?;
+    let first = describe(&common::read_pbf_elements(&first_path)?, |_, id| id);
+    assert_eq!(first, describe(&common::read_pbf_elements(&second_path)?, |_, id| id));
+    assert_eq!(first[0], "n10");
+    // only the output of the run starting from an empty mapping is sorted
+    assert!(pbf::reader::Reader::new(&first_path)?.info().optional("Sort.Type_then_ID"));
+    assert!(!pbf::reader::Reader::new(&second_path)?.info().optional("Sort.Type_then_ID"));
+
+    let mapping = IdMapping::load(&mapping_path, 10, 20, 30)?;
+    assert_eq!(mapping.mapping(ObjectType::Node).get(14)?, Some(21));
+    assert_eq!(mapping.mapping(ObjectType::Node).next(), 22);
+    // objects added later are numbered after the saved mapping
+    let mut mapping = mapping;
+    assert_eq!(mapping.map(ObjectType::Node, 1000)?, 22);
+    assert_eq!(mapping.map(ObjectType::Node, 14)?, 21);
+    Ok(())
+}
+
+#[test]
+fn test_renumber_history() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/history-niue-230109.osm.pbf");
+    let output_path = PathBuf::from("./target/results/history-niue-230109-renumbered.osm.pbf");
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let mut renumber = Renumber::new(IdMapping::new(1, 1, 1)?);
+    renumber.run(&reader, output_path.clone(), CompressionType::Zlib)?;
+
+    let input = common::read_pbf_elements(&input_path)?;
+    let output = common::read_pbf_elements(&output_path)?;
+    assert_eq!(input.len(), output.len());
+    let mapping = renumber.into_mapping();
+    for (old, new) in input.iter().zip(output.iter()) {
+        let old_id = TypedId::of(old).unwrap();
+        let new_id = TypedId::of(new).unwrap();
+        assert_eq!(mapping.mapping(old_id.object_type()).get(old_id.id())?, Some(new_id.id()));
+    }
+    let nodes = mapping.mapping(ObjectType::Node);
+    let distinct_nodes: std::collections::HashSet<i64> = input.iter().filter(|e| e.is_node()).map(|e| TypedId::of(e).unwrap().id()).collect();
+    assert!(nodes.len() >= distinct_nodes.len());
+    assert_eq!(nodes.get(*distinct_nodes.iter().min().unwrap())?, Some(1));
+    Ok(())
+}
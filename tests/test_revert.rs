@@ -0,0 +1,109 @@
+use std::fs;
+use std::path::PathBuf;
+
+use osm_io::osm::apidb_dump;
+use osm_io::osm::filter::get_id::TypedId;
+use osm_io::osm::history::revert::{Revert, RevertConflict};
+use osm_io::osm::model::element::Element;
+use osm_io::osm::model::relation::Member;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+use osm_io::osm::xml::compression_type::CompressionType;
+
+#[allow(dead_code)]
+mod common;
+
+fn version_ids(elements: &[Element]) -> Vec<String> {
+    elements.iter()
+        .map(|element| {
+            let version = match element {
+                Element::Node { node } => node.version(),
+                Element::Way { way } => way.version(),
+                Element::Relation { relation } => relation.version(),
+                Element::Sentinel => 0,
+            };
+            format!("{} v{}", TypedId::of(element).unwrap(), version)
+        })
+        .collect()
+}
+
+fn assert_revert(revert: &Revert) {
+    assert_eq!(revert.changeset(), 5);
+    assert_eq!(version_ids(revert.modify()), vec!["n1 v2", "n3 v2", "w11 v3", "r20 v2"]);
+    assert_eq!(version_ids(revert.delete()), vec!["w10 v1", "n6 v3", "n2 v1"]);
+    assert_eq!(
+        revert.conflicts(),
+        &vec![
+            RevertConflict {
+                typed_id: "n4".parse().unwrap(),
+                version: 3,
+                changeset: 7,
+                user: "carol".to_string(),
+            }
+        ]
+    );
+    for element in revert.modify() {
+        match element {
+            Element::Node { node } if node.id() == 1 => {
+                assert_eq!(node.tags()[0].v(), "old");
+                assert_eq!(node.coordinate().lon(), 1.0);
+                assert!(node.visible());
+            }
+            Element::Node { node } => {
+                // undeleted
+                assert!(node.visible());
+                assert_eq!(node.coordinate().lon(), 3.0);
+            }
+            Element::Way { way } => {
+                assert_eq!(way.refs(), &vec![1, 3]);
+                assert_eq!(way.tags()[0].v(), "fence");
+            }
+            Element::Relation { relation } => {
+                assert_eq!(relation.members().len(), 1);
+                assert!(matches!(&relation.members()[0], Member::Way { member } if member.id() == 11));
+            }
+            Element::Sentinel => {}
+        }
+    }
+}
+
+#[test]
+fn test_revert() -> Result<(), anyhow::Error> {
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/revert.opl"))?;
+    assert_revert(&Revert::compute(5, reader.elements()?));
+
+    let untouched = Revert::compute(99, reader.elements()?);
+    assert!(untouched.modify().is_empty() && untouched.delete().is_empty() && untouched.conflicts().is_empty());
+
+    let input_path = common::opl_fixture_to_pbf("revert", "revert.osm.pbf")?;
+    assert_revert(&Revert::from_pbf(&pbf::reader::Reader::new(&input_path)?, 5)?);
+
+    let dump_path = PathBuf::from("./target/results/revert-apidb");
+    let mut writer = apidb_dump::write::writer::Writer::new(dump_path.clone(), 0)?;
+    for element in pbf::reader::Reader::new(&input_path)?.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+    let reader = apidb_dump::read::reader::Reader::new(dump_path, PathBuf::from("./target/results/revert-apidb-tmp"))?;
+    assert_revert(&Revert::from_apidb(&reader, 5)?);
+    Ok(())
+}
+
+#[test]
+fn test_revert_osc() -> Result<(), anyhow::Error> {
+    let input_path = common::opl_fixture_to_pbf("revert", "revert.osm.pbf")?;
+    let output_path = PathBuf::from("./target/results/revert-5.osc");
+    let revert = Revert::from_pbf(&pbf::reader::Reader::new(&input_path)?, 5)?;
+    assert!(revert.conflicts().iter().any(|conflict| conflict.to_string().contains("n4 v3 edited later in changeset 7 by carol")));
+    revert.write(output_path.clone(), CompressionType::Uncompressed)?;
+
+    let osc = fs::read_to_string(&output_path)?;
+    assert!(osc.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<osmChange version=\"0.6\" generator=\"osm-io\">\n  <modify>\n"));
+    assert!(osc.ends_with("  </delete>\n</osmChange>\n"));
+    assert_eq!(osc.matches("<modify>").count(), 1);
+    assert_eq!(osc.matches("<delete>").count(), 1);
+    assert!(osc.contains("<node id=\"3\" version=\"2\""));
+    assert!(osc.contains("<tag k=\"name\" v=\"old\"/>"));
+    assert!(osc.contains("<way id=\"10\" version=\"1\""));
+    Ok(())
+}
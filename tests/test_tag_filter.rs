@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use osm_io::osm::filter::tag_filter::{filter, TagFilter};
+use osm_io::osm::model::element::Element;
+use osm_io::osm::model::tag::Tag;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::compression_type::CompressionType;
+
+#[allow(dead_code)]
+mod common;
+
+fn matching_ids(tag_filter: &TagFilter) -> Result<Vec<i64>, anyhow::Error> {
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/areas.opl"))?;
+    Ok(
+        reader.elements()?
+            .filter(|element| tag_filter.matches(element))
+            .map(|element| {
+                match element {
+                    Element::Node { node } => node.id(),
+                    Element::Way { way } => way.id(),
+                    Element::Relation { relation } => relation.id(),
+                    Element::Sentinel => 0,
+                }
+            })
+            .collect()
+    )
+}
+
+#[test]
+fn test_tag_filter() -> Result<(), anyhow::Error> {
+    assert_eq!(matching_ids(&TagFilter::parse(&["building"])?)?, vec![30]);
+    assert_eq!(matching_ids(&TagFilter::parse(&["r/type=multipolygon"])?)?, vec![10, 11, 12]);
+    assert_eq!(matching_ids(&TagFilter::parse(&["w/highway", "r/type~^bound"])?)?, vec![32, 13]);
+    assert_eq!(matching_ids(&TagFilter::parse(&["w/!type", "r/type!=multipolygon,boundary"])?)?, vec![20, 21, 22, 30, 31, 32, 9, 14]);
+    assert_eq!(matching_ids(&TagFilter::parse(&["n/*"])?)?, Vec::<i64>::new());
+    assert_eq!(matching_ids(&TagFilter::parse(&["natural=w*"])?)?, vec![11, 12]);
+
+    let tag_filter = TagFilter::from_file(&PathBuf::from("./tests/fixtures/tag-expressions.txt"))?;
+    assert_eq!(tag_filter.expressions().len(), 3);
+    assert_eq!(tag_filter.expressions()[1].to_string(), "n/amenity=cafe");
+
+    let tag_filter = TagFilter::from_file(&PathBuf::from("./tests/fixtures/tag-expressions-hash.txt"))?;
+    assert_eq!(tag_filter.expressions().len(), 2);
+    assert_eq!(tag_filter.expressions()[0].to_string(), "colour=#ff0000");
+    assert_eq!(tag_filter.expressions()[1].to_string(), "name~^#");
+    assert!(tag_filter.expressions()[0].matches_tags(&[Tag::new("colour".to_string(), "#ff0000".to_string())]));
+    assert!(tag_filter.expressions()[1].matches_tags(&[Tag::new("name".to_string(), "#1".to_string())]));
+    Ok(())
+}
+
+#[test]
+fn test_filter_pbf() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let output_path = PathBuf::from("./target/results/niue-230109-filtered.osm.pbf");
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let tag_filter = TagFilter::from_file(&PathBuf::from("./tests/fixtures/tag-expressions.txt"))?;
+    let count = filter(&reader, &tag_filter, output_path.clone(), CompressionType::Zlib)?;
+    assert!(count > 0);
+
+    let output = pbf::reader::Reader::new(&output_path)?;
+    let mut written = 0;
+    for element in output.elements()? {
+        if let Element::Sentinel = element {
+            continue;
+        }
+        assert!(tag_filter.matches(&element));
+        written += 1;
+    }
+    assert_eq!(written, count);
+    Ok(())
+}
+
+#[test]
+fn test_tags_filter_command() -> Result<(), anyhow::Error> {
+    common::setup();
+    let output_path = PathBuf::from("./target/results/niue-230109-cli-filtered.osm.pbf");
+    let status = Command::new(env!("CARGO_BIN_EXE_osmio"))
+        .args([
+            "tags-filter",
+            "./tests/fixtures/niue-230109.osm.pbf",
+            "-o",
+            output_path.to_str().unwrap(),
+            "w/highway=primary,secondary,tertiary",
+            "n/amenity=cafe",
+            "r/type=multipolygon",
+        ])
+        .status()?;
+    assert!(status.success());
+
+    let reference_path = PathBuf::from("./target/results/niue-230109-cli-reference.osm.pbf");
+    let reader = pbf::reader::Reader::new(&PathBuf::from("./tests/fixtures/niue-230109.osm.pbf"))?;
+    let tag_filter = TagFilter::from_file(&PathBuf::from("./tests/fixtures/tag-expressions.txt"))?;
+    let count = filter(&reader, &tag_filter, reference_path, CompressionType::Zlib)?;
+    let written = pbf::reader::Reader::new(&output_path)?.elements()?
+        .filter(|element| !matches!(element, Element::Sentinel))
+        .count();
+    assert_eq!(written as u64, count);
+
+    let status = Command::new(env!("CARGO_BIN_EXE_osmio"))
+        .args(["tags-filter", "./tests/fixtures/niue-230109.osm.pbf", "-o", output_path.to_str().unwrap(), "highway!primary"])
+        .status()?;
+    assert!(!status.success());
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::PathBuf;
+
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::compression_type::CompressionType;
+use osm_io::osm::stats::tag_stats::{TagStats, TagStatsKind, TypeCounts};
+
+#[allow(dead_code)]
+mod common;
+
+fn areas_tag_stats() -> Result<TagStats, anyhow::Error> {
+    common::setup();
+    let path = PathBuf::from("./target/results/areas-tag-stats.osm.pbf");
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/areas.opl"))?;
+    let mut writer = pbf::writer::Writer::from_file_info(path.clone(), reader.info().clone(), CompressionType::Zlib)?;
+    writer.write_header()?;
+    for element in reader.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+    TagStats::calc(&pbf::reader::Reader::new(&path)?)
+}
+
+#[test]
+fn test_tag_stats() -> Result<(), anyhow::Error> {
+    let mut tag_stats = areas_tag_stats()?;
+    assert_eq!(tag_stats.key_count(), 9);
+    assert_eq!(tag_stats.key("type"), Some(&TypeCounts { all: 6, nodes: 0, ways: 0, relations: 6 }));
+    assert_eq!(tag_stats.tag("type", "multipolygon").unwrap().relations, 3);
+    assert_eq!(tag_stats.combination("type", "natural").unwrap().all, 2);
+    assert_eq!(tag_stats.combination("barrier", "area").unwrap().ways, 1);
+    assert!(tag_stats.key("name").is_none());
+
+    let keys: Vec<&str> = tag_stats.rows(TagStatsKind::Keys).iter().map(|row| row.key).collect();
+    assert_eq!(keys, vec!["type", "natural", "area", "barrier", "boundary", "building", "highway", "landuse", "route"]);
+
+    tag_stats.with_min_count(2);
+    let combinations: Vec<(&str, Option<&str>)> = tag_stats.rows(TagStatsKind::Combinations).iter()
+        .map(|row| (row.key, row.other))
+        .collect();
+    assert_eq!(combinations, vec![("natural", Some("type"))]);
+
+    tag_stats.with_min_count(0);
+    tag_stats.with_top(Some(2));
+    let mut csv = Vec::new();
+    tag_stats.write_csv_to(TagStatsKind::Tags, &mut csv)?;
+    assert_eq!(
+        String::from_utf8(csv)?,
+        "key,value,count_all,count_nodes,count_ways,count_relations\n\
+        type,multipolygon,3,0,0,3\n\
+        area,no,1,0,1,0\n"
+    );
+
+    let json = tag_stats.to_json();
+    assert_eq!(json["keys"].len(), 2);
+    assert_eq!(json["keys"][0]["key"], "type");
+    assert_eq!(json["combinations"][0]["key1"], "natural");
+    assert_eq!(json["combinations"][0]["key2"], "type");
+    Ok(())
+}
+
+#[test]
+fn test_tag_stats_csv() -> Result<(), anyhow::Error> {
+    common::setup();
+    let output_path = PathBuf::from("./target/results/niue-230109-tags.csv");
+    let mut tag_stats = TagStats::calc(&pbf::reader::Reader::new(&PathBuf::from("./tests/fixtures/niue-230109.osm.pbf"))?)?;
+    tag_stats.with_top(Some(10));
+    tag_stats.write_csv(TagStatsKind::Tags, &output_path)?;
+
+    let csv = fs::read_to_string(&output_path)?;
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines[0], "key,value,count_all,count_nodes,count_ways,count_relations");
+    let counts: Vec<u64> = lines[1..].iter()
+        .map(|line| line.split(',').rev().nth(3).unwrap().parse().unwrap())
+        .collect();
+    assert!(counts.windows(2).all(|pair| pair[0] >= pair[1]));
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use osm_io::osm::converters::iso8601_to_timestamp_millis;
+use osm_io::osm::filter::get_id::TypedId;
+use osm_io::osm::history::time_filter::TimeFilter;
+use osm_io::osm::model::element::Element;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::compression_type::CompressionType;
+
+#[allow(dead_code)]
+mod common;
+
+fn t(iso8601: &str) -> i64 {
+    iso8601_to_timestamp_millis(iso8601).unwrap()
+}
+
+fn version(element: &Element) -> i32 {
+    match element {
+        Element::Node { node } => node.version(),
+        Element::Way { way } => way.version(),
+        Element::Relation { relation } => relation.version(),
+        Element::Sentinel => 0,
+    }
+}
+
+fn versions(time_filter: &TimeFilter) -> Result<Vec<String>, anyhow::Error> {
+    let reader = opl::reader::Reader::new(&PathBuf::from("./tests/fixtures/history-sample.opl"))?;
+    Ok(
+        time_filter.filter(reader.elements()?)
+            .map(|element| format!("{} v{}", TypedId::of(&element).unwrap(), version(&element)))
+            .collect()
+    )
+}
+
+#[test]
+fn test_time_filter_snapshot() -> Result<(), anyhow::Error> {
+    assert!(versions(&TimeFilter::snapshot(t("2009-01-01T00:00:00Z")))?.is_empty());
+    assert_eq!(
+        versions(&TimeFilter::snapshot(t("2010-01-01T00:00:00Z")))?,
+        vec!["n100 v1", "n101 v1", "n102 v1", "w200 v1"]
+    );
+    // n100 v2 is current from its timestamp on
+    assert_eq!(
+        versions(&TimeFilter::snapshot(t("2010-05-01T10:00:00Z")))?,
+        vec!["n100 v2", "n101 v1", "n102 v1", "w200 v1"]
+    );
+    // n102 is deleted
+    assert_eq!(
+        versions(&TimeFilter::snapshot(t("2011-06-01T00:00:00Z")))?,
+        vec!["n100 v2", "n101 v1", "w200 v1"]
+    );
+    assert_eq!(
+        versions(&TimeFilter::snapshot(t("2013-01-01T00:00:00Z")))?,
+        vec!["n100 v2", "n101 v1", "w200 v1", "r300 v3"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_time_filter_range() -> Result<(), anyhow::Error> {
+    assert_eq!(
+        versions(&TimeFilter::range(t("2010-01-01T00:00:00Z"), t("2011-06-01T00:00:00Z"))?)?,
+        vec!["n100 v1", "n100 v2", "n101 v1", "n102 v1", "n102 v2", "w200 v1"]
+    );
+    assert_eq!(
+        versions(&TimeFilter::range(t("2011-06-01T00:00:00Z"), t("2012-02-02T02:02:02Z"))?)?,
+        vec!["n100 v2", "n101 v1", "n102 v2", "w200 v1"]
+    );
+    assert!(TimeFilter::range(t("2011-06-01T00:00:00Z"), t("2010-01-01T00:00:00Z")).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_time_filter_pbf() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/history-niue-230109.osm.pbf");
+    let output_path = PathBuf::from("./target/results/history-niue-230109-snapshot.osm.pbf");
+    let snapshot = TimeFilter::snapshot(t("2020-01-01T00:00:00Z"));
+    snapshot.run(&pbf::reader::Reader::new(&input_path)?, output_path.clone(), CompressionType::Zlib)?;
+
+    let reader = pbf::reader::Reader::new(&output_path)?;
+    assert!(!reader.info().required("HistoricalInformation"));
+    assert!(!reader.info().optional("HistoricalInformation"));
+    let mut ids = HashSet::new();
+    let mut count = 0;
+    for element in reader.elements()? {
+        let (timestamp, visible) = match &element {
+            Element::Node { node } => (node.timestamp(), node.visible()),
+            Element::Way { way } => (way.timestamp(), way.visible()),
+            Element::Relation { relation } => (relation.timestamp(), relation.visible()),
+            Element::Sentinel => continue,
+        };
+        assert!(visible);
+        assert!(timestamp <= t("2020-01-01T00:00:00Z"));
+        assert!(ids.insert(TypedId::of(&element).unwrap()));
+        count += 1;
+    }
+    assert!(count > 0);
+
+    let history_reader = pbf::reader::Reader::new(&input_path)?;
+    let expected = TimeFilter::snapshot(t("2020-01-01T00:00:00Z")).filter(history_reader.elements()?).count();
+    assert_eq!(count, expected);
+
+    let range_path = PathBuf::from("./target/results/history-niue-230109-range.osm.pbf");
+    let range = TimeFilter::range(t("2015-01-01T00:00:00Z"), t("2020-01-01T00:00:00Z"))?;
+    range.run(&history_reader, range_path.clone(), CompressionType::Zlib)?;
+    let range_reader = pbf::reader::Reader::new(&range_path)?;
+    assert!(range_reader.info().required("HistoricalInformation"));
+    let range_count = range_reader.elements()?.filter(|element| !matches!(element, Element::Sentinel)).count();
+    let history_count = history_reader.elements()?.filter(|element| !matches!(element, Element::Sentinel)).count();
+    assert!(range_count >= count);
+    assert!(range_count < history_count);
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use osm_io::osm::model::element::Element;
+use osm_io::osm::model::relation::Member;
+use osm_io::osm::xml::compression_type::CompressionType;
+use osm_io::osm::xml::reader::Reader;
+
+fn read_fixture(input_path: PathBuf) -> Result<Vec<Element>, anyhow::Error> {
+    let reader = Reader::new(&input_path)?;
+    let bounding_box = reader.info().bounding_box().clone().unwrap();
+    assert_eq!(bounding_box.left(), -169.96);
+    assert_eq!(bounding_box.bottom(), -19.06);
+    assert_eq!(bounding_box.right(), -169.91);
+    assert_eq!(bounding_box.top(), -19.04);
+    assert_eq!(reader.info().writingprogram(), &Some("osmium/1.14.0".to_string()));
+    Ok(reader.elements()?.collect())
+}
+
+#[test]
+fn test_xml_reader() -> Result<(), anyhow::Error> {
+    let elements = read_fixture(PathBuf::from("./tests/fixtures/history-sample.osm"))?;
+    assert_eq!(elements.len(), 7);
+    assert_eq!(elements.iter().filter(|e| e.is_node()).count(), 5);
+    assert_eq!(elements.iter().filter(|e| e.is_way()).count(), 1);
+    assert_eq!(elements.iter().filter(|e| e.is_relation()).count(), 1);
+
+    match &elements[1] {
+        Element::Node { node } => {
+            assert_eq!(node.id(), 100);
+            assert_eq!(node.version(), 2);
+            assert_eq!(node.timestamp(), 1272708000000);
+            assert_eq!(node.changeset(), 4511220);
+            assert_eq!(node.uid(), 18204);
+            assert_eq!(node.user(), "bob & co");
+            assert_eq!(node.coordinate().lat7(), -190554400);
+            assert_eq!(node.coordinate().lon7(), -1699218800);
+            assert!(node.visible());
+            assert_eq!(node.tags().len(), 2);
+            assert_eq!(node.tags()[1].v(), "Café \"Niue\" <main>");
+        }
+        _ => panic!("expected a node"),
+    }
+
+    match &elements[4] {
+        Element::Node { node } => {
+            assert_eq!(node.id(), 102);
+            assert!(!node.visible());
+        }
+        _ => panic!("expected a node"),
+    }
+
+    match &elements[5] {
+        Element::Way { way } => {
+            assert_eq!(way.refs(), &vec![100, 101, 102, 100]);
+            assert_eq!(way.tags()[0].k(), "landuse");
+        }
+        _ => panic!("expected a way"),
+    }
+
+    match &elements[6] {
+        Element::Relation { relation } => {
+            assert_eq!(relation.members().len(), 2);
+            match &relation.members()[0] {
+                Member::Way { member } => {
+                    assert_eq!(member.id(), 200);
+                    assert_eq!(member.role(), "outer");
+                }
+                _ => panic!("expected a way member"),
+            }
+            assert!(matches!(&relation.members()[1], Member::Node { .. }));
+        }
+        _ => panic!("expected a relation"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_compressed_xml_reader() -> Result<(), anyhow::Error> {
+    let uncompressed = read_fixture(PathBuf::from("./tests/fixtures/history-sample.osm"))?;
+    for path in ["./tests/fixtures/history-sample.osm.gz", "./tests/fixtures/history-sample.osm.bz2"] {
+        let input_path = PathBuf::from(path);
+        assert_ne!(CompressionType::from_path(&input_path), CompressionType::Uncompressed);
+        let compressed = read_fixture(input_path)?;
+        assert_eq!(uncompressed, compressed);
+    }
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_non_existent_xml_input() {
+    let input_path = PathBuf::from("./tests/fixtures/non-existent.osm");
+    Reader::new(&input_path).expect("path doesn't exist");
+}
@@ -0,0 +1,153 @@
+use std::path::PathBuf;
+
+use simple_logger::SimpleLogger;
+
+use osm_io::osm::model::element::Element;
+use osm_io::osm::opl;
+use osm_io::osm::pbf;
+use osm_io::osm::pbf::file_info::FileInfo;
+use osm_io::osm::xml;
+use osm_io::osm::xml::change_writer::{ChangeAction, ChangeWriter};
+use osm_io::osm::xml::compression_type::CompressionType;
+
+use crate::common::read_fixture_analysis;
+
+#[allow(dead_code)]
+mod common;
+
+#[test]
+fn test_pbf_reader_xml_writer_pipe() -> Result<(), anyhow::Error> {
+    SimpleLogger::new().init()?;
+    log::info!("Started pbf reader xml writer pipe test");
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf");
+    let output_path = PathBuf::from("./target/results/niue-230109.osm.gz");
+    let fixture_analysis_path = PathBuf::from("./tests/fixtures/niue-230109.osm.pbf.analysis.json");
+
+    let reader = pbf::reader::Reader::new(&input_path)?;
+    let mut writer = xml::writer::Writer::from_file_info(
+        output_path.clone(),
+        reader.info().clone(),
+        CompressionType::Gzip,
+    )?;
+    writer.write_header()?;
+    for element in reader.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+
+    let xml_reader = xml::reader::Reader::new(&output_path)?;
+    assert_eq!(
+        xml_reader.info().bounding_box().as_ref().map(|b| b.to_string()),
+        reader.info().bounding_box().as_ref().map(|b| b.to_string()),
+    );
+    let (mut nodes, mut ways, mut relations) = (0_i64, 0_i64, 0_i64);
+    for (pbf_element, xml_element) in reader.elements()?.zip(xml_reader.elements()?) {
+        assert_eq!(pbf_element, xml_element);
+        match (&pbf_element, &xml_element) {
+            (Element::Node { node: expected }, Element::Node { node: actual }) => {
+                nodes += 1;
+                assert_eq!(expected.coordinate().lat7(), actual.coordinate().lat7());
+                assert_eq!(expected.coordinate().lon7(), actual.coordinate().lon7());
+                assert_eq!(expected.tags(), actual.tags());
+                assert_eq!(expected.user(), actual.user());
+                assert_eq!(expected.timestamp(), actual.timestamp());
+            }
+            (Element::Way { way: expected }, Element::Way { way: actual }) => {
+                ways += 1;
+                assert_eq!(expected.refs(), actual.refs());
+                assert_eq!(expected.tags(), actual.tags());
+            }
+            (Element::Relation { relation: expected }, Element::Relation { relation: actual }) => {
+                relations += 1;
+                assert_eq!(expected.members(), actual.members());
+                assert_eq!(expected.tags(), actual.tags());
+            }
+            _ => {
+                panic!("element type mismatch");
+            }
+        }
+    }
+
+    let fixture_analysis = read_fixture_analysis(&fixture_analysis_path);
+    assert_eq!(nodes, fixture_analysis["data"]["count"]["nodes"].as_i64().unwrap());
+    assert_eq!(ways, fixture_analysis["data"]["count"]["ways"].as_i64().unwrap());
+    assert_eq!(relations, fixture_analysis["data"]["count"]["relations"].as_i64().unwrap());
+    log::info!("Finished pbf reader xml writer pipe test");
+    Ok(())
+}
+
+#[test]
+fn test_xml_writer_without_metadata() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/history-sample.osm");
+    let output_path = PathBuf::from("./target/results/history-sample-no-metadata.osm.bz2");
+
+    let reader = xml::reader::Reader::new(&input_path)?;
+    let mut writer = xml::writer::Writer::from_file_info(
+        output_path.clone(),
+        reader.info().clone(),
+        CompressionType::Bzip2,
+    )?;
+    writer.with_metadata(false);
+    writer.write_header()?;
+    for element in reader.elements()? {
+        writer.write_element(element)?;
+    }
+    writer.close()?;
+
+    let written: Vec<Element> = xml::reader::Reader::new(&output_path)?.elements()?.collect();
+    assert_eq!(written.len(), 7);
+    for element in &written {
+        if let Element::Node { node } = element {
+            assert_eq!(node.user(), "");
+            assert_eq!(node.changeset(), -1);
+        }
+    }
+    match &written[1] {
+        Element::Node { node } => {
+            assert_eq!(node.tags()[1].v(), "Café \"Niue\" <main>");
+        }
+        _ => panic!("expected a node"),
+    }
+    match &written[4] {
+        Element::Node { node } => {
+            assert!(!node.visible());
+        }
+        _ => panic!("expected a node"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_xml_writer_missing_metadata() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = PathBuf::from("./tests/fixtures/no-metadata.opl");
+    let output_path = PathBuf::from("./target/results/no-metadata.osm");
+    let change_path = PathBuf::from("./target/results/no-metadata.osc");
+
+    let mut writer = xml::writer::Writer::from_file_info(output_path.clone(), FileInfo::default(), CompressionType::Uncompressed)?;
+    let mut change_writer = ChangeWriter::new(change_path.clone(), CompressionType::Uncompressed)?;
+    writer.write_header()?;
+    change_writer.write_header()?;
+    for element in opl::reader::Reader::new(&input_path)?.elements()? {
+        writer.write_element(element.clone())?;
+        change_writer.write_change(ChangeAction::Create, element)?;
+    }
+    writer.close()?;
+    change_writer.close()?;
+
+    for path in [&output_path, &change_path] {
+        let text = std::fs::read_to_string(path)?;
+        assert!(text.contains("<node id=\"1\""));
+        for attribute in ["timestamp=", "changeset=", "uid=", "user="] {
+            assert!(!text.contains(attribute), "{} in {}", attribute, path.display());
+        }
+    }
+    for element in xml::reader::Reader::new(&output_path)?.elements()? {
+        if let Element::Node { node } = element {
+            assert_eq!((node.timestamp(), node.changeset(), node.uid()), (-1, -1, -1));
+        }
+    }
+    Ok(())
+}